@@ -0,0 +1,292 @@
+// checks that steady state frames don't allocate, and reports the average frame time
+// run with: cargo bench -p anchor-kit-core --bench frame_allocations
+// `--features layout-files` also checks a ui built from a layout document with bindings that change every frame
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Write,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use anchor_kit_core::{
+    anchor::AnchorPosition,
+    element::{
+        BarChartOptions, BarSeries, DividerOrientation, LineChartOptions, LineSeries, TableColumn,
+        TableOptions,
+    },
+    primitives::color::Color,
+    render::RenderList,
+    style::{Align, Insets, SizingPolicy, Style, TextStyle},
+    FrameInfo, UIState, UI,
+};
+
+// counts every allocation made by the process
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const WARMUP_FRAMES: u32 = 10;
+const MEASURED_FRAMES: u32 = 10_000;
+
+// similar to the winit-overlay example plus a chart and table panel, with a value that changes every frame
+fn build_overlay(ui: &mut UI, speed: &str, value: f32) {
+    let pill_style = Style {
+        background_color: Color {
+            r: 20,
+            g: 20,
+            b: 20,
+            a: 180,
+        },
+        border_width: 3.0,
+        border_radius: [25.0, 25.0, 25.0, 25.0],
+        padding: Insets {
+            top: 20,
+            right: 30,
+            bottom: 25,
+            left: 30,
+        },
+        ..Default::default()
+    };
+    let text_style = TextStyle {
+        font_size: 24.0,
+        text_color: Color {
+            r: 255,
+            g: 255,
+            b: 255,
+            a: 255,
+        },
+        ..Default::default()
+    };
+
+    ui.anchor(AnchorPosition::TopCenter, None, |ui| {
+        ui.pill(Some(pill_style), |ui| {
+            ui.text("anchor-kit", None, Some(text_style.clone()));
+        });
+    });
+    ui.anchor(AnchorPosition::BottomLeft, None, |ui| {
+        ui.pill(Some(pill_style), |ui| {
+            ui.flex_column(
+                Some(Style {
+                    align_x: Align::Middle,
+                    ..Default::default()
+                }),
+                |ui| {
+                    ui.text(speed, None, Some(text_style.clone()));
+                    ui.divider(DividerOrientation::Horizontal, 2, None);
+                    ui.text("MPH", None, Some(text_style.clone()));
+                },
+            );
+        });
+    });
+    ui.anchor(AnchorPosition::TopLeft, None, |ui| {
+        ui.pill(Some(pill_style), |ui| {
+            build_panel(ui, speed, value);
+        });
+    });
+    ui.anchor(AnchorPosition::BottomRight, None, |ui| {
+        ui.flex_row(None, |ui| {
+            for label in ["1", "2", "3", "4", "5", "6"] {
+                ui.pill(Some(pill_style), |ui| {
+                    ui.text(label, None, Some(text_style.clone()));
+                });
+            }
+        });
+    });
+}
+
+// the chart and table elements format labels and measure columns every frame
+fn build_panel(ui: &mut UI, speed: &str, value: f32) {
+    let chart_style = Style {
+        width: SizingPolicy::Fixed(300),
+        height: SizingPolicy::Fixed(150),
+        ..Default::default()
+    };
+    // the value stays below the other values, so the axes and their labels don't change from frame to frame
+    let value = value / 2.0;
+    let times = [0.0, 30.0, 60.0, 90.0];
+    let columns = [
+        TableColumn::new("name"),
+        TableColumn {
+            width: SizingPolicy::FillParent,
+            ..TableColumn::new("value")
+        },
+    ];
+    let rows = [["speed", speed], ["limit", "120"]];
+
+    ui.flex_column(None, |ui| {
+        ui.bar_chart(
+            &["a", "b", "c"],
+            &[
+                BarSeries::new(&[value, 120.0, -20.0]),
+                BarSeries::new(&[10.0, value, 30.0]),
+            ],
+            Some(BarChartOptions {
+                value_labels: true,
+                ..Default::default()
+            }),
+            Some(chart_style),
+        );
+        ui.line_chart(
+            &[LineSeries::new("speed", &times, &[0.0, 120.0, 60.0, value])],
+            Some(LineChartOptions {
+                legend: true,
+                gridlines: true,
+                unit: "mph",
+                ..Default::default()
+            }),
+            Some(chart_style),
+        );
+        ui.table(
+            &columns,
+            &rows,
+            Some(TableOptions {
+                zebra_color: Some(Color::rgb(40, 40, 55)),
+                ..Default::default()
+            }),
+            Some(Style {
+                width: SizingPolicy::Fixed(300),
+                ..Default::default()
+            }),
+        );
+    });
+}
+
+fn run_frames(
+    ui_state: &mut UIState,
+    render_list: &mut RenderList,
+    changing: bool,
+) -> (usize, f64) {
+    let mut speed = String::with_capacity(16);
+
+    let mut frame = |i: u32| {
+        speed.clear();
+        let value = if changing { i % 200 } else { 100 };
+        write!(speed, "{value}").unwrap();
+        ui_state.generate_frame_into(FrameInfo { size: [1280, 720] }, render_list, |ui| {
+            build_overlay(ui, &speed, value as f32)
+        });
+    };
+
+    for i in 0..WARMUP_FRAMES {
+        frame(i);
+    }
+
+    let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for i in 0..MEASURED_FRAMES {
+        frame(i);
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations_before;
+
+    (
+        allocations,
+        elapsed.as_secs_f64() * 1e6 / MEASURED_FRAMES as f64,
+    )
+}
+
+// the same kind of panel as a layout document, with all of its data coming from bindings
+#[cfg(feature = "layout-files")]
+fn run_layout_frames(ui_state: &mut UIState, render_list: &mut RenderList) -> (usize, f64) {
+    use anchor_kit_core::{
+        layout_file::{Bindings, LayoutDocument},
+        time_series::TimeSeries,
+    };
+
+    let document = LayoutDocument::from_ron(
+        r#"(nodes: [Anchor(position: TopLeft, children: [FlexColumn(children: [
+            Text(text: "speed: {speed} km/h"),
+            Progress(value: "{speed}", range: (0, 200), style: (width: Fixed(200), height: Fixed(8))),
+            Sparkline(values: "history", style: (width: Fixed(200), height: Fixed(30))),
+            BarChart(categories: ["a", "b", "c"], series: [(values: "bars")], options: (value_labels: true)),
+            LineChart(series: [(name: "speed", series: "speed")], unit: " km/h", options: (legend: true, range: (0, 200))),
+            Gauge(value: "{speed}", range: (0, 200), label: "{speed}"),
+            Donut(values: "bars", label: "{speed}"),
+            Heatmap(rows: 1, columns: 3, values: "bars", scale: Threshold((below: (r: 0, g: 255, b: 0, a: 255), thresholds: [(100, (r: 255, g: 0, b: 0, a: 255))]))),
+            Table(columns: [(header: "stat"), (header: "value")], rows: [["speed", "{speed}"], ["frame", "{frame}"]]),
+        ])])])"#,
+    )
+    .unwrap();
+    let mut bindings = Bindings::new();
+    let mut series = TimeSeries::new(32);
+    let mut history = [0.0; 32];
+
+    let mut frame = |i: u32| {
+        let value = (i % 200) as f32;
+        history[i as usize % history.len()] = value;
+        // the same times every frame, so the axes and their labels stay the same
+        series.clear();
+        for (time, value) in history.iter().enumerate() {
+            series.push(time as f64, *value);
+        }
+        bindings.set("speed", value);
+        bindings.set("frame", i % 1000);
+        bindings.set_values("history", &history);
+        bindings.set_values("bars", &[value, 200.0 - value, 50.0]);
+        bindings.set_time_series("speed", &series);
+        ui_state.generate_frame_into(FrameInfo { size: [1280, 720] }, render_list, |ui| {
+            document.build(ui, &bindings)
+        });
+    };
+
+    for i in 0..WARMUP_FRAMES {
+        frame(i);
+    }
+
+    let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for i in 0..MEASURED_FRAMES {
+        frame(i);
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations_before;
+
+    (
+        allocations,
+        elapsed.as_secs_f64() * 1e6 / MEASURED_FRAMES as f64,
+    )
+}
+
+fn main() {
+    let mut ui_state = UIState::new([1280, 720]);
+    let mut render_list = RenderList::default();
+
+    for (name, changing) in [("unchanged", false), ("changing", true)] {
+        let (allocations, frame_us) = run_frames(&mut ui_state, &mut render_list, changing);
+        println!("{name} frames: {allocations} allocations, {frame_us:.2}us per frame");
+        assert_eq!(
+            allocations, 0,
+            "{name} steady state frames should not allocate"
+        );
+    }
+
+    #[cfg(feature = "layout-files")]
+    {
+        let (allocations, frame_us) = run_layout_frames(&mut ui_state, &mut render_list);
+        println!("layout file frames: {allocations} allocations, {frame_us:.2}us per frame");
+        assert_eq!(
+            allocations, 0,
+            "layout file steady state frames should not allocate"
+        );
+    }
+}
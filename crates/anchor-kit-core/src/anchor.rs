@@ -1,5 +1,6 @@
 // nine core anchor positions for ui elements
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnchorPosition {
     TopLeft,
     TopCenter,
@@ -0,0 +1,399 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    anchor::AnchorPosition,
+    element::{Element, ElementTree, ElementType},
+    render::RenderList,
+};
+
+// effects can be combined, e.g. fade + slide for a pill that fades in while sliding from the edge of its anchor
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
+pub struct TransitionEffect {
+    pub fade: bool,
+    pub slide: Option<u32>, // distance in px to slide in from (or out to) the closest edge of the parent anchor
+    pub scale: Option<f32>, // starting scale (0.0 - 1.0) to grow from (or shrink to)
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+        }
+    }
+}
+
+// set in style to animate an element when it appears in (enter) or disappears from (exit) the element tree
+// elements are matched between frames by their id, so use `ui.key(...)` for elements that come and go
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
+pub struct Transition {
+    pub enter: Option<TransitionEffect>,
+    pub exit: Option<TransitionEffect>,
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Self {
+            enter: None,
+            exit: None,
+            duration: Duration::from_millis(200),
+            easing: Easing::EaseOut,
+        }
+    }
+}
+
+// per element animation values, set on the element tree between the layout and render passes
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct AnimationFrame {
+    pub effect: TransitionEffect,
+    pub progress: f32, // 0.0 = hidden, 1.0 = fully visible (after easing)
+    pub anchor: Option<AnchorPosition>, // closest parent anchor, used for the slide direction
+}
+
+#[derive(Clone, Copy)]
+struct TrackedElement {
+    index: usize, // index of the element in the tree it was last laid out in
+    anchor: Option<AnchorPosition>,
+}
+
+struct ExitingElement {
+    id: u64,
+    element: TrackedElement, // where the laid out copy of the element is in the previous frame's tree
+    transition: Transition,
+    started: Instant,
+}
+
+// keeps track of element identity between frames so that enter/ exit transitions can be played
+// all of the maps are double buffered and cleared instead of re-created, so steady state frames don't allocate
+#[derive(Default)]
+pub(crate) struct AnimationState {
+    entered: HashMap<u64, Instant>, // when each element with an enter transition first appeared
+    next_entered: HashMap<u64, Instant>,
+    last_frame: HashMap<u64, TrackedElement>, // elements with exit transitions from the previous frame
+    this_frame: HashMap<u64, TrackedElement>,
+    current_ids: HashSet<u64>,
+    exiting: Vec<ExitingElement>,
+    animating: bool, // true if any element was mid-transition in the last update
+}
+
+impl AnimationState {
+    // updates the tracked elements for the new frame and sets the animation values on the element tree
+    // exiting elements are copied from the previous frame's tree and appended to the root so they keep being rendered
+    // until their transition is done
+    pub fn update(&mut self, tree: &mut ElementTree, previous: &ElementTree, now: Instant) {
+        self.current_ids.clear();
+        self.next_entered.clear();
+        self.this_frame.clear();
+        self.animating = false;
+
+        let mut child = tree[ElementTree::ROOT].first_child;
+        while let Some(c) = child {
+            self.visit_element(tree, c, None, false, now);
+            child = tree[c].next_sibling;
+        }
+
+        // anything from the last frame that is no longer in the tree starts its exit transition
+        for (id, element) in self.last_frame.drain() {
+            if !self.current_ids.contains(&id) {
+                let transition = previous[element.index].style.transition.unwrap_or_default();
+                self.exiting.push(ExitingElement {
+                    id,
+                    element,
+                    transition,
+                    started: now,
+                });
+            }
+        }
+        // elements that came back (or finished exiting) should stop being rendered as exiting
+        let current_ids = &self.current_ids;
+        self.exiting
+            .retain(|e| !current_ids.contains(&e.id) && now - e.started < e.transition.duration);
+
+        std::mem::swap(&mut self.entered, &mut self.next_entered);
+        std::mem::swap(&mut self.last_frame, &mut self.this_frame);
+
+        for e in self.exiting.iter_mut() {
+            let index = tree.copy_subtree_from(previous, e.element.index, ElementTree::ROOT);
+            tree[index].animation = e.transition.exit.map(|effect| AnimationFrame {
+                effect,
+                progress: 1.0 - progress(e.transition, now - e.started),
+                anchor: e.element.anchor,
+            });
+            e.element.index = index; // the copy will be in the previous tree for the next frame
+            self.animating = true;
+        }
+    }
+
+    // frames can't be reused from the cache while any element is animating
+    pub fn is_animating(&self) -> bool {
+        self.animating
+    }
+
+    fn visit_element(
+        &mut self,
+        tree: &mut ElementTree,
+        index: usize,
+        anchor: Option<AnchorPosition>,
+        inside_exit_transition: bool, // children are kept alive as part of their parent's copy, so we don't track them seperately
+        now: Instant,
+    ) {
+        let id = tree[index].id;
+        self.current_ids.insert(id);
+        tree[index].animation = None;
+
+        let transition = tree[index].style.transition;
+        if let Some(Transition {
+            enter: Some(effect),
+            ..
+        }) = transition
+        {
+            let transition = transition.unwrap_or_default();
+            let started = *self.entered.get(&id).unwrap_or(&now);
+            self.next_entered.insert(id, started);
+
+            let elapsed = now - started;
+            if elapsed < transition.duration {
+                tree[index].animation = Some(AnimationFrame {
+                    effect,
+                    progress: progress(transition, elapsed),
+                    anchor,
+                });
+                self.animating = true;
+            }
+        }
+
+        let has_exit_transition = matches!(transition, Some(Transition { exit: Some(_), .. }));
+        if has_exit_transition && !inside_exit_transition {
+            self.this_frame.insert(id, TrackedElement { index, anchor });
+        }
+
+        let child_anchor = match tree[index]._type {
+            ElementType::Anchor(anchor_position) => Some(anchor_position),
+            _ => anchor,
+        };
+        let mut child = tree[index].first_child;
+        while let Some(c) = child {
+            self.visit_element(
+                tree,
+                c,
+                child_anchor,
+                inside_exit_transition || has_exit_transition,
+                now,
+            );
+            child = tree[c].next_sibling;
+        }
+    }
+}
+
+fn progress(transition: Transition, elapsed: Duration) -> f32 {
+    if transition.duration.is_zero() {
+        return 1.0;
+    }
+    let t = elapsed.as_secs_f32() / transition.duration.as_secs_f32();
+    transition.easing.apply(t)
+}
+
+// applies the animation values to all primitives rendered by an animated element (and its children)
+// elements sliding in from the left or top edge of the screen start partly off screen, primitive positions can't be
+// negative so those are clipped to the screen (or left out until they are fully on it, if they can't be cut)
+pub(crate) fn apply_animation(
+    animation: &AnimationFrame,
+    element: &Element,
+    render_list: &mut RenderList,
+) {
+    let progress = animation.progress.clamp(0.0, 1.0);
+    let effect = animation.effect;
+
+    // slide offset is based on the edge of the anchor the element is attached to
+    let (mut dx, mut dy) = (0.0, 0.0);
+    if let Some(distance) = effect.slide {
+        let offset = distance as f32 * (1.0 - progress);
+        (dx, dy) = match animation.anchor {
+            Some(AnchorPosition::TopLeft)
+            | Some(AnchorPosition::MiddleLeft)
+            | Some(AnchorPosition::BottomLeft) => (-offset, 0.0),
+            Some(AnchorPosition::TopRight)
+            | Some(AnchorPosition::MiddleRight)
+            | Some(AnchorPosition::BottomRight) => (offset, 0.0),
+            Some(AnchorPosition::TopCenter) => (0.0, -offset),
+            _ => (0.0, offset), // bottom center, middle center and un-anchored elements come from below
+        };
+    }
+
+    // scaling is done around the center of the animated element
+    let scale = match effect.scale {
+        Some(from) => from + (1.0 - from) * progress,
+        None => 1.0,
+    };
+    let [ex, ey] = element.frame_position.unwrap_or([0, 0]);
+    let center = [
+        ex as f32 + element.size[0] as f32 / 2.0,
+        ey as f32 + element.size[1] as f32 / 2.0,
+    ];
+
+    let transform_position = |position: [u32; 2]| -> [f32; 2] {
+        [
+            center[0] + (position[0] as f32 - center[0]) * scale + dx,
+            center[1] + (position[1] as f32 - center[1]) * scale + dy,
+        ]
+    };
+    let transform_point = |[x, y]: [f32; 2]| -> [f32; 2] {
+        [
+            center[0] + (x - center[0]) * scale + dx,
+            center[1] + (y - center[1]) * scale + dy,
+        ]
+    };
+    let transform_size = |size: [u32; 2]| -> [f32; 2] { size.map(|s| s as f32 * scale) };
+
+    let opacity = if effect.fade { progress } else { 1.0 };
+
+    render_list.rectangles.retain_mut(|rect| {
+        let Some((position, size)) =
+            clip_to_screen(transform_position(rect.position), transform_size(rect.size))
+        else {
+            return false;
+        };
+        rect.position = position;
+        rect.size = size;
+        for r in rect.style.border_radius.iter_mut() {
+            *r *= scale;
+        }
+        rect.style.border_width *= scale;
+        rect.style.background_color.a = (rect.style.background_color.a as f32 * opacity) as u8;
+        rect.style.border_color.a = (rect.style.border_color.a as f32 * opacity) as u8;
+        true
+    });
+    // images would be squashed instead of cut, so they only show once they are fully on screen
+    render_list.images.retain_mut(|image| {
+        let position = transform_position(image.rectangle.position);
+        let size = transform_size(image.rectangle.size);
+        match clip_to_screen(position, size) {
+            Some((clipped, _)) if position[0] >= 0.0 && position[1] >= 0.0 => {
+                image.rectangle.position = clipped;
+                image.rectangle.size = size.map(|s| s as u32);
+            }
+            _ => return false,
+        }
+        for r in image.rectangle.style.border_radius.iter_mut() {
+            *r *= scale;
+        }
+        image.rectangle.style.border_width *= scale;
+        image.rectangle.style.border_color.a =
+            (image.rectangle.style.border_color.a as f32 * opacity) as u8;
+        image.opacity *= opacity;
+        true
+    });
+    // text is moved instead of cut, the renderers clip it to the part of its box that is still on screen
+    render_list.text.retain_mut(|text| {
+        let position = transform_position(text.position);
+        let Some((clipped, size)) = clip_to_screen(position, transform_size(text.size)) else {
+            return false;
+        };
+        text.offset = [
+            text.offset[0] * scale + position[0] - clipped[0] as f32,
+            text.offset[1] * scale + position[1] - clipped[1] as f32,
+        ];
+        text.position = clipped;
+        text.size = size;
+        text.text_style.font_size *= scale;
+        text.text_style.line_height *= scale;
+        text.text_style.text_color.a = (text.text_style.text_color.a as f32 * opacity) as u8;
+        true
+    });
+    for polyline in render_list.polylines.iter_mut() {
+        for point in polyline.points.iter_mut() {
+            *point = transform_point(*point);
+        }
+        polyline.stroke.width *= scale;
+        if let Some(dash) = polyline.stroke.dash.as_mut() {
+            *dash = dash.map(|length| length * scale);
+        }
+        polyline.stroke.color.a = (polyline.stroke.color.a as f32 * opacity) as u8;
+        if let Some(area) = polyline.area.as_mut() {
+            area.baseline = transform_point([0.0, area.baseline])[1];
+            area.color.a = (area.color.a as f32 * opacity) as u8;
+        }
+    }
+    // the transform is a uniform scale plus an offset, so it can be applied to the commands directly (arcs included)
+    let origin = transform_point([0.0, 0.0]);
+    for path in render_list.paths.iter_mut() {
+        for command in path.commands.iter_mut() {
+            *command = command.transformed(scale, origin);
+        }
+        if let Some(fill) = path.fill.as_mut() {
+            fill.a = (fill.a as f32 * opacity) as u8;
+        }
+        if let Some(stroke) = path.stroke.as_mut() {
+            stroke.width *= scale;
+            if let Some(dash) = stroke.dash.as_mut() {
+                *dash = dash.map(|length| length * scale);
+            }
+            stroke.color.a = (stroke.color.a as f32 * opacity) as u8;
+        }
+    }
+    for arc in render_list.arcs.iter_mut() {
+        arc.center = transform_point(arc.center);
+        arc.radius *= scale;
+        arc.thickness *= scale;
+        arc.color.a = (arc.color.a as f32 * opacity) as u8;
+    }
+    // like images, cutting a grid would squash its cells
+    render_list.cell_grids.retain_mut(|grid| {
+        let position = transform_position(grid.position);
+        let size = transform_size(grid.size);
+        match clip_to_screen(position, size) {
+            Some((clipped, _)) if position[0] >= 0.0 && position[1] >= 0.0 => {
+                grid.position = clipped;
+                grid.size = size.map(|s| s as u32);
+            }
+            _ => return false,
+        }
+        grid.gap = (grid.gap as f32 * scale).round() as u32;
+        grid.cell_radius *= scale;
+        for color in grid.colors.iter_mut() {
+            color.a = (color.a as f32 * opacity) as u8;
+        }
+        true
+    });
+}
+
+// the part of the box that is right of and below the screen's top left corner, None if none of it is
+fn clip_to_screen(position: [f32; 2], size: [f32; 2]) -> Option<([u32; 2], [u32; 2])> {
+    let end = [position[0] + size[0], position[1] + size[1]];
+    if end[0] <= 0.0 || end[1] <= 0.0 {
+        return None;
+    }
+    let start = position.map(|p| p.max(0.0));
+    Some((
+        start.map(|p| p as u32),
+        [(end[0] - start[0]) as u32, (end[1] - start[1]) as u32],
+    ))
+}
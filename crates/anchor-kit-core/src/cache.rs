@@ -0,0 +1,315 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::{
+    animation::{Transition, TransitionEffect},
+    element::{ElementTree, ElementType},
+    primitives::{path::PathCommand, polyline::Stroke},
+    style::{Style, TextStyle},
+};
+
+// the content hash covers everything that affects how an element (and its children) is measured or rendered
+// it is computed bottom up by the UI builder, so children hashes are always set before their parent's
+pub(crate) fn hash_element(tree: &ElementTree, index: usize) -> u64 {
+    let element = &tree[index];
+    let mut hasher = DefaultHasher::new();
+    hash_element_type(tree, &element._type, &mut hasher);
+    hash_style(&element.style, &mut hasher);
+    match &element.text_style {
+        Some(text_style) => {
+            true.hash(&mut hasher);
+            hash_text_style(text_style, &mut hasher);
+        }
+        None => false.hash(&mut hasher),
+    }
+    element.child_count.hash(&mut hasher);
+    for c in tree.children(index) {
+        tree[c].id.hash(&mut hasher);
+        tree[c].hash.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn hash_element_type(tree: &ElementTree, element_type: &ElementType, hasher: &mut DefaultHasher) {
+    std::mem::discriminant(element_type).hash(hasher);
+    match element_type {
+        ElementType::Root | ElementType::FlexRow | ElementType::FlexColumn | ElementType::Pill => {}
+        ElementType::Anchor(anchor_position) => anchor_position.hash(hasher),
+        ElementType::Text(span) => tree.text(*span).hash(hasher), // hash the text itself since spans move between frames
+        ElementType::Image(texture_id) => texture_id.hash(hasher),
+        ElementType::Divider(orientation, thickness) => {
+            orientation.hash(hasher);
+            thickness.hash(hasher);
+        }
+        ElementType::Progress(progress) => {
+            progress.fraction.to_bits().hash(hasher);
+            progress.orientation.hash(hasher);
+            progress.fill_color.hash(hasher);
+            progress.segments.hash(hasher);
+            progress.segment_gap.hash(hasher);
+        }
+        ElementType::Sparkline(sparkline) => {
+            for value in tree.data(sparkline.data) {
+                value.to_bits().hash(hasher);
+            }
+            sparkline.range.map(f32::to_bits).hash(hasher);
+            sparkline.line_color.hash(hasher);
+            sparkline.line_width.to_bits().hash(hasher);
+            sparkline.fill_color.hash(hasher);
+            sparkline.dot_radius.map(f32::to_bits).hash(hasher);
+        }
+        ElementType::Path(path) => {
+            for command in tree.path_commands(path.commands) {
+                hash_path_command(command, hasher);
+            }
+            path.viewbox.map(f32::to_bits).hash(hasher);
+            path.fill.hash(hasher);
+            path.fill_rule.hash(hasher);
+            match &path.stroke {
+                Some(stroke) => {
+                    true.hash(hasher);
+                    hash_stroke(stroke, hasher);
+                }
+                None => false.hash(hasher),
+            }
+        }
+        ElementType::BarChart(chart) => {
+            for value in tree.data(chart.values) {
+                value.to_bits().hash(hasher);
+            }
+            tree.colors(chart.colors).hash(hasher);
+            chart.labels.map(|span| tree.text(span)).hash(hasher);
+            chart.category_count.hash(hasher);
+            chart.range.map(f32::to_bits).hash(hasher);
+            chart.orientation.hash(hasher);
+            chart.stacked.hash(hasher);
+            chart.category_gap.to_bits().hash(hasher);
+            chart.bar_radius.to_bits().hash(hasher);
+            chart.value_labels.hash(hasher);
+            chart.value_decimals.hash(hasher);
+            chart.axis_color.hash(hasher);
+        }
+        ElementType::LineChart(chart) => {
+            for series in tree.chart_series(chart.series) {
+                for value in tree.data(series.points) {
+                    value.to_bits().hash(hasher);
+                }
+                tree.text(series.name).hash(hasher);
+                series.color.hash(hasher);
+                series.fill.hash(hasher);
+            }
+            chart.time_range.map(f64::to_bits).hash(hasher);
+            chart.range.map(f32::to_bits).hash(hasher);
+            chart.time_format.hash(hasher);
+            chart.unit.map(|span| tree.text(span)).hash(hasher);
+            chart.line_width.to_bits().hash(hasher);
+            chart.gridlines.hash(hasher);
+            chart.legend.hash(hasher);
+            chart.axis_color.hash(hasher);
+        }
+        ElementType::Gauge(gauge) => {
+            gauge.fraction.to_bits().hash(hasher);
+            gauge.angles.map(f32::to_bits).hash(hasher);
+            gauge.thickness.to_bits().hash(hasher);
+            gauge.track_color.hash(hasher);
+            gauge.fill_color.hash(hasher);
+            gauge.rounded_caps.hash(hasher);
+            gauge.label.map(|span| tree.text(span)).hash(hasher);
+        }
+        ElementType::Donut(donut) => {
+            for value in tree.data(donut.values) {
+                value.to_bits().hash(hasher);
+            }
+            tree.colors(donut.colors).hash(hasher);
+            donut.total.to_bits().hash(hasher);
+            donut.start_angle.to_bits().hash(hasher);
+            donut.thickness.to_bits().hash(hasher);
+            donut.gap.to_bits().hash(hasher);
+            donut.rounded_caps.hash(hasher);
+            donut.track_color.hash(hasher);
+            donut.label.map(|span| tree.text(span)).hash(hasher);
+        }
+        ElementType::Heatmap(heatmap) => {
+            tree.colors(heatmap.colors).hash(hasher);
+            heatmap.rows.hash(hasher);
+            heatmap.columns.hash(hasher);
+            heatmap.cell_size.hash(hasher);
+            heatmap.gap.hash(hasher);
+            heatmap.cell_radius.to_bits().hash(hasher);
+            heatmap.row_labels.map(|span| tree.text(span)).hash(hasher);
+            heatmap
+                .column_labels
+                .map(|span| tree.text(span))
+                .hash(hasher);
+        }
+        ElementType::Table(table) => {
+            for column in tree.table_columns(table.columns) {
+                column.width.hash(hasher);
+                column.align.hash(hasher);
+            }
+            tree.text(table.cells).hash(hasher);
+            table.rows.hash(hasher);
+            table.header.hash(hasher);
+            table.cell_padding.hash(hasher);
+            table.header_color.hash(hasher);
+            table.header_background.hash(hasher);
+            table.bold_header.hash(hasher);
+            table.zebra_color.hash(hasher);
+            table.divider_thickness.hash(hasher);
+            table.divider_color.hash(hasher);
+        }
+    }
+}
+
+fn hash_path_command(command: &PathCommand, hasher: &mut DefaultHasher) {
+    std::mem::discriminant(command).hash(hasher);
+    let hash_point =
+        |point: &[f32; 2], hasher: &mut DefaultHasher| point.map(f32::to_bits).hash(hasher);
+    match command {
+        PathCommand::MoveTo(to) | PathCommand::LineTo(to) => hash_point(to, hasher),
+        PathCommand::QuadTo { control, to } => {
+            hash_point(control, hasher);
+            hash_point(to, hasher);
+        }
+        PathCommand::CubicTo {
+            control_1,
+            control_2,
+            to,
+        } => {
+            hash_point(control_1, hasher);
+            hash_point(control_2, hasher);
+            hash_point(to, hasher);
+        }
+        PathCommand::ArcTo {
+            radii,
+            x_rotation,
+            large_arc,
+            sweep,
+            to,
+        } => {
+            hash_point(radii, hasher);
+            x_rotation.to_bits().hash(hasher);
+            large_arc.hash(hasher);
+            sweep.hash(hasher);
+            hash_point(to, hasher);
+        }
+        PathCommand::Close => {}
+    }
+}
+
+fn hash_stroke(stroke: &Stroke, hasher: &mut DefaultHasher) {
+    stroke.width.to_bits().hash(hasher);
+    stroke.color.hash(hasher);
+    stroke.join.hash(hasher);
+    stroke.cap.hash(hasher);
+    stroke.dash.map(|dash| dash.map(f32::to_bits)).hash(hasher);
+}
+
+// floats can't derive Hash, so we hash their bits instead
+fn hash_style(style: &Style, hasher: &mut DefaultHasher) {
+    style.padding.hash(hasher);
+    style.margin.hash(hasher);
+    style.width.hash(hasher);
+    style.height.hash(hasher);
+    style.align_x.hash(hasher);
+    style.align_y.hash(hasher);
+    style.justify_x.hash(hasher);
+    style.justify_y.hash(hasher);
+    style.background_color.hash(hasher);
+    style.border_color.hash(hasher);
+    for r in style.border_radius {
+        r.to_bits().hash(hasher);
+    }
+    style.border_width.to_bits().hash(hasher);
+    match &style.transition {
+        Some(transition) => {
+            true.hash(hasher);
+            hash_transition(transition, hasher);
+        }
+        None => false.hash(hasher),
+    }
+}
+
+fn hash_text_style(text_style: &TextStyle, hasher: &mut DefaultHasher) {
+    text_style.font_size.to_bits().hash(hasher);
+    text_style.line_height.to_bits().hash(hasher);
+    text_style.font_family.hash(hasher);
+    text_style.font_weight.hash(hasher);
+    text_style.font_style.hash(hasher);
+    text_style.text_color.hash(hasher);
+}
+
+fn hash_transition(transition: &Transition, hasher: &mut DefaultHasher) {
+    for effect in [&transition.enter, &transition.exit] {
+        match effect {
+            Some(TransitionEffect { fade, slide, scale }) => {
+                true.hash(hasher);
+                fade.hash(hasher);
+                slide.hash(hasher);
+                scale.map(f32::to_bits).hash(hasher);
+            }
+            None => false.hash(hasher),
+        }
+    }
+    transition.duration.hash(hasher);
+    transition.easing.hash(hasher);
+}
+
+// copies the measured sizes from the previous frame's tree for any subtree that hasn't changed
+// the measure pass skips elements that already have a size for the same constraints
+pub(crate) fn reuse_previous_measurements(
+    tree: &mut ElementTree,
+    index: usize,
+    previous: &ElementTree,
+    previous_index: usize,
+) {
+    if tree[index].hash == previous[previous_index].hash {
+        copy_measurements(tree, index, previous, previous_index);
+        return;
+    }
+
+    let mut previous_child = previous[previous_index].first_child;
+    let mut child = tree[index].first_child;
+    while let Some(c) = child {
+        // children usually stay at the same position, so check there first before searching by id
+        let id = tree[c].id;
+        let matching_child = match previous_child {
+            Some(p) if previous[p].id == id => Some(p),
+            _ => previous
+                .children(previous_index)
+                .find(|p| previous[*p].id == id),
+        };
+        if let Some(p) = matching_child {
+            reuse_previous_measurements(tree, c, previous, p);
+        }
+
+        previous_child = matching_child.and_then(|p| previous[p].next_sibling);
+        child = tree[c].next_sibling;
+    }
+}
+
+// the hashes are equal here, so both trees have the same shape
+fn copy_measurements(
+    tree: &mut ElementTree,
+    index: usize,
+    previous: &ElementTree,
+    previous_index: usize,
+) {
+    tree[index].size = previous[previous_index].size;
+    tree[index].measured_constraints = previous[previous_index].measured_constraints;
+    // table column widths are measured into the tree's buffer, so they need copying along with the size
+    if let (ElementType::Table(mut table), ElementType::Table(previous_table)) =
+        (tree[index]._type, previous[previous_index]._type)
+    {
+        let widths = previous.table_column_widths(previous_table.widths);
+        table.widths = tree.push_table_column_widths(widths.iter().copied());
+        tree[index]._type = ElementType::Table(table);
+    }
+
+    let mut previous_child = previous[previous_index].first_child;
+    let mut child = tree[index].first_child;
+    while let (Some(c), Some(p)) = (child, previous_child) {
+        copy_measurements(tree, c, previous, p);
+        child = tree[c].next_sibling;
+        previous_child = previous[p].next_sibling;
+    }
+}
@@ -0,0 +1,465 @@
+use std::fmt::Write;
+
+use crate::{
+    element::{
+        BarChart, ChartSeries, DividerOrientation, ElementTree, Heatmap, LineChart, TextSpan,
+        TimeFormat,
+    },
+    measure::{measure_text, TextMeasurement},
+    style::TextStyle,
+};
+
+// shared by the measure and render passes, so the space reserved for labels matches where they are drawn
+
+// space between labels and what they label, and the width of axis lines
+// terminals can't draw thin lines, so charts there leave out the axis lines and use whole cells for the gaps
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ChartMetrics {
+    pub(crate) gap: [u32; 2], // x, y
+    pub(crate) axis_width: u32,
+}
+
+impl ChartMetrics {
+    pub(crate) fn new(text_measurement: TextMeasurement) -> Self {
+        match text_measurement {
+            TextMeasurement::FontSize => Self {
+                gap: [4, 2],
+                axis_width: 1,
+            },
+            TextMeasurement::Cells => Self {
+                gap: [1, 0],
+                axis_width: 0,
+            },
+        }
+    }
+}
+
+// writes the value with the given number of decimals, or as few as needed to tell it apart from other values
+pub(crate) fn format_value(out: &mut String, value: f32, decimals: Option<u8>) {
+    out.clear();
+    let _ = match decimals {
+        Some(decimals) => write!(out, "{value:.*}", decimals as usize),
+        None => write!(out, "{value}"),
+    };
+}
+
+// the values of one series, `category_count` long
+pub(crate) fn bar_series<'a>(tree: &'a ElementTree, chart: &BarChart, series: usize) -> &'a [f32] {
+    let values = tree.data(chart.values);
+    &values[series * chart.category_count..(series + 1) * chart.category_count]
+}
+
+pub(crate) fn bar_series_count(chart: &BarChart) -> usize {
+    match chart.category_count {
+        0 => 0,
+        count => (chart.values.end - chart.values.start) / count,
+    }
+}
+
+// ends of the positive and negative parts of a stack, negative values stack down from zero
+pub(crate) fn bar_stack(tree: &ElementTree, chart: &BarChart, category: usize) -> [f32; 2] {
+    (0..bar_series_count(chart))
+        .map(|series| bar_series(tree, chart, series)[category])
+        .filter(|value| value.is_finite())
+        .fold([0.0, 0.0], |[positive, negative], value| {
+            match value >= 0.0 {
+                true => [positive + value, negative],
+                false => [positive, negative + value],
+            }
+        })
+}
+
+// calls `f` with the value of each value label: every bar when grouped, or the total of each stack
+pub(crate) fn for_each_bar_label(tree: &ElementTree, chart: &BarChart, mut f: impl FnMut(f32)) {
+    for category in 0..chart.category_count {
+        match chart.stacked {
+            true => {
+                let [positive, negative] = bar_stack(tree, chart, category);
+                if (0..bar_series_count(chart))
+                    .any(|s| bar_series(tree, chart, s)[category].is_finite())
+                {
+                    f(positive + negative);
+                }
+            }
+            false => {
+                for series in 0..bar_series_count(chart) {
+                    let value = bar_series(tree, chart, series)[category];
+                    if value.is_finite() {
+                        f(value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// space around the bars for the labels and the axis
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BarChartInsets {
+    pub(crate) sides: [u32; 4],               // top, right, bottom, left
+    pub(crate) category_offset: u32, // from the start of the value axis to the category labels, past any negative labels
+    pub(crate) category_label_size: [u32; 2], // of the largest category label
+}
+
+pub(crate) fn bar_chart_insets(
+    tree: &ElementTree,
+    chart: &BarChart,
+    text_style: &TextStyle,
+) -> BarChartInsets {
+    let metrics = ChartMetrics::new(tree.text_measurement);
+    let measure = |text: &str| measure_text(text, text_style, tree.text_measurement);
+
+    let mut category_size = [0, 0];
+    if let Some(labels) = chart.labels {
+        for label in tree.text(labels).split('\n') {
+            let [w, h] = measure(label);
+            category_size = [category_size[0].max(w), category_size[1].max(h)];
+        }
+    }
+    // labels of negative values go past the other end of their bars
+    let mut value_size = [[0, 0], [0, 0]]; // positive, negative
+    if chart.value_labels {
+        let mut text = tree.take_scratch_text();
+        for_each_bar_label(tree, chart, |value| {
+            format_value(&mut text, value, chart.value_decimals);
+            let [w, h] = measure(&text);
+            let size = &mut value_size[(value < 0.0) as usize];
+            *size = [size[0].max(w), size[1].max(h)];
+        });
+        tree.return_scratch_text(text);
+    }
+
+    let axis = match chart.labels {
+        Some(_) => metrics.axis_width,
+        None => 0,
+    };
+    let label_space = |size: u32, gap: u32| match size {
+        0 => 0,
+        size => size + gap,
+    };
+    let [positive, negative] = value_size;
+    let [gap_x, gap_y] = metrics.gap;
+    match chart.orientation {
+        DividerOrientation::Vertical => {
+            let category_offset = label_space(negative[1], gap_y) + axis;
+            BarChartInsets {
+                sides: [
+                    label_space(positive[1], gap_y),
+                    0,
+                    category_offset + label_space(category_size[1], gap_y),
+                    0,
+                ],
+                category_offset,
+                category_label_size: category_size,
+            }
+        }
+        DividerOrientation::Horizontal => {
+            let category_offset = label_space(negative[0], gap_x) + axis;
+            BarChartInsets {
+                sides: [
+                    0,
+                    label_space(positive[0], gap_x),
+                    0,
+                    category_offset + label_space(category_size[0], gap_x),
+                ],
+                category_offset,
+                category_label_size: category_size,
+            }
+        }
+    }
+}
+
+// steps that line up with the clock, in seconds
+const TIME_STEPS: [f64; 18] = [
+    1.0, 2.0, 5.0, 10.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 900.0, 1800.0, 3600.0, 7200.0,
+    10800.0, 21600.0, 43200.0, 86400.0,
+];
+const DAY: f64 = 86400.0;
+
+// the smallest of 1, 2 or 5 times a power of ten that is at least `min_step`
+pub(crate) fn nice_step(min_step: f64) -> f64 {
+    if !(min_step > 0.0 && min_step.is_finite()) {
+        return 0.0;
+    }
+    let magnitude = 10f64.powf(min_step.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|m| m * magnitude)
+        .find(|step| *step >= min_step * (1.0 - 1e-9))
+        .unwrap_or(magnitude * 10.0)
+}
+
+// sub second steps, then `TIME_STEPS`, then whole days
+fn time_steps() -> impl Iterator<Item = f64> {
+    let fractions = [0.001, 0.002, 0.005, 0.01, 0.02, 0.05, 0.1, 0.2, 0.5];
+    let days = (0..6).flat_map(|p| [2.0, 5.0, 10.0].map(|m| m * 10f64.powi(p) * DAY));
+    fractions.into_iter().chain(TIME_STEPS).chain(days)
+}
+
+// enough decimals to tell apart values that are `step` apart
+fn step_decimals(step: f64) -> u8 {
+    (-step.log10().floor()).clamp(0.0, 9.0) as u8
+}
+
+// every multiple of the step in the range, so ticks land on round values
+pub(crate) fn value_ticks(range: [f32; 2], step: f32) -> impl Iterator<Item = f32> {
+    let [min, max] = range;
+    let first = match step > 0.0 {
+        true => (min / step).ceil() as i64,
+        false => 1,
+    };
+    let last = match step > 0.0 {
+        true => (max / step + 1e-4).floor() as i64,
+        false => 0,
+    };
+    (first..=last).map(move |i| i as f32 * step)
+}
+
+// clock ticks are on multiples of the step since the unix epoch, relative ones on multiples of the step back from
+// the end of the axis
+pub(crate) fn time_ticks(chart: &LineChart, step: f64) -> impl Iterator<Item = f64> {
+    let [start, end] = chart.time_range;
+    let (first, last, origin) = match (step > 0.0, chart.time_format) {
+        (false, _) => (1, 0, 0.0),
+        (true, TimeFormat::Clock) => (
+            (start / step - 1e-6).ceil() as i64,
+            (end / step + 1e-6).floor() as i64,
+            0.0,
+        ),
+        (true, TimeFormat::Relative) => (-((end - start) / step + 1e-6).floor() as i64, 0, end),
+    };
+    (first..=last).map(move |i| origin + i as f64 * step)
+}
+
+pub(crate) fn format_value_tick(out: &mut String, value: f32, step: f32, unit: &str) {
+    format_value(out, value, Some(step_decimals(step as f64)));
+    if out.starts_with('-') && out[1..].bytes().all(|b| b == b'0' || b == b'.') {
+        out.remove(0); // no `-0.0` for values rounded to zero
+    }
+    out.push_str(unit);
+}
+
+// writes a tick label of the time axis, with the parts of the time that change between ticks `step` seconds apart
+pub(crate) fn format_time(out: &mut String, time: f64, step: f64, chart: &LineChart) {
+    out.clear();
+    let _ = match chart.time_format {
+        TimeFormat::Clock => {
+            let decimals = match step < 1.0 {
+                true => step_decimals(step) as u32,
+                false => 0,
+            };
+            let scale = 10i64.pow(decimals);
+            let ticks = (time * scale as f64).round() as i64; // in units of the smallest decimal shown
+            let seconds = ticks.div_euclid(scale);
+            let fraction = ticks.rem_euclid(scale);
+            let [hours, minutes, secs] = [
+                seconds.rem_euclid(DAY as i64) / 3600,
+                seconds.rem_euclid(3600) / 60,
+                seconds.rem_euclid(60),
+            ];
+            if step >= DAY {
+                let (_, month, day) = civil_from_days(seconds.div_euclid(DAY as i64));
+                write!(out, "{month:02}-{day:02}")
+            } else if step >= 60.0 {
+                write!(out, "{hours:02}:{minutes:02}")
+            } else if step >= 1.0 {
+                write!(out, "{hours:02}:{minutes:02}:{secs:02}")
+            } else {
+                let width = decimals as usize;
+                write!(out, "{minutes:02}:{secs:02}.{fraction:0width$}")
+            }
+        }
+        TimeFormat::Relative => {
+            let before = ((chart.time_range[1] - time) / step).round() * step;
+            if before <= 0.0 {
+                write!(out, "now")
+            } else if step < 1.0 {
+                write!(out, "-{before:.*}s", step_decimals(step) as usize)
+            } else if step < 60.0 {
+                write!(out, "-{before}s")
+            } else if step < 3600.0 {
+                write!(out, "-{}m", before / 60.0)
+            } else if step < DAY {
+                write!(out, "-{}h", before / 3600.0)
+            } else {
+                write!(out, "-{}d", before / DAY)
+            }
+        }
+    };
+}
+
+// year, month and day of a day count since the unix epoch, see
+// https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+// where the parts of a line chart go, from the size of its content area
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct LineChartLayout {
+    pub(crate) sides: [u32; 4], // space around the plot, top, right, bottom, left
+    pub(crate) value_step: f32, // between value axis ticks
+    pub(crate) time_step: f64,  // between time axis ticks, in seconds
+    pub(crate) legend_height: u32,
+}
+
+pub(crate) fn line_chart_layout(
+    tree: &ElementTree,
+    chart: &LineChart,
+    text_style: &TextStyle,
+    content_size: [u32; 2],
+) -> LineChartLayout {
+    let metrics = ChartMetrics::new(tree.text_measurement);
+    let measure = |text: &str| measure_text(text, text_style, tree.text_measurement);
+    let [gap_x, gap_y] = metrics.gap;
+    let [_, label_height] = measure("0");
+    let unit = chart.unit.map_or("", |span| tree.text(span));
+    let mut text = tree.take_scratch_text();
+
+    // the legend and time labels have a fixed height, so the plot height (and with it the value ticks) is known first
+    let legend_height = match chart.legend && chart.series.end > chart.series.start {
+        true => label_height,
+        false => 0,
+    };
+    let top = match legend_height {
+        0 => label_height / 2, // room for the top half of the highest value label
+        height => height + gap_y,
+    };
+    let bottom = metrics.axis_width + gap_y + label_height;
+    let plot_height = content_size[1].saturating_sub(top + bottom);
+    let max_value_ticks = plot_height / (label_height * 2).max(1) + 1;
+    let [min, max] = chart.range;
+    let value_step = nice_step((max - min) as f64 / (max_value_ticks.max(2) - 1) as f64) as f32;
+
+    let mut label_width = 0;
+    for value in value_ticks(chart.range, value_step) {
+        format_value_tick(&mut text, value, value_step, unit);
+        label_width = label_width.max(measure(&text)[0]);
+    }
+    let left = label_width + gap_x + metrics.axis_width;
+
+    // the widest step whose labels fit side by side with some space between them
+    let plot_width = content_size[0].saturating_sub(left);
+    let span = chart.time_range[1] - chart.time_range[0];
+    let mut time_step = 0.0;
+    let mut right = 0;
+    for step in time_steps() {
+        format_time(&mut text, chart.time_range[0], step, chart);
+        let [width, _] = measure(&text);
+        time_step = step;
+        right = width / 2; // the last label is centred on the end of the axis
+        if (span / step + 1.0) * (width + gap_x * 4) as f64 <= plot_width as f64 {
+            break;
+        }
+    }
+    tree.return_scratch_text(text);
+
+    LineChartLayout {
+        sides: [top, right, bottom, left],
+        value_step,
+        time_step,
+        legend_height,
+    }
+}
+
+// calls `f` with the x offset of each legend item from the left of the legend, its series and the size of its name,
+// an item is a colour swatch then the name, returns the width of the whole legend
+pub(crate) fn for_each_legend_item(
+    tree: &ElementTree,
+    chart: &LineChart,
+    text_style: &TextStyle,
+    mut f: impl FnMut(u32, &ChartSeries, [u32; 2]),
+) -> u32 {
+    let metrics = ChartMetrics::new(tree.text_measurement);
+    let [gap_x, _] = metrics.gap;
+    let swatch = legend_swatch_size(tree, text_style);
+    let mut x = 0;
+    for (i, series) in tree.chart_series(chart.series).iter().enumerate() {
+        if i > 0 {
+            x += gap_x * 3;
+        }
+        let size = measure_text(tree.text(series.name), text_style, tree.text_measurement);
+        f(x, series, size);
+        x += swatch + gap_x + size[0];
+    }
+    x
+}
+
+pub(crate) fn legend_swatch_size(tree: &ElementTree, text_style: &TextStyle) -> u32 {
+    let [_, height] = measure_text("0", text_style, tree.text_measurement);
+    match tree.text_measurement {
+        TextMeasurement::FontSize => (height as f32 * 0.5).round() as u32,
+        TextMeasurement::Cells => 1,
+    }
+}
+
+// the points of a series (sorted by time) that are inside the time range, and points interpolated where the line
+// crosses the ends of the range
+pub(crate) fn clip_to_time_range<'a>(
+    times: &'a [f64],
+    values: &'a [f32],
+    range: [f64; 2],
+) -> impl Iterator<Item = (f64, f32)> + 'a {
+    let [start, end] = range;
+    (0..times.len().min(values.len())).flat_map(move |i| {
+        let (time, value) = (times[i], values[i]);
+        let crossing = |at: f64| {
+            let (previous_time, previous_value) = (times[i - 1], values[i - 1]);
+            let t = ((at - previous_time) / (time - previous_time)) as f32;
+            (at, previous_value + (value - previous_value) * t)
+        };
+        let entering = (i > 0 && times[i - 1] < start && time > start).then(|| crossing(start));
+        let leaving = (i > 0 && times[i - 1] < end && time > end).then(|| crossing(end));
+        let inside = (start..=end).contains(&time).then_some((time, value));
+        entering.into_iter().chain(leaving).chain(inside)
+    })
+}
+
+// space for the row labels to the left of the cells and the column labels above them
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct HeatmapLabels {
+    pub(crate) left: u32, // widest row label plus the gap, 0 without row labels
+    pub(crate) top: u32,  // tallest column label plus the gap, 0 without column labels
+    pub(crate) column_width: u32, // of the widest column label
+}
+
+pub(crate) fn heatmap_labels(
+    tree: &ElementTree,
+    heatmap: &Heatmap,
+    text_style: &TextStyle,
+) -> HeatmapLabels {
+    let [gap_x, gap_y] = ChartMetrics::new(tree.text_measurement).gap;
+    let largest = |labels: Option<TextSpan>, count: u32| {
+        let mut size = [0, 0];
+        if let Some(labels) = labels {
+            for label in tree.text(labels).split('\n').take(count as usize) {
+                let [w, h] = measure_text(label, text_style, tree.text_measurement);
+                size = [size[0].max(w), size[1].max(h)];
+            }
+        }
+        size
+    };
+    let row_size = largest(heatmap.row_labels, heatmap.rows);
+    let column_size = largest(heatmap.column_labels, heatmap.columns);
+    HeatmapLabels {
+        left: match row_size[0] {
+            0 => 0,
+            width => width + gap_x,
+        },
+        top: match column_size[1] {
+            0 => 0,
+            height => height + gap_y,
+        },
+        column_width: column_size[0],
+    }
+}
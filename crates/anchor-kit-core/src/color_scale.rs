@@ -0,0 +1,288 @@
+use std::borrow::Cow;
+
+use crate::primitives::color::Color;
+
+// maps data values to colours, e.g. `background_color: latency_scale.color(ms)` for a pill that goes from green to red
+// NaN values map to transparent so missing data doesn't look like a real value
+pub trait ColorScale {
+    fn color(&self, value: f64) -> Color;
+}
+
+// evenly spaced colour stops, interpolated in oklab so the steps look even
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Palette {
+    stops: Cow<'static, [Color]>,
+}
+
+impl Palette {
+    pub fn new(stops: impl Into<Cow<'static, [Color]>>) -> Self {
+        Self {
+            stops: stops.into(),
+        }
+    }
+
+    // matplotlib's perceptually uniform colour maps, sampled at 11 points
+    pub fn viridis() -> Self {
+        Self::new(&VIRIDIS[..])
+    }
+
+    pub fn magma() -> Self {
+        Self::new(&MAGMA[..])
+    }
+
+    pub fn inferno() -> Self {
+        Self::new(&INFERNO[..])
+    }
+
+    pub fn plasma() -> Self {
+        Self::new(&PLASMA[..])
+    }
+
+    // colorbrewer diverging palettes, the middle stop is the neutral colour
+    pub fn red_blue() -> Self {
+        Self::new(&RED_BLUE[..])
+    }
+
+    pub fn red_yellow_green() -> Self {
+        Self::new(&RED_YELLOW_GREEN[..])
+    }
+
+    pub fn stops(&self) -> &[Color] {
+        &self.stops
+    }
+
+    pub fn reversed(&self) -> Self {
+        Self::new(self.stops.iter().rev().copied().collect::<Vec<_>>())
+    }
+
+    // t is clamped to 0.0 - 1.0
+    pub fn sample(&self, t: f64) -> Color {
+        match self.stops.len() {
+            0 => Color::TRANSPARENT,
+            1 => self.stops[0],
+            len => {
+                let position = t.clamp(0.0, 1.0) * (len - 1) as f64;
+                let index = (position.floor() as usize).min(len - 2);
+                let local_t = (position - index as f64) as f32;
+                self.stops[index].lerp_oklab(self.stops[index + 1], local_t)
+            }
+        }
+    }
+}
+
+// maps a [min, max] domain onto a palette, values outside the domain are clamped
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SequentialScale {
+    pub domain: [f64; 2],
+    pub palette: Palette,
+}
+
+impl SequentialScale {
+    // e.g. `SequentialScale::new([0.0, 100.0], Palette::new(vec![Color::LIME, Color::YELLOW, Color::RED]))`
+    pub fn new(domain: [f64; 2], palette: Palette) -> Self {
+        Self { domain, palette }
+    }
+
+    pub fn viridis(domain: [f64; 2]) -> Self {
+        Self::new(domain, Palette::viridis())
+    }
+
+    pub fn magma(domain: [f64; 2]) -> Self {
+        Self::new(domain, Palette::magma())
+    }
+
+    pub fn inferno(domain: [f64; 2]) -> Self {
+        Self::new(domain, Palette::inferno())
+    }
+
+    pub fn plasma(domain: [f64; 2]) -> Self {
+        Self::new(domain, Palette::plasma())
+    }
+}
+
+impl ColorScale for SequentialScale {
+    fn color(&self, value: f64) -> Color {
+        if value.is_nan() {
+            return Color::TRANSPARENT;
+        }
+        self.palette
+            .sample(normalize(value, self.domain[0], self.domain[1]))
+    }
+}
+
+// maps [min, mid] to the first half of the palette and [mid, max] to the second half, so the midpoint always gets
+// the neutral colour even if the domain isn't symmetric, e.g. change from -20% to +50% around 0
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DivergingScale {
+    pub domain: [f64; 3], // min, mid, max
+    pub palette: Palette,
+}
+
+impl DivergingScale {
+    pub fn new(domain: [f64; 3], palette: Palette) -> Self {
+        Self { domain, palette }
+    }
+
+    pub fn red_blue(domain: [f64; 3]) -> Self {
+        Self::new(domain, Palette::red_blue())
+    }
+
+    pub fn red_yellow_green(domain: [f64; 3]) -> Self {
+        Self::new(domain, Palette::red_yellow_green())
+    }
+}
+
+impl ColorScale for DivergingScale {
+    fn color(&self, value: f64) -> Color {
+        if value.is_nan() {
+            return Color::TRANSPARENT;
+        }
+        let [min, mid, max] = self.domain;
+        // how far the value is from the midpoint towards one end, past the end of a half with no width
+        let towards = |end: f64| match end == mid {
+            true => 1.0,
+            false => normalize(value, mid, end),
+        };
+        let t = match value {
+            _ if value < mid => 0.5 - towards(min) * 0.5,
+            _ if value > mid => 0.5 + towards(max) * 0.5,
+            _ => 0.5,
+        };
+        self.palette.sample(t)
+    }
+}
+
+// discrete colours for ranges of values, e.g. green below 50ms, yellow from 50ms and red from 100ms:
+// `ThresholdScale::new(Color::LIME, vec![(50.0, Color::YELLOW), (100.0, Color::RED)])`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThresholdScale {
+    pub below: Color,                  // colour for values below the first threshold
+    pub thresholds: Vec<(f64, Color)>, // sorted ascending, each colour applies from its threshold (inclusive)
+}
+
+impl ThresholdScale {
+    pub fn new(below: Color, mut thresholds: Vec<(f64, Color)>) -> Self {
+        thresholds.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { below, thresholds }
+    }
+
+    // `steps` evenly sized bins over the domain, coloured by sampling the palette at the centre of each bin
+    pub fn quantize(domain: [f64; 2], steps: usize, palette: &Palette) -> Self {
+        let [min, max] = domain;
+        let steps = steps.max(1);
+        let bin_color = |i: usize| palette.sample((i as f64 + 0.5) / steps as f64);
+        let thresholds = (1..steps)
+            .map(|i| (min + (max - min) * i as f64 / steps as f64, bin_color(i)))
+            .collect();
+        Self::new(bin_color(0), thresholds)
+    }
+}
+
+impl ColorScale for ThresholdScale {
+    fn color(&self, value: f64) -> Color {
+        if value.is_nan() {
+            return Color::TRANSPARENT;
+        }
+        let index = self.thresholds.partition_point(|(t, _)| *t <= value);
+        match index {
+            0 => self.below,
+            i => self.thresholds[i - 1].1,
+        }
+    }
+}
+
+// position of the value in [min, max] as 0.0 - 1.0, reversed domains (max < min) work too
+fn normalize(value: f64, min: f64, max: f64) -> f64 {
+    if max == min {
+        return 0.5;
+    }
+    ((value - min) / (max - min)).clamp(0.0, 1.0)
+}
+
+const VIRIDIS: [Color; 11] = [
+    Color::rgb(0x44, 0x01, 0x54),
+    Color::rgb(0x48, 0x24, 0x75),
+    Color::rgb(0x41, 0x44, 0x87),
+    Color::rgb(0x35, 0x5f, 0x8d),
+    Color::rgb(0x2a, 0x78, 0x8e),
+    Color::rgb(0x21, 0x91, 0x8c),
+    Color::rgb(0x22, 0xa8, 0x84),
+    Color::rgb(0x44, 0xbf, 0x70),
+    Color::rgb(0x7a, 0xd1, 0x51),
+    Color::rgb(0xbd, 0xdf, 0x26),
+    Color::rgb(0xfd, 0xe7, 0x25),
+];
+
+const MAGMA: [Color; 11] = [
+    Color::rgb(0x00, 0x00, 0x04),
+    Color::rgb(0x14, 0x0e, 0x36),
+    Color::rgb(0x3b, 0x0f, 0x70),
+    Color::rgb(0x64, 0x1a, 0x80),
+    Color::rgb(0x8c, 0x29, 0x81),
+    Color::rgb(0xb7, 0x37, 0x79),
+    Color::rgb(0xde, 0x49, 0x68),
+    Color::rgb(0xf7, 0x70, 0x5c),
+    Color::rgb(0xfe, 0x9f, 0x6d),
+    Color::rgb(0xfe, 0xcf, 0x92),
+    Color::rgb(0xfc, 0xfd, 0xbf),
+];
+
+const INFERNO: [Color; 11] = [
+    Color::rgb(0x00, 0x00, 0x04),
+    Color::rgb(0x16, 0x0b, 0x39),
+    Color::rgb(0x42, 0x0a, 0x68),
+    Color::rgb(0x6a, 0x17, 0x6e),
+    Color::rgb(0x93, 0x26, 0x67),
+    Color::rgb(0xbc, 0x37, 0x54),
+    Color::rgb(0xdd, 0x51, 0x3a),
+    Color::rgb(0xf3, 0x78, 0x19),
+    Color::rgb(0xfc, 0xa5, 0x0a),
+    Color::rgb(0xf6, 0xd7, 0x46),
+    Color::rgb(0xfc, 0xff, 0xa4),
+];
+
+const PLASMA: [Color; 11] = [
+    Color::rgb(0x0d, 0x08, 0x87),
+    Color::rgb(0x41, 0x04, 0x9d),
+    Color::rgb(0x6a, 0x00, 0xa8),
+    Color::rgb(0x8f, 0x0d, 0xa4),
+    Color::rgb(0xb1, 0x2a, 0x90),
+    Color::rgb(0xcc, 0x47, 0x78),
+    Color::rgb(0xe1, 0x64, 0x62),
+    Color::rgb(0xf2, 0x84, 0x4b),
+    Color::rgb(0xfc, 0xa6, 0x36),
+    Color::rgb(0xfc, 0xce, 0x25),
+    Color::rgb(0xf0, 0xf9, 0x21),
+];
+
+const RED_BLUE: [Color; 11] = [
+    Color::rgb(0x67, 0x00, 0x1f),
+    Color::rgb(0xb2, 0x18, 0x2b),
+    Color::rgb(0xd6, 0x60, 0x4d),
+    Color::rgb(0xf4, 0xa5, 0x82),
+    Color::rgb(0xfd, 0xdb, 0xc7),
+    Color::rgb(0xf7, 0xf7, 0xf7),
+    Color::rgb(0xd1, 0xe5, 0xf0),
+    Color::rgb(0x92, 0xc5, 0xde),
+    Color::rgb(0x43, 0x93, 0xc3),
+    Color::rgb(0x21, 0x66, 0xac),
+    Color::rgb(0x05, 0x30, 0x61),
+];
+
+const RED_YELLOW_GREEN: [Color; 11] = [
+    Color::rgb(0xa5, 0x00, 0x26),
+    Color::rgb(0xd7, 0x30, 0x27),
+    Color::rgb(0xf4, 0x6d, 0x43),
+    Color::rgb(0xfd, 0xae, 0x61),
+    Color::rgb(0xfe, 0xe0, 0x8b),
+    Color::rgb(0xff, 0xff, 0xbf),
+    Color::rgb(0xd9, 0xef, 0x8b),
+    Color::rgb(0xa6, 0xd9, 0x6a),
+    Color::rgb(0x66, 0xbd, 0x63),
+    Color::rgb(0x1a, 0x98, 0x50),
+    Color::rgb(0x00, 0x68, 0x37),
+];
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use crate::render::RenderList;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PrimitiveKind {
+    Rectangle,
+    Text,
+    Image,
+    Polyline,
+    Path,
+    Arc,
+    CellGrid,
+}
+
+// index of a primitive in the matching list of a render list (e.g. `render_list.text[index]` for text)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PrimitiveRef {
+    pub kind: PrimitiveKind,
+    pub index: usize,
+}
+
+// screen area that needs to be redrawn, in the same pixel units as the primitives
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DirtyRegion {
+    pub position: [u32; 2], // x, y
+    pub size: [u32; 2],     // w, h
+}
+
+impl DirtyRegion {
+    // regions that reach past u32::MAX are cut off there
+    pub fn union(&self, other: &DirtyRegion) -> DirtyRegion {
+        let [x0, y0] = [0, 1].map(|i| self.position[i].min(other.position[i]));
+        let [x1, y1] = [0, 1].map(|i| self.end(i).max(other.end(i)));
+        DirtyRegion {
+            position: [x0, y0],
+            size: [x1 - x0, y1 - y0],
+        }
+    }
+
+    // touching regions also count, so that neighbouring areas get merged into one region
+    pub fn intersects(&self, other: &DirtyRegion) -> bool {
+        self.position[0] <= other.end(0)
+            && other.position[0] <= self.end(0)
+            && self.position[1] <= other.end(1)
+            && other.position[1] <= self.end(1)
+    }
+
+    // right (0) or bottom (1) edge
+    fn end(&self, axis: usize) -> u32 {
+        self.position[axis].saturating_add(self.size[axis])
+    }
+
+    fn is_empty(&self) -> bool {
+        self.size[0] == 0 || self.size[1] == 0
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RenderListDiff {
+    pub added: Vec<PrimitiveRef>,   // indices into the current render list
+    pub removed: Vec<PrimitiveRef>, // indices into the previous render list
+    pub changed: Vec<(PrimitiveRef, PrimitiveRef)>, // previous, current
+    // non-overlapping union of all areas that changed, or their bounding box when more than
+    // `MAX_DIRTY_REGIONS` areas changed
+    pub dirty_regions: Vec<DirtyRegion>,
+}
+
+impl RenderListDiff {
+    // merging regions is quadratic in their number, so frames where more areas than this changed get one bounding box
+    pub const MAX_DIRTY_REGIONS: usize = 64;
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    // single bounding box of all dirty regions, for backends that can only redraw one area
+    pub fn dirty_bounds(&self) -> Option<DirtyRegion> {
+        self.dirty_regions
+            .iter()
+            .copied()
+            .reduce(|bounds, region| bounds.union(&region))
+    }
+}
+
+impl RenderList {
+    // compares against the previous frame's render list
+    // primitives that are exactly the same in both lists are unchanged unless their draw order changed, the remaining
+    // ones are paired up in render order as changed, and any left over are added/ removed
+    pub fn diff(&self, previous: &RenderList) -> RenderListDiff {
+        let mut diff = RenderListDiff::default();
+
+        diff_primitives(
+            PrimitiveKind::Rectangle,
+            &previous.rectangles,
+            &self.rectangles,
+            |r| (r.position, r.size),
+            &mut diff,
+        );
+        diff_primitives(
+            PrimitiveKind::Text,
+            &previous.text,
+            &self.text,
+            |t| (t.position, t.size),
+            &mut diff,
+        );
+        diff_primitives(
+            PrimitiveKind::Image,
+            &previous.images,
+            &self.images,
+            |i| (i.rectangle.position, i.rectangle.size),
+            &mut diff,
+        );
+        diff_primitives(
+            PrimitiveKind::Polyline,
+            &previous.polylines,
+            &self.polylines,
+            |p| p.bounds(),
+            &mut diff,
+        );
+        diff_primitives(
+            PrimitiveKind::Path,
+            &previous.paths,
+            &self.paths,
+            |p| p.bounds(),
+            &mut diff,
+        );
+        diff_primitives(
+            PrimitiveKind::Arc,
+            &previous.arcs,
+            &self.arcs,
+            |a| a.bounds(),
+            &mut diff,
+        );
+        diff_primitives(
+            PrimitiveKind::CellGrid,
+            &previous.cell_grids,
+            &self.cell_grids,
+            |g| g.bounds(),
+            &mut diff,
+        );
+
+        let mut regions: Vec<DirtyRegion> = vec![];
+        for r in diff.removed.iter() {
+            regions.push(previous.bounds_of(*r));
+        }
+        for r in diff.added.iter() {
+            regions.push(self.bounds_of(*r));
+        }
+        for (p, c) in diff.changed.iter() {
+            regions.push(previous.bounds_of(*p));
+            regions.push(self.bounds_of(*c));
+        }
+        diff.dirty_regions = merge_regions(regions);
+
+        diff
+    }
+
+    pub fn bounds_of(&self, primitive: PrimitiveRef) -> DirtyRegion {
+        let (position, size) = match primitive.kind {
+            PrimitiveKind::Rectangle => {
+                let r = &self.rectangles[primitive.index];
+                (r.position, r.size)
+            }
+            PrimitiveKind::Text => {
+                let t = &self.text[primitive.index];
+                (t.position, t.size)
+            }
+            PrimitiveKind::Image => {
+                let i = &self.images[primitive.index];
+                (i.rectangle.position, i.rectangle.size)
+            }
+            PrimitiveKind::Polyline => self.polylines[primitive.index].bounds(),
+            PrimitiveKind::Path => self.paths[primitive.index].bounds(),
+            PrimitiveKind::Arc => self.arcs[primitive.index].bounds(),
+            PrimitiveKind::CellGrid => self.cell_grids[primitive.index].bounds(),
+        };
+        DirtyRegion { position, size }
+    }
+}
+
+fn diff_primitives<T: PartialEq>(
+    kind: PrimitiveKind,
+    previous: &[T],
+    current: &[T],
+    bounds: impl Fn(&T) -> ([u32; 2], [u32; 2]),
+    diff: &mut RenderListDiff,
+) {
+    // bucket the previous primitives by their bounds so we only compare primitives that could be equal
+    let mut previous_by_bounds: HashMap<([u32; 2], [u32; 2]), Vec<usize>> = HashMap::new();
+    for (i, p) in previous.iter().enumerate() {
+        previous_by_bounds.entry(bounds(p)).or_default().push(i);
+    }
+
+    let primitive = |index| PrimitiveRef { kind, index };
+    let mut previous_matched = vec![false; previous.len()];
+    let mut unmatched_current: Vec<usize> = vec![];
+    let mut last_matched: Option<usize> = None; // furthest previous index matched so far, in render order
+    for (i, c) in current.iter().enumerate() {
+        let matched = previous_by_bounds
+            .get(&bounds(c))
+            .and_then(|candidates| {
+                candidates
+                    .iter()
+                    .find(|p| !previous_matched[**p] && previous[**p] == *c)
+            })
+            .copied();
+        match matched {
+            Some(p) => {
+                previous_matched[p] = true;
+                // now drawn before a primitive that used to be drawn after it, so where the two overlap the other
+                // one may be on top. marking the later of the two is enough since the overlap is inside both
+                if last_matched.is_some_and(|last| p < last) {
+                    diff.changed.push((primitive(p), primitive(i)));
+                }
+                last_matched = last_matched.max(Some(p));
+            }
+            None => unmatched_current.push(i),
+        }
+    }
+    let unmatched_previous = previous_matched
+        .iter()
+        .enumerate()
+        .filter(|(_, matched)| !**matched)
+        .map(|(i, _)| i);
+
+    let mut unmatched_current = unmatched_current.into_iter();
+    for p in unmatched_previous {
+        match unmatched_current.next() {
+            Some(c) => diff.changed.push((primitive(p), primitive(c))),
+            None => diff.removed.push(primitive(p)),
+        }
+    }
+    diff.added.extend(unmatched_current.map(primitive));
+}
+
+// merges overlapping regions until none of them overlap. every region is compared with every merged one (and again
+// each time a merge makes it grow), which is O(n²) or worse, so too many regions are merged into one bounding box
+fn merge_regions(mut regions: Vec<DirtyRegion>) -> Vec<DirtyRegion> {
+    regions.retain(|r| !r.is_empty());
+    if regions.len() > RenderListDiff::MAX_DIRTY_REGIONS {
+        let bounds = regions.into_iter().reduce(|bounds, r| bounds.union(&r));
+        return bounds.into_iter().collect();
+    }
+    let mut merged: Vec<DirtyRegion> = vec![];
+    for region in regions {
+        let mut region = region;
+        // a merged region can grow to overlap regions that were already merged, so keep going until it doesn't
+        while let Some(i) = merged.iter().position(|m| m.intersects(&region)) {
+            region = region.union(&merged.swap_remove(i));
+        }
+        merged.push(region);
+    }
+    merged
+}
@@ -1,35 +1,414 @@
+use std::{
+    cell::RefCell,
+    ops::{Index, IndexMut},
+};
+
 use crate::{
     anchor::AnchorPosition,
-    style::{Style, TextStyle},
+    animation::AnimationFrame,
+    measure::TextMeasurement,
+    primitives::{
+        color::Color,
+        path::{FillRule, PathCommand},
+        polyline::Stroke,
+    },
+    style::{Align, SizingPolicy, Style, TextStyle},
+    time_series::TimeSeries,
 };
 use uuid::Uuid;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ElementType {
     Root,
     Anchor(AnchorPosition),
-    Text(String),
+    Text(TextSpan), // location of the text in the tree's text buffer
     FlexRow,
     FlexColumn,
     Pill,
     Image(Uuid),                      // stores the texture id for image rendering
     Divider(DividerOrientation, u32), // orientation and thickness
+    Progress(ProgressBar),
+    Sparkline(Sparkline),
+    Path(PathShape),
+    BarChart(BarChart),
+    LineChart(LineChart),
+    Gauge(Gauge),
+    Donut(Donut),
+    Heatmap(Heatmap),
+    Table(Table),
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DividerOrientation {
     Horizontal,
     Vertical,
 }
 
+// a horizontal or vertical fill bar, the track uses the element's style
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgressBar {
+    pub(crate) fraction: f32, // 0.0 - 1.0
+    pub(crate) orientation: DividerOrientation,
+    pub(crate) fill_color: Color,
+    pub(crate) segments: u32, // 0 for a continuous bar
+    pub(crate) segment_gap: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
+pub struct ProgressOptions {
+    pub orientation: Option<DividerOrientation>, // horizontal by default, vertical bars fill from the bottom up
+    pub fill_color: Option<Color>,               // the theme's accent colour by default
+    pub segments: u32, // splits the bar into this many blocks (e.g. for a battery or signal meter), 0 for a continuous bar
+    pub segment_gap: u32, // pixels between segments
+}
+
+// a small line chart of the values, the background uses the element's style
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sparkline {
+    pub(crate) data: DataSpan,
+    pub(crate) range: [f32; 2], // y values mapped to the bottom and top of the content area
+    pub(crate) line_color: Color,
+    pub(crate) line_width: f32,
+    pub(crate) fill_color: Option<Color>,
+    pub(crate) dot_radius: Option<f32>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
+pub struct SparklineOptions {
+    pub range: Option<[f32; 2]>, // [min, max] of the y axis, fitted to the values by default
+    pub line_color: Option<Color>, // the theme's accent colour by default
+    pub line_width: Option<f32>, // 1.5px by default
+    pub fill_color: Option<Color>, // fills the area under the line
+    pub dot: bool,               // marks the last value with a dot
+    pub dot_radius: Option<f32>, // twice the line width by default
+}
+
+// a vector path scaled to fit inside the element's padding and border, keeping its aspect ratio
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PathShape {
+    pub(crate) commands: PathSpan,
+    pub(crate) viewbox: [f32; 4], // x, y, w, h of the area of the path that is shown
+    pub(crate) fill: Option<Color>,
+    pub(crate) fill_rule: FillRule,
+    pub(crate) stroke: Option<Stroke>,
+}
+
+// bars for one or more series of values per category, with the labels drawn inside the element's padding and border
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BarChart {
+    pub(crate) values: DataSpan, // `category_count` values for each series, one series after the other
+    pub(crate) colors: ColorSpan, // one for each series
+    pub(crate) labels: Option<TextSpan>, // category labels on separate lines, if the category axis is shown
+    pub(crate) category_count: usize,
+    pub(crate) range: [f32; 2], // values mapped to the start and end of the value axis
+    pub(crate) orientation: DividerOrientation,
+    pub(crate) stacked: bool,
+    pub(crate) category_gap: f32,
+    pub(crate) bar_radius: f32,
+    pub(crate) value_labels: bool,
+    pub(crate) value_decimals: Option<u8>,
+    pub(crate) axis_color: Color,
+}
+
+// one value for each category, e.g. `BarSeries::new(&scores)`
+#[derive(Clone, Copy, Debug)]
+pub struct BarSeries<'a> {
+    pub values: &'a [f32],    // non finite values leave out the bar
+    pub color: Option<Color>, // `Theme::series_color` for the index of the series by default
+}
+
+impl<'a> BarSeries<'a> {
+    pub fn new(values: &'a [f32]) -> Self {
+        Self {
+            values,
+            color: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
+pub struct BarChartOptions {
+    pub orientation: Option<DividerOrientation>, // vertical bars growing up by default, horizontal bars grow to the right
+    pub stacked: bool, // series are stacked on top of each other instead of side by side
+    pub range: Option<[f32; 2]>, // [min, max] of the value axis, from 0 to the largest bar (or stack) by default
+    pub category_gap: Option<f32>, // fraction of each category's space left between neighbouring groups, 0.2 by default
+    pub bar_radius: f32,
+    pub value_labels: bool, // shows the value (or stack total) past the end of each bar
+    pub value_decimals: Option<u8>, // decimals of the value labels, as few as needed by default
+    pub category_axis: bool, // shows the category labels and a line along the base of the bars
+    pub axis_color: Option<Color>, // the theme's muted colour by default
+}
+
+// lines (and shaded areas) for one or more series over time, with the axes, gridlines and legend drawn inside the
+// element's padding and border
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineChart {
+    pub(crate) series: SeriesSpan,
+    pub(crate) time_range: [f64; 2], // seconds mapped to the left and right of the plot
+    pub(crate) range: [f32; 2],      // values mapped to the bottom and top of the plot
+    pub(crate) time_format: TimeFormat,
+    pub(crate) unit: Option<TextSpan>,
+    pub(crate) line_width: f32,
+    pub(crate) gridlines: bool,
+    pub(crate) legend: bool,
+    pub(crate) axis_color: Color,
+}
+
+// the points of one series of a line chart, stored in the tree's series buffer
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct ChartSeries {
+    pub(crate) points: DataSpan, // x, y pairs, x in seconds from the start of the chart's time range
+    pub(crate) name: TextSpan,
+    pub(crate) color: Color,
+    pub(crate) fill: Option<Color>,
+}
+
+// the time (in seconds, e.g. since the unix epoch) and value of each point, e.g. `LineSeries::new("cpu", &times, &load)`
+#[derive(Clone, Copy, Debug)]
+pub struct LineSeries<'a> {
+    pub name: &'a str, // shown in the legend
+    pub times: &'a [f64],
+    pub values: &'a [f32], // same length as the times, non finite values leave a gap in the line
+    pub color: Option<Color>, // `Theme::series_color` for the index of the series by default
+    pub fill: Option<Color>, // fills the area under the line
+}
+
+impl<'a> LineSeries<'a> {
+    pub fn new(name: &'a str, times: &'a [f64], values: &'a [f32]) -> Self {
+        Self {
+            name,
+            times,
+            values,
+            color: None,
+            fill: None,
+        }
+    }
+
+    pub fn from_time_series(name: &'a str, series: &'a TimeSeries) -> Self {
+        Self::new(name, series.times(), series.values())
+    }
+}
+
+// how the time axis labels are written
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeFormat {
+    #[default]
+    Clock, // utc time of day, with seconds when the ticks are less than a minute apart, e.g. `14:05:30`
+    Relative, // time before the end of the axis, e.g. `-30s`, `-5m`, for clocks that don't start at the unix epoch
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
+pub struct LineChartOptions<'a> {
+    pub time_range: Option<[f64; 2]>, // [start, end] of the time axis in seconds, fitted to the points by default
+    pub range: Option<[f32; 2]>, // [min, max] of the value axis, fitted to the values plus the padding by default
+    pub range_padding: Option<f32>, // fraction of the fitted range added above and below the values, 0.1 by default
+    pub time_format: TimeFormat,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    // a field of the node in layout files, so it can use bindings
+    pub unit: &'a str, // appended to the value axis labels, e.g. " ms" or "%"
+    pub line_width: Option<f32>,   // 1.5px by default
+    pub gridlines: bool,           // lines across the plot at every tick
+    pub legend: bool,              // the series names and colours above the plot
+    pub axis_color: Option<Color>, // the theme's muted colour by default, gridlines use it at a lower alpha
+}
+
+// a partly filled ring, like a speedometer, in the largest square that fits inside the element's padding and border
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gauge {
+    pub(crate) fraction: f32, // 0.0 - 1.0 of the way from the start angle to the end angle
+    pub(crate) angles: [f32; 2], // start and end, in degrees clockwise from 12 o'clock
+    pub(crate) thickness: f32, // fraction of the radius
+    pub(crate) track_color: Color,
+    pub(crate) fill_color: Color,
+    pub(crate) rounded_caps: bool,
+    pub(crate) label: Option<TextSpan>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
+pub struct GaugeOptions<'a> {
+    pub angles: Option<[f32; 2]>, // start and end in degrees clockwise from 12 o'clock, [-135, 135] by default
+    pub thickness: Option<f32>,   // fraction of the radius, 0.2 by default
+    pub track_color: Option<Color>, // the theme's muted colour by default
+    pub fill_color: Option<Color>, // the theme's accent colour by default
+    pub rounded_caps: Option<bool>, // true by default
+    #[cfg_attr(feature = "serde", serde(skip))]
+    // a field of the node in layout files, so it can use bindings
+    pub label: &'a str, // drawn in the centre, e.g. the formatted value
+}
+
+// slices of a ring (or a pie) sized by their share of the total, in the largest square that fits inside the element's
+// padding and border
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Donut {
+    pub(crate) values: DataSpan,
+    pub(crate) colors: ColorSpan, // one for each value
+    pub(crate) total: f32,        // the value of a full ring
+    pub(crate) start_angle: f32,  // degrees clockwise from 12 o'clock
+    pub(crate) thickness: f32,    // fraction of the radius
+    pub(crate) gap: f32,          // pixels between neighbouring slices
+    pub(crate) rounded_caps: bool,
+    pub(crate) track_color: Option<Color>, // drawn under the slices
+    pub(crate) label: Option<TextSpan>,
+}
+
+// e.g. `DonutSlice::new(used)`
+#[derive(Clone, Copy, Debug)]
+pub struct DonutSlice {
+    pub value: f32, // slices with values that aren't positive and finite are left out
+    pub color: Option<Color>, // `Theme::series_color` for the index of the slice by default
+}
+
+impl DonutSlice {
+    pub fn new(value: f32) -> Self {
+        Self { value, color: None }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
+pub struct DonutOptions<'a> {
+    pub start_angle: f32, // degrees clockwise from 12 o'clock where the first slice starts
+    pub thickness: Option<f32>, // fraction of the radius, 1.0 for a pie, 0.35 by default
+    pub gap: f32,         // pixels between neighbouring slices
+    pub rounded_caps: bool,
+    pub track_color: Option<Color>, // shows the whole ring under the slices, e.g. when they don't add up to the total
+    pub total: Option<f32>,         // what a full ring is worth, the sum of the values by default
+    #[cfg_attr(feature = "serde", serde(skip))]
+    // a field of the node in layout files, so it can use bindings
+    pub label: &'a str, // drawn in the centre
+}
+
+// a grid of cells coloured by their values, with optional labels to the left of the rows and above the columns
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Heatmap {
+    pub(crate) colors: ColorSpan, // rows * columns, row by row, already mapped through the colour scale
+    pub(crate) rows: u32,
+    pub(crate) columns: u32,
+    pub(crate) cell_size: Option<[u32; 2]>, // of each cell when the element is sized automatically
+    pub(crate) gap: u32,
+    pub(crate) cell_radius: f32,
+    pub(crate) row_labels: Option<TextSpan>, // one line per row
+    pub(crate) column_labels: Option<TextSpan>, // one line per column
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HeatmapOptions<'a> {
+    pub row_labels: &'a [&'a str], // from the top, rows past the end of the list are left unlabelled
+    pub column_labels: &'a [&'a str], // from the left
+    pub cell_size: Option<[u32; 2]>, // 16x16 pixels (2x1 cells in terminals), widened to fit the column labels by default
+    pub gap: u32,                    // between neighbouring cells
+    pub cell_radius: f32,
+}
+
+// rows of text cells in columns sized to their widest cell, with an optional header row above them
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Table {
+    pub(crate) columns: TableColumnSpan,
+    pub(crate) widths: TableColumnSpan, // in the tree's table width buffer, one per column, set by the measure pass
+    pub(crate) cells: TextSpan, // one line per cell, the header row first (if there is one) and then row by row
+    pub(crate) rows: u32,       // not counting the header
+    pub(crate) header: bool,
+    pub(crate) cell_padding: Option<[u32; 2]>, // x, y on each side of every cell
+    pub(crate) header_color: Option<Color>, // text colour of the header, the element's by default
+    pub(crate) header_background: Option<Color>,
+    pub(crate) bold_header: bool,
+    pub(crate) zebra_color: Option<Color>, // background of every other row
+    pub(crate) divider_thickness: u32,     // horizontal dividers between rows, 0 for none
+    pub(crate) divider_color: Color,
+}
+
+// the sizing and alignment of one table column, stored in the tree's table column buffer
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct TableColumnLayout {
+    pub(crate) width: SizingPolicy,
+    pub(crate) align: Align,
+}
+
+// e.g. `TableColumn::new("latency")`, columns with an empty header don't show a header row unless another column has one
+#[derive(Clone, Copy, Debug)]
+pub struct TableColumn<'a> {
+    pub header: &'a str,
+    // auto fits the widest cell, fill parent also shares out the space left over in a wider table,
+    // fixed sets the width including the cell padding. auto and fill parent columns shrink when the table is too narrow
+    pub width: SizingPolicy,
+    pub align: Option<Align>, // end for columns of numbers, start otherwise
+}
+
+impl<'a> TableColumn<'a> {
+    pub fn new(header: &'a str) -> Self {
+        Self {
+            header,
+            width: SizingPolicy::Auto,
+            align: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
+pub struct TableOptions {
+    pub cell_padding: Option<[u32; 2]>, // x, y on each side of every cell, [6, 3] pixels ([1, 0] cells in terminals) by default
+    pub header_color: Option<Color>, // text colour of the header row, the table's text colour by default
+    pub header_background: Option<Color>,
+    pub bold_header: Option<bool>,    // true by default
+    pub zebra_color: Option<Color>,   // background of every other row below the header
+    pub row_dividers: u32, // thickness of the horizontal dividers between rows, 0 for none
+    pub divider_color: Option<Color>, // the theme's muted colour by default
+}
+
+// text for all elements is stored in one buffer per tree so we don't need a new string for every text element
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextSpan {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Element {
     pub(crate) _type: ElementType, // 'type' is a reserved word in rust
     pub(crate) size: [u32; 2],
     pub(crate) style: Style,
     pub(crate) text_style: Option<TextStyle>,
     pub(crate) frame_position: Option<[u32; 2]>, // element positions are None until the layout pass
-    pub(crate) children: Vec<Element>, // for now we will render all children first -> last = left -> right, but this could be configurable in future
+    // children are linked by their index in the tree, for now we will render all children first -> last = left -> right
+    pub(crate) first_child: Option<usize>,
+    pub(crate) last_child: Option<usize>,
+    pub(crate) next_sibling: Option<usize>,
+    pub(crate) parent: Option<usize>, // used for matching descendant selectors in stylesheets
+    pub(crate) child_count: usize,
+    pub(crate) classes: Option<TextSpan>, // space separated stylesheet classes, stored in the tree's text buffer
+    pub(crate) id: u64, // stable identity between frames, set by the UI builder from the parent id and key/ child index
+    pub(crate) animation: Option<AnimationFrame>, // set after the layout pass if the element is in an enter/ exit transition
+    pub(crate) hash: u64, // content hash of the element and its children, used to skip unchanged subtrees
+    pub(crate) measured_constraints: Option<[u32; 2]>, // constraints the current size was measured with
 }
 
 impl Element {
@@ -40,7 +419,16 @@ impl Element {
             style: style.unwrap_or_default(),
             text_style: None,
             frame_position: None,
-            children: Vec::new(),
+            first_child: None,
+            last_child: None,
+            next_sibling: None,
+            parent: None,
+            child_count: 0,
+            classes: None,
+            id: 0,
+            animation: None,
+            hash: 0,
+            measured_constraints: None,
         }
     }
 
@@ -50,50 +438,391 @@ impl Element {
         thickness: u32,
         style: Option<Style>,
     ) -> Self {
-        Self {
-            _type: ElementType::Divider(orientation, thickness),
-            size: [0, 0], // will be overwritten if using SizingPolicy::Auto in style
-            style: style.unwrap_or_default(),
-            text_style: None,
-            frame_position: None,
-            children: Vec::new(),
-        }
+        Self::new(ElementType::Divider(orientation, thickness), style)
     }
 
     pub fn new_image(texture_id: Uuid, style: Option<Style>) -> Self {
-        Self {
-            _type: ElementType::Image(texture_id),
-            size: [0, 0], // will be overwritten if using SizingPolicy::Auto in style
-            style: style.unwrap_or_default(),
-            text_style: None,
-            frame_position: None,
-            children: Vec::new(),
-        }
+        Self::new(ElementType::Image(texture_id), style)
     }
 
-    pub fn new_text(text: String, style: Option<Style>, text_style: TextStyle) -> Self {
+    pub fn new_text(text: TextSpan, style: Option<Style>, text_style: TextStyle) -> Self {
         Self {
-            _type: ElementType::Text(text),
-            size: [0, 0], // will be overwritten if using SizingPolicy::Auto in style
-            style: style.unwrap_or_default(),
             text_style: Some(text_style),
-            frame_position: None,
-            children: Vec::new(),
+            ..Self::new(ElementType::Text(text), style)
         }
     }
 
     pub fn new_root(size: [u32; 2]) -> Self {
         Self {
-            _type: ElementType::Root,
             size,
-            style: Style::default(),
-            text_style: None,
-            frame_position: None,
-            children: Vec::new(),
+            ..Self::new(ElementType::Root, None)
+        }
+    }
+}
+
+// like text, numeric data (e.g. sparkline values) for all elements is stored in one buffer per tree
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataSpan {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PathSpan {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorSpan {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeriesSpan {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableColumnSpan {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+// all elements of a frame are stored in a single arena, which is cleared (but keeps its capacity) between frames
+// so steady state frames don't need to allocate
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElementTree {
+    pub(crate) elements: Vec<Element>, // root is always at index 0
+    pub(crate) text: String,
+    pub(crate) data: Vec<f32>,
+    pub(crate) path_commands: Vec<PathCommand>,
+    pub(crate) colors: Vec<Color>,
+    pub(crate) series: Vec<ChartSeries>,
+    pub(crate) table_columns: Vec<TableColumnLayout>,
+    pub(crate) table_column_widths: Vec<u32>,
+    // formats chart labels during the measure and render passes, kept so steady state frames don't need a new string
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) scratch_text: RefCell<String>,
+    pub(crate) text_measurement: TextMeasurement, // set by the measure pass
+}
+
+impl ElementTree {
+    pub const ROOT: usize = 0;
+
+    pub fn new(size: [u32; 2]) -> Self {
+        Self {
+            elements: vec![Element::new_root(size)],
+            text: String::new(),
+            data: Vec::new(),
+            path_commands: Vec::new(),
+            colors: Vec::new(),
+            series: Vec::new(),
+            table_columns: Vec::new(),
+            table_column_widths: Vec::new(),
+            scratch_text: RefCell::default(),
+            text_measurement: TextMeasurement::default(),
         }
     }
 
+    // removes all elements except for a fresh root
     pub fn clear(&mut self) {
-        self.children = Vec::new();
+        let size = self.elements[Self::ROOT].size;
+        self.elements.clear();
+        self.elements.push(Element::new_root(size));
+        self.text.clear();
+        self.data.clear();
+        self.path_commands.clear();
+        self.colors.clear();
+        self.series.clear();
+        self.table_columns.clear();
+        self.table_column_widths.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub(crate) fn push_text(&mut self, text: &str) -> TextSpan {
+        let start = self.text.len();
+        self.text.push_str(text);
+        TextSpan {
+            start,
+            end: self.text.len(),
+        }
+    }
+
+    pub(crate) fn text(&self, span: TextSpan) -> &str {
+        &self.text[span.start..span.end]
+    }
+
+    // separate lines of text in one span, e.g. the category labels of a chart
+    // line breaks inside a line are replaced with spaces so every line stays one entry
+    pub(crate) fn push_lines<S: AsRef<str>>(
+        &mut self,
+        lines: impl IntoIterator<Item = S>,
+    ) -> TextSpan {
+        let start = self.text.len();
+        for (i, line) in lines.into_iter().enumerate() {
+            if i > 0 {
+                self.text.push('\n');
+            }
+            for (j, part) in line.as_ref().split('\n').enumerate() {
+                if j > 0 {
+                    self.text.push(' ');
+                }
+                self.text.push_str(part);
+            }
+        }
+        TextSpan {
+            start,
+            end: self.text.len(),
+        }
+    }
+
+    // the string is taken out of the tree while it's used, nested users just get a new one
+    pub(crate) fn take_scratch_text(&self) -> String {
+        self.scratch_text.take()
+    }
+
+    pub(crate) fn return_scratch_text(&self, text: String) {
+        self.scratch_text.replace(text);
+    }
+
+    pub(crate) fn push_data(&mut self, data: impl IntoIterator<Item = f32>) -> DataSpan {
+        let start = self.data.len();
+        self.data.extend(data);
+        DataSpan {
+            start,
+            end: self.data.len(),
+        }
+    }
+
+    pub(crate) fn data(&self, span: DataSpan) -> &[f32] {
+        &self.data[span.start..span.end]
+    }
+
+    pub(crate) fn push_colors(&mut self, colors: impl IntoIterator<Item = Color>) -> ColorSpan {
+        let start = self.colors.len();
+        self.colors.extend(colors);
+        ColorSpan {
+            start,
+            end: self.colors.len(),
+        }
+    }
+
+    pub(crate) fn colors(&self, span: ColorSpan) -> &[Color] {
+        &self.colors[span.start..span.end]
+    }
+
+    pub(crate) fn push_chart_series(
+        &mut self,
+        series: impl IntoIterator<Item = ChartSeries>,
+    ) -> SeriesSpan {
+        let start = self.series.len();
+        self.series.extend(series);
+        SeriesSpan {
+            start,
+            end: self.series.len(),
+        }
+    }
+
+    pub(crate) fn chart_series(&self, span: SeriesSpan) -> &[ChartSeries] {
+        &self.series[span.start..span.end]
+    }
+
+    pub(crate) fn push_table_columns(
+        &mut self,
+        columns: impl IntoIterator<Item = TableColumnLayout>,
+    ) -> TableColumnSpan {
+        let start = self.table_columns.len();
+        self.table_columns.extend(columns);
+        TableColumnSpan {
+            start,
+            end: self.table_columns.len(),
+        }
+    }
+
+    pub(crate) fn table_columns(&self, span: TableColumnSpan) -> &[TableColumnLayout] {
+        &self.table_columns[span.start..span.end]
+    }
+
+    pub(crate) fn push_table_column_widths(
+        &mut self,
+        widths: impl IntoIterator<Item = u32>,
+    ) -> TableColumnSpan {
+        let start = self.table_column_widths.len();
+        self.table_column_widths.extend(widths);
+        TableColumnSpan {
+            start,
+            end: self.table_column_widths.len(),
+        }
+    }
+
+    pub(crate) fn table_column_widths(&self, span: TableColumnSpan) -> &[u32] {
+        &self.table_column_widths[span.start..span.end]
+    }
+
+    pub(crate) fn push_path_commands(&mut self, commands: &[PathCommand]) -> PathSpan {
+        let start = self.path_commands.len();
+        self.path_commands.extend_from_slice(commands);
+        PathSpan {
+            start,
+            end: self.path_commands.len(),
+        }
+    }
+
+    pub(crate) fn path_commands(&self, span: PathSpan) -> &[PathCommand] {
+        &self.path_commands[span.start..span.end]
+    }
+
+    // adds the element as the last child of the parent and returns its index
+    pub(crate) fn push_child(&mut self, parent: usize, element: Element) -> usize {
+        let index = self.elements.len();
+        self.elements.push(element);
+        self.elements[index].parent = Some(parent);
+
+        match self.elements[parent].last_child {
+            Some(last_child) => self.elements[last_child].next_sibling = Some(index),
+            None => self.elements[parent].first_child = Some(index),
+        }
+        let parent_element = &mut self.elements[parent];
+        parent_element.last_child = Some(index);
+        parent_element.child_count += 1;
+        index
+    }
+
+    // iterating only borrows the tree immutably, passes that mutate children should walk next_sibling instead
+    pub(crate) fn children(&self, parent: usize) -> Children<'_> {
+        Children {
+            tree: self,
+            next: self.elements[parent].first_child,
+        }
+    }
+
+    // copies an element and all of its children from another tree, returns the index of the copy
+    pub(crate) fn copy_subtree_from(
+        &mut self,
+        source: &ElementTree,
+        source_index: usize,
+        parent: usize,
+    ) -> usize {
+        let mut element = source[source_index].clone();
+        element.first_child = None;
+        element.last_child = None;
+        element.next_sibling = None;
+        element.child_count = 0;
+        if let ElementType::Text(span) = element._type {
+            element._type = ElementType::Text(self.push_text(source.text(span)));
+        }
+        if let ElementType::Sparkline(mut sparkline) = element._type {
+            sparkline.data = self.push_data(source.data(sparkline.data).iter().copied());
+            element._type = ElementType::Sparkline(sparkline);
+        }
+        if let ElementType::Path(mut path) = element._type {
+            path.commands = self.push_path_commands(source.path_commands(path.commands));
+            element._type = ElementType::Path(path);
+        }
+        if let ElementType::BarChart(mut chart) = element._type {
+            chart.values = self.push_data(source.data(chart.values).iter().copied());
+            chart.colors = self.push_colors(source.colors(chart.colors).iter().copied());
+            chart.labels = chart.labels.map(|span| self.push_text(source.text(span)));
+            element._type = ElementType::BarChart(chart);
+        }
+        if let ElementType::LineChart(mut chart) = element._type {
+            let start = self.series.len();
+            for series in source.chart_series(chart.series) {
+                let copy = ChartSeries {
+                    points: self.push_data(source.data(series.points).iter().copied()),
+                    name: self.push_text(source.text(series.name)),
+                    ..*series
+                };
+                self.series.push(copy);
+            }
+            chart.series = SeriesSpan {
+                start,
+                end: self.series.len(),
+            };
+            chart.unit = chart.unit.map(|span| self.push_text(source.text(span)));
+            element._type = ElementType::LineChart(chart);
+        }
+        if let ElementType::Gauge(mut gauge) = element._type {
+            gauge.label = gauge.label.map(|span| self.push_text(source.text(span)));
+            element._type = ElementType::Gauge(gauge);
+        }
+        if let ElementType::Donut(mut donut) = element._type {
+            donut.values = self.push_data(source.data(donut.values).iter().copied());
+            donut.colors = self.push_colors(source.colors(donut.colors).iter().copied());
+            donut.label = donut.label.map(|span| self.push_text(source.text(span)));
+            element._type = ElementType::Donut(donut);
+        }
+        if let ElementType::Heatmap(mut heatmap) = element._type {
+            heatmap.colors = self.push_colors(source.colors(heatmap.colors).iter().copied());
+            heatmap.row_labels = heatmap
+                .row_labels
+                .map(|span| self.push_text(source.text(span)));
+            heatmap.column_labels = heatmap
+                .column_labels
+                .map(|span| self.push_text(source.text(span)));
+            element._type = ElementType::Heatmap(heatmap);
+        }
+        if let ElementType::Table(mut table) = element._type {
+            let columns = source.table_columns(table.columns).iter().copied();
+            table.columns = self.push_table_columns(columns);
+            let widths = source.table_column_widths(table.widths).iter().copied();
+            table.widths = self.push_table_column_widths(widths);
+            table.cells = self.push_text(source.text(table.cells));
+            element._type = ElementType::Table(table);
+        }
+        if let Some(span) = element.classes {
+            element.classes = Some(self.push_text(source.text(span)));
+        }
+
+        let index = self.push_child(parent, element);
+        for c in source.children(source_index) {
+            self.copy_subtree_from(source, c, index);
+        }
+        index
+    }
+}
+
+impl Index<usize> for ElementTree {
+    type Output = Element;
+
+    fn index(&self, index: usize) -> &Element {
+        &self.elements[index]
+    }
+}
+
+impl IndexMut<usize> for ElementTree {
+    fn index_mut(&mut self, index: usize) -> &mut Element {
+        &mut self.elements[index]
+    }
+}
+
+pub(crate) struct Children<'a> {
+    tree: &'a ElementTree,
+    next: Option<usize>,
+}
+
+impl Iterator for Children<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let current = self.next?;
+        self.next = self.tree[current].next_sibling;
+        Some(current)
     }
 }
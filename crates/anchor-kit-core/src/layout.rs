@@ -1,48 +1,74 @@
-use crate::{anchor::AnchorPosition, element::ElementType, style::Align, Element, FrameInfo};
+use crate::{
+    anchor::AnchorPosition,
+    element::{ElementTree, ElementType},
+    style::Align,
+    FrameInfo,
+};
 
 const FRAME_ORIGIN: [u32; 2] = [0, 0];
 
-pub fn layout_pass(root: &mut Element, frame_info: &FrameInfo) {
+pub fn layout_pass(tree: &mut ElementTree, frame_info: &FrameInfo) {
     // TODO: add some way to check that the tree passed in is valid, and will fit in the window size before rendering
-    for c in root.children.iter_mut() {
+    let mut child = tree[ElementTree::ROOT].first_child;
+    while let Some(c) = child {
         // for all top-level elements the parent position is the frame origin, and parent size is just the entire frame's resolution
-        handle_element_layout(c, FRAME_ORIGIN, frame_info.size);
+        handle_element_layout(tree, c, FRAME_ORIGIN, frame_info.size);
+        child = tree[c].next_sibling;
     }
 }
 
 fn handle_element_layout(
-    element: &mut Element,
+    tree: &mut ElementTree,
+    index: usize,
     allocated_origin: [u32; 2],
     allocated_size: [u32; 2],
 ) {
-    match element._type.clone() {
+    match tree[index]._type {
         ElementType::Root => {
-            for c in element.children.iter_mut() {
-                handle_element_layout(c, allocated_origin, allocated_size);
+            let mut child = tree[index].first_child;
+            while let Some(c) = child {
+                handle_element_layout(tree, c, allocated_origin, allocated_size);
+                child = tree[c].next_sibling;
             }
         }
         ElementType::Anchor(anchor_position) => {
-            handle_anchor_element(element, anchor_position, allocated_origin, allocated_size);
+            handle_anchor_element(
+                tree,
+                index,
+                anchor_position,
+                allocated_origin,
+                allocated_size,
+            );
         }
         ElementType::Text(_) => {
-            handle_text_element(element, allocated_origin);
+            handle_text_element(tree, index, allocated_origin);
         }
-        ElementType::FlexRow => handle_flex_row(element, allocated_origin),
-        ElementType::FlexColumn => handle_flex_column(element, allocated_origin),
-        ElementType::Pill => handle_pill(element, allocated_origin),
-        ElementType::Image(_) => handle_image(element, allocated_origin),
-        ElementType::Divider(_, _) => handle_divider(element, allocated_origin),
+        ElementType::FlexRow => handle_flex_row(tree, index, allocated_origin),
+        ElementType::FlexColumn => handle_flex_column(tree, index, allocated_origin),
+        ElementType::Pill => handle_pill(tree, index, allocated_origin),
+        ElementType::Image(_)
+        | ElementType::Divider(_, _)
+        | ElementType::Progress(_)
+        | ElementType::Sparkline(_)
+        | ElementType::Path(_)
+        | ElementType::BarChart(_)
+        | ElementType::LineChart(_)
+        | ElementType::Gauge(_)
+        | ElementType::Donut(_)
+        | ElementType::Heatmap(_)
+        | ElementType::Table(_) => handle_leaf(tree, index, allocated_origin),
     }
 }
 
 fn handle_anchor_element(
-    element: &mut Element,
+    tree: &mut ElementTree,
+    index: usize,
     anchor_position: AnchorPosition,
     allocated_origin: [u32; 2],
     allocated_size: [u32; 2],
 ) {
     let [aw, ah] = allocated_size;
-    let [ew, eh] = element.size; // set in measure pass
+    let [ew, eh] = tree[index].size; // set in measure pass
 
     // produces the relative x,y that all children elements should be anchored to for rendering
     let (rel_x, rel_y) = match anchor_position {
@@ -57,42 +83,48 @@ fn handle_anchor_element(
         AnchorPosition::BottomRight => ((aw - ew) as f64, (ah - eh) as f64),
     };
 
-    element.frame_position = Some([
+    let frame_position = [
         allocated_origin[0] + rel_x as u32,
         allocated_origin[1] + rel_y as u32,
-    ]);
+    ];
+    tree[index].frame_position = Some(frame_position);
+    let element_size = tree[index].size;
 
-    for c in element.children.iter_mut() {
+    let mut child = tree[index].first_child;
+    while let Some(c) = child {
         handle_element_layout(
+            tree,
             c,
-            element.frame_position.unwrap_or(FRAME_ORIGIN),
-            element.size, // TODO: do we need to account for padding on the anchor position here?
+            frame_position,
+            element_size, // TODO: do we need to account for padding on the anchor position here?
         );
+        child = tree[c].next_sibling;
     }
 }
 
-fn handle_text_element(element: &mut Element, allocated_origin: [u32; 2]) {
-    element.frame_position = Some(allocated_origin);
+fn handle_text_element(tree: &mut ElementTree, index: usize, allocated_origin: [u32; 2]) {
+    tree[index].frame_position = Some(allocated_origin);
 }
 
-fn handle_flex_row(element: &mut Element, allocated_origin: [u32; 2]) {
-    let style = element.style;
-    let num_children = element.children.len();
+fn handle_flex_row(tree: &mut ElementTree, index: usize, allocated_origin: [u32; 2]) {
+    let style = tree[index].style;
+    let num_children = tree[index].child_count;
     let padding_between_children: u32 = 0; // TODO: this is a placeholder for now, it should be set by styling
 
     let [ax, ay] = allocated_origin;
-    element.frame_position = Some(allocated_origin);
+    tree[index].frame_position = Some(allocated_origin);
+    let element_size = tree[index].size;
 
     let mut content_x_start = ax + style.padding.left;
     let content_y_start = ay + style.padding.top;
     let total_content_width =
-        element.size[0].saturating_sub(style.padding.left + style.padding.right);
+        element_size[0].saturating_sub(style.padding.left + style.padding.right);
     let total_content_height =
-        element.size[1].saturating_sub(style.padding.top + style.padding.bottom);
+        element_size[1].saturating_sub(style.padding.top + style.padding.bottom);
 
     // for determining the justify style, we need to iterate over all children to acount for margins to distribute elements correctly
     let mut content_width_with_margin: u32 = 0;
-    for c in element.children.iter() {
+    for c in tree.children(index).map(|c| &tree[c]) {
         content_width_with_margin = content_width_with_margin
             .saturating_add(c.style.margin.left)
             .saturating_add(c.size[0])
@@ -117,57 +149,64 @@ fn handle_flex_row(element: &mut Element, allocated_origin: [u32; 2]) {
     let mut x_offset = content_x_start; // current offset of where to place the next child
 
     // left to right rendering order is assumed for now, but should be configurable in the future
-    for (i, c) in element.children.iter_mut().enumerate() {
-        x_offset = x_offset.saturating_add(c.style.margin.left); // add margin of the child
+    let mut child = tree[index].first_child;
+    let mut i = 0;
+    while let Some(c) = child {
+        let (c_style, c_size) = (tree[c].style, tree[c].size);
+        x_offset = x_offset.saturating_add(c_style.margin.left); // add margin of the child
 
         if i > 0 {
             x_offset = x_offset.saturating_add(padding_between_children);
         }
 
-        let cy = match c.style.align_y {
-            Align::Start => content_y_start + c.style.margin.top,
+        let cy = match c_style.align_y {
+            Align::Start => content_y_start + c_style.margin.top,
             Align::Middle => {
                 content_y_start
-                    + c.style.margin.top
+                    + c_style.margin.top
                     + (total_content_height
-                        .saturating_sub(c.style.margin.top + c.style.margin.bottom) // can only use space without the child elements margins
-                        .saturating_sub(c.size[1])
+                        .saturating_sub(c_style.margin.top + c_style.margin.bottom) // can only use space without the child elements margins
+                        .saturating_sub(c_size[1])
                         / 2)
             }
             Align::End => {
                 content_y_start
                     + total_content_height
-                        .saturating_sub(c.size[1])
-                        .saturating_sub(c.style.margin.bottom)
+                        .saturating_sub(c_size[1])
+                        .saturating_sub(c_style.margin.bottom)
             }
         };
 
         let curr_child_origin = [x_offset, cy];
-        handle_element_layout(c, curr_child_origin, c.size);
+        handle_element_layout(tree, c, curr_child_origin, c_size);
 
         x_offset = x_offset
-            .saturating_add(c.size[0])
-            .saturating_add(c.style.margin.right); // add the current child's width and its margin so the next child is offset correctly
+            .saturating_add(c_size[0])
+            .saturating_add(c_style.margin.right); // add the current child's width and its margin so the next child is offset correctly
+
+        child = tree[c].next_sibling;
+        i += 1;
     }
 }
 
-fn handle_flex_column(element: &mut Element, allocated_origin: [u32; 2]) {
-    let style = element.style;
-    let num_children = element.children.len();
+fn handle_flex_column(tree: &mut ElementTree, index: usize, allocated_origin: [u32; 2]) {
+    let style = tree[index].style;
+    let num_children = tree[index].child_count;
     let padding_between_children: u32 = 0; // TODO: set by styling
 
     let [ax, ay] = allocated_origin;
-    element.frame_position = Some(allocated_origin);
+    tree[index].frame_position = Some(allocated_origin);
+    let element_size = tree[index].size;
 
     let content_x_start = ax + style.padding.left;
     let mut content_y_start = ay + style.padding.top;
     let total_content_width =
-        element.size[0].saturating_sub(style.padding.left + style.padding.right);
+        element_size[0].saturating_sub(style.padding.left + style.padding.right);
     let total_content_height =
-        element.size[1].saturating_sub(style.padding.top + style.padding.bottom);
+        element_size[1].saturating_sub(style.padding.top + style.padding.bottom);
 
     let mut content_height_with_margin: u32 = 0;
-    for c in element.children.iter() {
+    for c in tree.children(index).map(|c| &tree[c]) {
         content_height_with_margin = content_height_with_margin
             .saturating_add(c.style.margin.top)
             .saturating_add(c.size[1])
@@ -191,61 +230,68 @@ fn handle_flex_column(element: &mut Element, allocated_origin: [u32; 2]) {
     let mut y_offset = content_y_start; // vertical offset for placing children
 
     // top down rendering order is assumed for now, we can make this configurable in the future
-    for (i, c) in element.children.iter_mut().enumerate() {
-        y_offset = y_offset.saturating_add(c.style.margin.top);
+    let mut child = tree[index].first_child;
+    let mut i = 0;
+    while let Some(c) = child {
+        let (c_style, c_size) = (tree[c].style, tree[c].size);
+        y_offset = y_offset.saturating_add(c_style.margin.top);
 
         if i > 0 {
             y_offset = y_offset.saturating_add(padding_between_children);
         }
 
-        let cx = match c.style.align_x {
-            Align::Start => content_x_start + c.style.margin.left,
+        let cx = match c_style.align_x {
+            Align::Start => content_x_start + c_style.margin.left,
             Align::Middle => {
                 content_x_start
-                    + c.style.margin.left
+                    + c_style.margin.left
                     + (total_content_width
-                        .saturating_sub(c.style.margin.left + c.style.margin.right) // for middle case we need to use only half of the with after margins
-                        .saturating_sub(c.size[0])
+                        .saturating_sub(c_style.margin.left + c_style.margin.right) // for middle case we need to use only half of the with after margins
+                        .saturating_sub(c_size[0])
                         / 2)
             }
             Align::End => {
                 content_x_start
                     + total_content_width
-                        .saturating_sub(c.size[0])
-                        .saturating_sub(c.style.margin.right)
+                        .saturating_sub(c_size[0])
+                        .saturating_sub(c_style.margin.right)
             }
         };
 
         let curr_child_origin = [cx, y_offset];
-        handle_element_layout(c, curr_child_origin, c.size);
+        handle_element_layout(tree, c, curr_child_origin, c_size);
 
         y_offset = y_offset
-            .saturating_add(c.size[1])
-            .saturating_add(c.style.margin.bottom);
+            .saturating_add(c_size[1])
+            .saturating_add(c_style.margin.bottom);
+
+        child = tree[c].next_sibling;
+        i += 1;
     }
 }
 
-fn handle_pill(element: &mut Element, allocated_origin: [u32; 2]) {
-    let style = element.style;
-    let num_children = element.children.len();
+fn handle_pill(tree: &mut ElementTree, index: usize, allocated_origin: [u32; 2]) {
+    let style = tree[index].style;
+    let num_children = tree[index].child_count;
     let padding_between_children: u32 = 0; // TODO: make configurable
     let [ax, ay] = allocated_origin;
-    element.frame_position = Some(allocated_origin);
+    tree[index].frame_position = Some(allocated_origin);
+    let element_size = tree[index].size;
 
     // pills should behave similar to flex rows where we can justify content within them
     let mut content_x_start = ax + style.padding.left + style.border_width as u32;
     let content_y_start = ay + style.padding.top + style.border_width as u32;
     // border with * 2 for left/right and top/bottom
-    let total_content_width = element.size[0]
+    let total_content_width = element_size[0]
         .saturating_sub(style.padding.left + style.padding.right)
         .saturating_sub(style.border_width as u32 * 2);
-    let total_content_height = element.size[1]
+    let total_content_height = element_size[1]
         .saturating_sub(style.padding.top + style.padding.bottom)
         .saturating_sub(style.border_width as u32 * 2);
 
     // for determining the justify style, we need to iterate over all children to acount for margins to distribute elements correctly
     let mut content_width_with_margin: u32 = 0;
-    for c in element.children.iter() {
+    for c in tree.children(index).map(|c| &tree[c]) {
         content_width_with_margin = content_width_with_margin
             .saturating_add(c.style.margin.left)
             .saturating_add(c.size[0])
@@ -270,44 +316,47 @@ fn handle_pill(element: &mut Element, allocated_origin: [u32; 2]) {
     let mut x_offset = content_x_start; // current offset of where to place the next child
 
     // left to right rendering order is assumed for now, but should be configurable in the future
-    for (i, c) in element.children.iter_mut().enumerate() {
-        x_offset = x_offset.saturating_add(c.style.margin.left); // add margin of the child
+    let mut child = tree[index].first_child;
+    let mut i = 0;
+    while let Some(c) = child {
+        let (c_style, c_size) = (tree[c].style, tree[c].size);
+        x_offset = x_offset.saturating_add(c_style.margin.left); // add margin of the child
 
         if i > 0 {
             x_offset = x_offset.saturating_add(padding_between_children);
         }
 
-        let cy = match c.style.align_y {
-            Align::Start => content_y_start + c.style.margin.top,
+        let cy = match c_style.align_y {
+            Align::Start => content_y_start + c_style.margin.top,
             Align::Middle => {
                 content_y_start
-                    + c.style.margin.top
+                    + c_style.margin.top
                     + (total_content_height
-                        .saturating_sub(c.style.margin.top + c.style.margin.bottom) // can only use space without the child elements margins
-                        .saturating_sub(c.size[1])
+                        .saturating_sub(c_style.margin.top + c_style.margin.bottom) // can only use space without the child elements margins
+                        .saturating_sub(c_size[1])
                         / 2)
             }
             Align::End => {
                 content_y_start
                     + total_content_height
-                        .saturating_sub(c.size[1])
-                        .saturating_sub(c.style.margin.bottom)
+                        .saturating_sub(c_size[1])
+                        .saturating_sub(c_style.margin.bottom)
             }
         };
 
         let curr_child_origin = [x_offset, cy];
-        handle_element_layout(c, curr_child_origin, c.size);
+        handle_element_layout(tree, c, curr_child_origin, c_size);
 
         x_offset = x_offset
-            .saturating_add(c.size[0])
-            .saturating_add(c.style.margin.right); // add the current child's width and its margin so the next child is offset correctly
-    }
-}
+            .saturating_add(c_size[0])
+            .saturating_add(c_style.margin.right); // add the current child's width and its margin so the next child is offset correctly
 
-fn handle_image(element: &mut Element, allocated_origin: [u32; 2]) {
-    element.frame_position = Some(allocated_origin);
+        child = tree[c].next_sibling;
+        i += 1;
+    }
 }
 
-fn handle_divider(element: &mut Element, allocated_origin: [u32; 2]) {
-    element.frame_position = Some(allocated_origin);
+// elements without children fill the space measured for them, so they only need a position
+fn handle_leaf(tree: &mut ElementTree, index: usize, allocated_origin: [u32; 2]) {
+    tree[index].frame_position = Some(allocated_origin);
 }
@@ -0,0 +1,659 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{self, Write},
+    fs, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    anchor::AnchorPosition,
+    color_scale::{ColorScale, DivergingScale, SequentialScale, ThresholdScale},
+    element::{
+        BarChartOptions, BarSeries, DividerOrientation, DonutOptions, DonutSlice, GaugeOptions,
+        HeatmapOptions, LineChartOptions, LineSeries, ProgressOptions, SparklineOptions,
+        TableColumn, TableOptions,
+    },
+    primitives::{self, color::Color},
+    style::{Align, PartialTextStyle, SizingPolicy, Style, TextStyle},
+    time_series::TimeSeries,
+    UI,
+};
+
+// one node per `UI` builder call, so building a document produces the same element tree as the equivalent closure
+// e.g. in ron: `Anchor(position: TopLeft, children: [Pill(children: [Text(text: "fps: {fps}")])])`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum LayoutNode {
+    Anchor {
+        position: AnchorPosition,
+        #[serde(default)]
+        style: Option<Style>,
+        #[serde(default)]
+        text_style: Option<PartialTextStyle>, // inherited by all text inside the container
+        #[serde(default)]
+        children: Vec<LayoutNode>,
+    },
+    FlexRow {
+        #[serde(default)]
+        style: Option<Style>,
+        #[serde(default)]
+        text_style: Option<PartialTextStyle>, // inherited by all text inside the container
+        #[serde(default)]
+        children: Vec<LayoutNode>,
+    },
+    FlexColumn {
+        #[serde(default)]
+        style: Option<Style>,
+        #[serde(default)]
+        text_style: Option<PartialTextStyle>, // inherited by all text inside the container
+        #[serde(default)]
+        children: Vec<LayoutNode>,
+    },
+    Pill {
+        #[serde(default)]
+        style: Option<Style>,
+        #[serde(default)]
+        text_style: Option<PartialTextStyle>, // inherited by all text inside the container
+        #[serde(default)]
+        children: Vec<LayoutNode>,
+    },
+    Text {
+        text: String, // can contain `{name}` placeholders, use `{{` and `}}` for literal braces
+        #[serde(default)]
+        style: Option<Style>,
+        #[serde(default)]
+        text_style: Option<TextStyle>,
+    },
+    Image {
+        image: String, // name of an image binding
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    Divider {
+        orientation: DividerOrientation,
+        thickness: u32,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    Progress {
+        value: String, // a number or a `{name}` placeholder, e.g. `"{cpu}"`
+        range: [f32; 2],
+        #[serde(default)]
+        options: Option<ProgressOptions>,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    Sparkline {
+        values: String, // name of a values binding
+        #[serde(default)]
+        options: Option<SparklineOptions>,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    Path {
+        path: primitives::path::Path,
+        #[serde(default)]
+        viewbox: Option<[f32; 4]>,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    BarChart {
+        #[serde(default)]
+        categories: Vec<String>,
+        series: Vec<LayoutBarSeries>,
+        #[serde(default)]
+        options: Option<BarChartOptions>,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    LineChart {
+        series: Vec<LayoutLineSeries>,
+        #[serde(default)]
+        unit: String, // can contain `{name}` placeholders
+        #[serde(default)]
+        options: Option<LineChartOptions<'static>>,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    Gauge {
+        value: String, // a number or a `{name}` placeholder
+        range: [f32; 2],
+        #[serde(default)]
+        label: String, // can contain `{name}` placeholders, e.g. `"{cpu}%"`
+        #[serde(default)]
+        options: Option<GaugeOptions<'static>>,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    Donut {
+        values: String, // name of a values binding, one slice per value
+        #[serde(default)]
+        colors: Vec<Color>, // one for each slice, `Theme::series_color` for slices past the end
+        #[serde(default)]
+        label: String, // can contain `{name}` placeholders
+        #[serde(default)]
+        options: Option<DonutOptions<'static>>,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    Heatmap {
+        rows: usize,
+        columns: usize,
+        values: String, // name of a values binding, row by row from the top left
+        scale: LayoutColorScale,
+        #[serde(default)]
+        row_labels: Vec<String>,
+        #[serde(default)]
+        column_labels: Vec<String>,
+        #[serde(default)]
+        cell_size: Option<[u32; 2]>,
+        #[serde(default)]
+        gap: u32,
+        #[serde(default)]
+        cell_radius: f32,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    Table {
+        columns: Vec<LayoutTableColumn>,
+        #[serde(default)]
+        rows: Vec<Vec<String>>, // cells can contain `{name}` placeholders
+        #[serde(default)]
+        options: Option<TableOptions>,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    // same as `ui.key(...)`, for elements that come and go with enter/ exit transitions
+    Keyed {
+        key: String,
+        node: Box<LayoutNode>,
+    },
+    // same as `ui.class(...)`, space separated stylesheet classes for the node
+    Class {
+        class: String,
+        node: Box<LayoutNode>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LayoutBarSeries {
+    pub values: String, // name of a values binding, one per category
+    #[serde(default)]
+    pub color: Option<Color>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LayoutLineSeries {
+    pub name: String,   // shown in the legend
+    pub series: String, // name of a time series binding
+    #[serde(default)]
+    pub color: Option<Color>,
+    #[serde(default)]
+    pub fill: Option<Color>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LayoutTableColumn {
+    #[serde(default)]
+    pub header: String,
+    #[serde(default)]
+    pub width: Option<SizingPolicy>, // auto by default
+    #[serde(default)]
+    pub align: Option<Align>,
+}
+
+// the colour scales that can be written out in a layout file, e.g. `Sequential((domain: (0, 100), palette: ...))`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum LayoutColorScale {
+    Sequential(SequentialScale),
+    Diverging(DivergingScale),
+    Threshold(ThresholdScale), // the thresholds have to be sorted in the file
+}
+
+impl ColorScale for LayoutColorScale {
+    fn color(&self, value: f64) -> Color {
+        match self {
+            LayoutColorScale::Sequential(scale) => scale.color(value),
+            LayoutColorScale::Diverging(scale) => scale.color(value),
+            LayoutColorScale::Threshold(scale) => scale.color(value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutDocument {
+    pub nodes: Vec<LayoutNode>, // top level elements, added to whatever element the document is built in
+    #[serde(skip)]
+    scratch: RefCell<Scratch>,
+}
+
+// substituted text and table cells, kept between builds so steady state frames don't need new strings
+#[derive(Clone, Debug, Default)]
+struct Scratch {
+    text: String,
+    cells: Vec<String>,
+}
+
+// runtime values for the placeholders in a layout document
+// values are overwritten in place, so updating them every frame doesn't allocate once they have been set
+#[derive(Clone, Debug, Default)]
+pub struct Bindings {
+    text: HashMap<String, String>,
+    values: HashMap<String, Vec<f32>>,
+    times: HashMap<String, Vec<f64>>,
+    images: HashMap<String, Uuid>,
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, name: &str, value: impl fmt::Display) {
+        let binding = binding(&mut self.text, name);
+        binding.clear();
+        let _ = write!(binding, "{value}");
+    }
+
+    // e.g. recent frame times for a sparkline or one value per category of a bar chart
+    pub fn set_values(&mut self, name: &str, values: &[f32]) {
+        let binding = binding(&mut self.values, name);
+        binding.clear();
+        binding.extend_from_slice(values);
+    }
+
+    // the points of a line chart series
+    pub fn set_time_series(&mut self, name: &str, series: &TimeSeries) {
+        self.set_values(name, series.values());
+        let binding = binding(&mut self.times, name);
+        binding.clear();
+        binding.extend_from_slice(series.times());
+    }
+
+    pub fn set_image(&mut self, name: &str, texture_id: Uuid) {
+        self.images.insert(name.to_string(), texture_id);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.text.get(name).map(String::as_str)
+    }
+
+    // unbound values are empty, so their elements show no data
+    fn values(&self, name: &str) -> &[f32] {
+        self.values.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    fn times(&self, name: &str) -> &[f64] {
+        self.times.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    // the value of a number or `{name}` template, unbound or invalid values are NaN
+    fn number(&self, template: &str, text: &mut String) -> f32 {
+        self.substitute(template, text);
+        text.trim().parse().unwrap_or(f32::NAN)
+    }
+
+    // replaces `{name}` with the bound value, unbound placeholders are left as is so they are easy to spot
+    fn substitute(&self, text: &str, out: &mut String) {
+        out.clear();
+        let mut rest = text;
+        while let Some(start) = rest.find(['{', '}']) {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+            if rest.starts_with("{{") || rest.starts_with("}}") {
+                out.push_str(&rest[..1]);
+                rest = &rest[2..];
+                continue;
+            }
+            match (rest.starts_with('{'), rest.find('}')) {
+                (true, Some(end)) => {
+                    match self.get(&rest[1..end]) {
+                        Some(value) => out.push_str(value),
+                        None => out.push_str(&rest[..=end]),
+                    }
+                    rest = &rest[end + 1..];
+                }
+                _ => {
+                    out.push_str(&rest[..1]);
+                    rest = &rest[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+    }
+}
+
+// only allocates the first time a name is set
+fn binding<'a, T: Default>(bindings: &'a mut HashMap<String, T>, name: &str) -> &'a mut T {
+    if !bindings.contains_key(name) {
+        bindings.insert(name.to_string(), T::default());
+    }
+    bindings.get_mut(name).unwrap()
+}
+
+#[derive(Debug)]
+pub enum LayoutFileError {
+    Io(io::Error),
+    Ron(ron::error::SpannedError),
+    Json(serde_json::Error),
+    UnknownFormat(PathBuf), // only .ron and .json files are supported
+}
+
+impl fmt::Display for LayoutFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutFileError::Io(err) => write!(f, "failed to read layout file: {err}"),
+            LayoutFileError::Ron(err) => write!(f, "invalid ron layout: {err}"),
+            LayoutFileError::Json(err) => write!(f, "invalid json layout: {err}"),
+            LayoutFileError::UnknownFormat(path) => {
+                write!(f, "unknown layout file format: {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayoutFileError {}
+
+impl LayoutDocument {
+    pub fn new(nodes: Vec<LayoutNode>) -> Self {
+        Self {
+            nodes,
+            scratch: RefCell::default(),
+        }
+    }
+
+    // `Some(...)` can be left out for optional values in ron documents
+    pub fn from_ron(ron: &str) -> Result<Self, LayoutFileError> {
+        ron::Options::default()
+            .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+            .from_str(ron)
+            .map_err(LayoutFileError::Ron)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, LayoutFileError> {
+        serde_json::from_str(json).map_err(LayoutFileError::Json)
+    }
+
+    // adds the document's elements to the ui, e.g. `ui_state.generate_frame(info, |ui| document.build(ui, &bindings))`
+    pub fn build(&self, ui: &mut UI, bindings: &Bindings) {
+        // taken out while it's used, so building the document inside itself just gets a new one
+        let mut scratch = self.scratch.take();
+        build_nodes(&self.nodes, ui, bindings, &mut scratch);
+        self.scratch.replace(scratch);
+    }
+}
+
+fn build_nodes(nodes: &[LayoutNode], ui: &mut UI, bindings: &Bindings, scratch: &mut Scratch) {
+    for node in nodes {
+        build_node(node, ui, bindings, scratch);
+    }
+}
+
+fn build_node(node: &LayoutNode, ui: &mut UI, bindings: &Bindings, scratch: &mut Scratch) {
+    match node {
+        LayoutNode::Anchor {
+            position,
+            style,
+            text_style,
+            children,
+        } => {
+            inherit_text_style(ui, text_style);
+            ui.anchor(*position, *style, |ui| {
+                build_nodes(children, ui, bindings, scratch)
+            })
+        }
+        LayoutNode::FlexRow {
+            style,
+            text_style,
+            children,
+        } => {
+            inherit_text_style(ui, text_style);
+            ui.flex_row(*style, |ui| build_nodes(children, ui, bindings, scratch))
+        }
+        LayoutNode::FlexColumn {
+            style,
+            text_style,
+            children,
+        } => {
+            inherit_text_style(ui, text_style);
+            ui.flex_column(*style, |ui| build_nodes(children, ui, bindings, scratch))
+        }
+        LayoutNode::Pill {
+            style,
+            text_style,
+            children,
+        } => {
+            inherit_text_style(ui, text_style);
+            ui.pill(*style, |ui| build_nodes(children, ui, bindings, scratch))
+        }
+        LayoutNode::Text {
+            text: template,
+            style,
+            text_style,
+        } => {
+            bindings.substitute(template, &mut scratch.text);
+            ui.text(&scratch.text, *style, text_style.clone());
+        }
+        LayoutNode::Image { image, style } => {
+            // unbound images are skipped (e.g. while the texture is still loading)
+            if let Some(texture_id) = bindings.images.get(image) {
+                ui.image(*texture_id, *style);
+            }
+        }
+        LayoutNode::Divider {
+            orientation,
+            thickness,
+            style,
+        } => ui.divider(*orientation, *thickness, *style),
+        LayoutNode::Progress {
+            value,
+            range,
+            options,
+            style,
+        } => {
+            let value = bindings.number(value, &mut scratch.text); // unbound or invalid values show an empty bar
+            ui.progress(value, *range, *options, *style);
+        }
+        LayoutNode::Sparkline {
+            values,
+            options,
+            style,
+        } => ui.sparkline(bindings.values(values), *options, *style),
+        LayoutNode::Path {
+            path,
+            viewbox,
+            style,
+        } => ui.path(path, *viewbox, *style),
+        LayoutNode::BarChart {
+            categories,
+            series,
+            options,
+            style,
+        } => {
+            let series = series.iter().map(|s| BarSeries {
+                values: bindings.values(&s.values),
+                color: s.color,
+            });
+            ui.push_bar_chart(categories, series, *options, *style);
+        }
+        LayoutNode::LineChart {
+            series,
+            unit,
+            options,
+            style,
+        } => {
+            let series = series.iter().map(|s| LineSeries {
+                name: &s.name,
+                times: bindings.times(&s.series),
+                values: bindings.values(&s.series),
+                color: s.color,
+                fill: s.fill,
+            });
+            bindings.substitute(unit, &mut scratch.text);
+            let options = LineChartOptions {
+                unit: &scratch.text,
+                ..options.unwrap_or_default()
+            };
+            ui.push_line_chart(series, Some(options), *style);
+        }
+        LayoutNode::Gauge {
+            value,
+            range,
+            label,
+            options,
+            style,
+        } => {
+            let value = bindings.number(value, &mut scratch.text);
+            bindings.substitute(label, &mut scratch.text);
+            let options = GaugeOptions {
+                label: &scratch.text,
+                ..options.unwrap_or_default()
+            };
+            ui.gauge(value, *range, Some(options), *style);
+        }
+        LayoutNode::Donut {
+            values,
+            colors,
+            label,
+            options,
+            style,
+        } => {
+            let slices = bindings
+                .values(values)
+                .iter()
+                .enumerate()
+                .map(|(i, value)| DonutSlice {
+                    value: *value,
+                    color: colors.get(i).copied(),
+                });
+            bindings.substitute(label, &mut scratch.text);
+            let options = DonutOptions {
+                label: &scratch.text,
+                ..options.unwrap_or_default()
+            };
+            ui.push_donut(slices, Some(options), *style);
+        }
+        LayoutNode::Heatmap {
+            rows,
+            columns,
+            values,
+            scale,
+            row_labels,
+            column_labels,
+            cell_size,
+            gap,
+            cell_radius,
+            style,
+        } => {
+            let options = HeatmapOptions {
+                cell_size: *cell_size,
+                gap: *gap,
+                cell_radius: *cell_radius,
+                ..Default::default()
+            };
+            let labels = [&row_labels[..], &column_labels[..]];
+            let values = bindings.values(values);
+            ui.push_heatmap(*rows, *columns, values, scale, labels, options, *style);
+        }
+        LayoutNode::Table {
+            columns,
+            rows,
+            options,
+            style,
+        } => {
+            // every row gets a cell for each column, so the rows can be sliced out of the cells
+            let width = columns.len().max(1);
+            let cells = &mut scratch.cells;
+            cells.resize(rows.len() * width, String::new());
+            for (row, row_cells) in rows.iter().zip(cells.chunks_mut(width)) {
+                for (i, cell) in row_cells.iter_mut().enumerate() {
+                    match row.get(i) {
+                        Some(template) => bindings.substitute(template, cell),
+                        None => cell.clear(),
+                    }
+                }
+            }
+            let columns = columns.iter().map(|column| TableColumn {
+                header: &column.header,
+                width: column.width.unwrap_or(SizingPolicy::Auto),
+                align: column.align,
+            });
+            ui.push_table(columns, cells.chunks(width), *options, *style);
+        }
+        LayoutNode::Keyed { key, node } => {
+            ui.key(key);
+            build_node(node, ui, bindings, scratch);
+        }
+        LayoutNode::Class { class, node } => {
+            ui.class(class);
+            build_node(node, ui, bindings, scratch);
+        }
+    }
+}
+
+fn inherit_text_style(ui: &mut UI, text_style: &Option<PartialTextStyle>) {
+    if let Some(text_style) = text_style {
+        ui.text_style(text_style.clone());
+    }
+}
+
+// a layout document loaded from a file, which can be reloaded when the file changes on disk
+pub struct LayoutFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    document: LayoutDocument,
+}
+
+impl LayoutFile {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LayoutFileError> {
+        let path = path.as_ref().to_path_buf();
+        let modified = modified_time(&path);
+        let document = read_document(&path)?;
+        Ok(Self {
+            path,
+            modified,
+            document,
+        })
+    }
+
+    // call once per frame, only re-reads the file if its modified time changed
+    // returns true if the document was reloaded, on errors the last valid document is kept
+    pub fn reload_if_changed(&mut self) -> Result<bool, LayoutFileError> {
+        let modified = modified_time(&self.path);
+        if modified == self.modified {
+            return Ok(false);
+        }
+        self.modified = modified; // don't retry a broken file every frame, wait for the next change
+        self.document = read_document(&self.path)?;
+        Ok(true)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn document(&self) -> &LayoutDocument {
+        &self.document
+    }
+
+    pub fn build(&self, ui: &mut UI, bindings: &Bindings) {
+        self.document.build(ui, bindings);
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn read_document(path: &Path) -> Result<LayoutDocument, LayoutFileError> {
+    let contents = fs::read_to_string(path).map_err(LayoutFileError::Io)?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("ron") => LayoutDocument::from_ron(&contents),
+        Some("json") => LayoutDocument::from_json(&contents),
+        _ => Err(LayoutFileError::UnknownFormat(path.to_path_buf())),
+    }
+}
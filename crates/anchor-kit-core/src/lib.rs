@@ -1,21 +1,54 @@
 pub mod anchor;
+pub mod animation;
+mod cache;
+mod chart;
+pub mod color_scale;
+pub mod diff;
 pub mod element;
 pub mod layout;
+#[cfg(feature = "layout-files")]
+pub mod layout_file;
 pub mod measure;
 pub mod primitives;
 pub mod render;
+#[cfg(feature = "serde")]
+mod serialize;
 pub mod style;
+pub mod stylesheet;
+pub mod svg;
+mod table;
+pub mod theme;
+pub mod time_series;
+
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    mem::discriminant,
+    time::Instant,
+};
 
 use anchor::AnchorPosition;
-use element::Element;
+use animation::AnimationState;
+use cache::{hash_element, reuse_previous_measurements};
+use diff::RenderListDiff;
+use element::{Element, ElementTree, ElementType, TextSpan};
 use layout::layout_pass;
 use render::{render_pass, RenderList};
 use uuid::Uuid;
 
 use crate::{
-    element::DividerOrientation,
-    measure::measure_pass,
-    style::{Style, TextStyle},
+    color_scale::ColorScale,
+    element::{
+        BarChart, BarChartOptions, BarSeries, ChartSeries, DividerOrientation, Donut, DonutOptions,
+        DonutSlice, Gauge, GaugeOptions, Heatmap, HeatmapOptions, LineChart, LineChartOptions,
+        LineSeries, PathShape, ProgressBar, ProgressOptions, Sparkline, SparklineOptions, Table,
+        TableColumn, TableColumnLayout, TableColumnSpan, TableOptions,
+    },
+    measure::{measure_pass, TextMeasurement},
+    primitives::path::Path,
+    style::{Align, PartialTextStyle, Style, TextStyle},
+    stylesheet::{ExplicitStyle, Stylesheet},
+    table::is_numeric,
+    theme::Theme,
 };
 
 pub struct FrameInfo {
@@ -23,19 +56,78 @@ pub struct FrameInfo {
 }
 
 // UIState stores the actual elements
+// the element trees and render lists are kept between frames and reused, so steady state frames don't allocate
 pub struct UIState {
-    root: Element,
+    tree: ElementTree,
+    animation_state: AnimationState, // tracks element identity between frames for enter/ exit transitions
+    previous_tree: ElementTree, // last frame's laid out tree, used to skip measuring unchanged subtrees
+    previous_render_list: RenderList, // reused as is if nothing changed since the last frame
+    has_previous_frame: bool,
+    previous_frame_size: [u32; 2],
+    text_measurement: TextMeasurement,
+    stylesheet: Stylesheet,
+    theme: Theme,
 }
 
 // UI is used for building the element tree with closures
 pub struct UI<'a> {
-    current_element: &'a mut Element,
+    tree: &'a mut ElementTree,
+    current_element: usize, // index of the element that new elements are added to
+    next_key: Option<u64>, // user provided key for the next element (otherwise the child index is used)
+    next_classes: Option<TextSpan>, // stylesheet classes for the next element
+    next_text_style: Option<PartialTextStyle>, // inherited text style for the next element
+    inherited_text_style: usize, // closest ancestor with a text style, falls back to the default for the root
+    stylesheet: &'a Stylesheet,
+    theme: &'a Theme,
 }
 
 impl UIState {
     pub fn new(size: [u32; 2]) -> Self {
         Self {
-            root: Element::new_root(size),
+            tree: ElementTree::new(size),
+            animation_state: AnimationState::default(),
+            previous_tree: ElementTree::new(size),
+            previous_render_list: RenderList::default(),
+            has_previous_frame: false,
+            previous_frame_size: size,
+            text_measurement: TextMeasurement::default(),
+            stylesheet: Stylesheet::default(),
+            theme: Theme::default(),
+        }
+    }
+
+    // e.g. switching between `Theme::dark()` and `Theme::high_contrast_light()`, takes effect on the next frame
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn theme_mut(&mut self) -> &mut Theme {
+        &mut self.theme
+    }
+
+    // styles are resolved while the frame is built, so changes apply from the next frame on
+    pub fn set_stylesheet(&mut self, stylesheet: Stylesheet) {
+        self.stylesheet = stylesheet;
+    }
+
+    pub fn stylesheet(&self) -> &Stylesheet {
+        &self.stylesheet
+    }
+
+    // e.g. `ui_state.stylesheet_mut().add(".card { padding: 8; }")?` or `.add_class("card", "padding: 8")?`
+    pub fn stylesheet_mut(&mut self) -> &mut Stylesheet {
+        &mut self.stylesheet
+    }
+
+    // e.g. `TextMeasurement::Cells` for terminal renderers, where layout units are characters instead of pixels
+    pub fn set_text_measurement(&mut self, text_measurement: TextMeasurement) {
+        if self.text_measurement != text_measurement {
+            self.text_measurement = text_measurement;
+            self.has_previous_frame = false; // sizes from the previous frame were measured differently
         }
     }
 
@@ -43,59 +135,265 @@ impl UIState {
     where
         F: FnOnce(&mut UI),
     {
-        self.root.clear(); // clear the previous frame's element tree
+        let mut render_list = RenderList::default();
+        self.generate_frame_into(frame_info, &mut render_list, f);
+        render_list
+    }
+
+    // same as `generate_frame`, but also compares the new frame against the last one so that backends can redraw
+    // only the dirty regions
+    pub fn generate_frame_with_diff<F>(
+        &mut self,
+        frame_info: FrameInfo,
+        f: F,
+    ) -> (RenderList, RenderListDiff)
+    where
+        F: FnOnce(&mut UI),
+    {
+        let has_previous_frame = self.has_previous_frame;
+        let mut render_list = RenderList::default();
+        if !self.render_frame(frame_info, &mut render_list, f) {
+            render_list.clone_from(&self.previous_render_list);
+            return (render_list, RenderListDiff::default());
+        }
+        // the new frame becomes the previous one, swapping it in leaves the old one to compare against
+        let mut previous_render_list = render_list.clone();
+        std::mem::swap(&mut previous_render_list, &mut self.previous_render_list);
+        if !has_previous_frame {
+            previous_render_list = RenderList::default();
+        }
+        let diff = render_list.diff(&previous_render_list);
+        (render_list, diff)
+    }
+
+    // same as `generate_frame`, but writes into an existing render list so its buffers can be reused between frames
+    pub fn generate_frame_into<F>(
+        &mut self,
+        frame_info: FrameInfo,
+        render_list: &mut RenderList,
+        f: F,
+    ) where
+        F: FnOnce(&mut UI),
+    {
+        match self.render_frame(frame_info, render_list, f) {
+            true => self.previous_render_list.clone_from(render_list),
+            false => render_list.clone_from(&self.previous_render_list),
+        }
+    }
+
+    // runs all the passes and renders into `render_list`, unless nothing changed since the last frame. returns
+    // whether it rendered, the caller keeps the previous render list up to date
+    fn render_frame<F>(&mut self, frame_info: FrameInfo, render_list: &mut RenderList, f: F) -> bool
+    where
+        F: FnOnce(&mut UI),
+    {
+        // keep the previous frame's tree around so we can compare against it
+        std::mem::swap(&mut self.tree, &mut self.previous_tree);
+        self.tree.clear(); // clear the previous frame's element tree
 
         f(&mut UI {
-            current_element: &mut self.root,
+            tree: &mut self.tree,
+            current_element: ElementTree::ROOT,
+            next_key: None,
+            next_classes: None,
+            next_text_style: None,
+            inherited_text_style: ElementTree::ROOT,
+            stylesheet: &self.stylesheet,
+            theme: &self.theme,
         });
+        self.tree[ElementTree::ROOT].hash = hash_element(&self.tree, ElementTree::ROOT);
 
-        let mut render_list = RenderList::default();
+        // if nothing changed we can skip all the passes and just reuse the last render list
+        let unchanged = self.has_previous_frame
+            && self.tree[ElementTree::ROOT].hash == self.previous_tree[ElementTree::ROOT].hash
+            && frame_info.size == self.previous_frame_size
+            && !self.animation_state.is_animating();
+        if unchanged {
+            std::mem::swap(&mut self.tree, &mut self.previous_tree);
+            return false;
+        }
 
-        measure_pass(&mut self.root, &frame_info);
-        layout_pass(&mut self.root, &frame_info);
-        render_pass(&self.root, &mut render_list);
+        if self.has_previous_frame && frame_info.size == self.previous_frame_size {
+            reuse_previous_measurements(
+                &mut self.tree,
+                ElementTree::ROOT,
+                &self.previous_tree,
+                ElementTree::ROOT,
+            );
+        }
+        measure_pass(&mut self.tree, &frame_info, self.text_measurement);
+        layout_pass(&mut self.tree, &frame_info);
+        // animations need the laid out positions of elements, and also add any exiting elements back to the tree
+        self.animation_state
+            .update(&mut self.tree, &self.previous_tree, Instant::now());
+        render_pass(&self.tree, render_list);
 
-        render_list
+        self.has_previous_frame = true;
+        self.previous_frame_size = frame_info.size;
+        true
     }
 }
 
 impl<'a> UI<'a> {
-    pub fn anchor<F>(&mut self, anchor_position: AnchorPosition, style: Option<Style>, f: F)
+    // sets a key for the next element so it keeps the same identity between frames even if its siblings change
+    // e.g. `ui.key("alert").pill(...)` for an element that is only shown some of the time
+    pub fn key<K: Hash>(&mut self, key: K) -> &mut Self {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        self.next_key = Some(hasher.finish());
+        self
+    }
+
+    // the current theme, so styles can use its tokens, e.g. `background_color: ui.theme().color(ColorToken::Surface)`
+    pub fn theme(&self) -> &'a Theme {
+        self.theme
+    }
+
+    // sets space separated stylesheet classes for the next element, e.g. `ui.class("card selected").pill(None, ...)`
+    // with an explicit `Some(Style {...})` the rules only fill in the fields it leaves at their default value
+    pub fn class(&mut self, classes: &str) -> &mut Self {
+        self.next_classes = Some(self.tree.push_text(classes));
+        self
+    }
+
+    // sets partial text style properties that are inherited by all text inside the next element
+    // e.g. `ui.text_style(PartialTextStyle { font_size: Some(12.0), ..Default::default() }).pill(None, ...)`
+    pub fn text_style(&mut self, text_style: PartialTextStyle) -> &mut Self {
+        self.next_text_style = Some(text_style);
+        self
+    }
+
+    // elements with an explicit style keep it, otherwise the matching stylesheet rules are applied to the default
+    // text styles cascade like in css: inherited from the closest container, then stylesheet rules, then the partial
+    // style from `text_style()`. text with an explicit `TextStyle` ignores all of them
+    fn resolve_style(&mut self, element: &mut Element, explicit: ExplicitStyle) {
+        element.classes = self.next_classes.take();
+        let partial_text_style = self.next_text_style.take();
+
+        let inherits_text_style = match element._type {
+            ElementType::Text(_)
+            | ElementType::BarChart(_)
+            | ElementType::LineChart(_)
+            | ElementType::Gauge(_)
+            | ElementType::Donut(_)
+            | ElementType::Heatmap(_)
+            | ElementType::Table(_) => !explicit.text_style,
+            // containers only need their own copy if something could change it, otherwise children look further up
+            ElementType::Anchor(_)
+            | ElementType::FlexRow
+            | ElementType::FlexColumn
+            | ElementType::Pill => partial_text_style.is_some() || !self.stylesheet.is_empty(),
+            _ => false,
+        };
+        if inherits_text_style {
+            let text_style = element.text_style.get_or_insert_with(TextStyle::default);
+            match &self.tree[self.inherited_text_style].text_style {
+                Some(inherited) => text_style.clone_from(inherited),
+                None => *text_style = TextStyle::default(),
+            }
+        }
+
+        if !self.stylesheet.is_empty() {
+            self.stylesheet.apply(
+                self.tree,
+                self.current_element,
+                element,
+                explicit,
+                self.theme,
+            );
+        }
+        if let (Some(partial), Some(text_style), false) = (
+            partial_text_style,
+            element.text_style.as_mut(),
+            explicit.text_style,
+        ) {
+            partial.apply_to(text_style);
+        }
+    }
+
+    // element ids are based on the parent's id, the element type and either the key or the child index
+    fn assign_id(&mut self, element: &mut Element) {
+        let parent = &self.tree[self.current_element];
+        let mut hasher = DefaultHasher::new();
+        parent.id.hash(&mut hasher);
+        discriminant(&element._type).hash(&mut hasher);
+        match self.next_key.take() {
+            Some(key) => key.hash(&mut hasher),
+            None => parent.child_count.hash(&mut hasher),
+        }
+        element.id = hasher.finish();
+    }
+
+    // adds the element to the current element, then builds its children with the closure
+    fn push_with_children<F>(&mut self, mut element: Element, explicit: ExplicitStyle, f: F)
     where
         F: FnOnce(&mut UI),
     {
-        let mut anchor_element = Element::new(element::ElementType::Anchor(anchor_position), style);
+        self.assign_id(&mut element);
+        self.resolve_style(&mut element, explicit);
+        let index = self.tree.push_child(self.current_element, element);
+        let inherited_text_style = match self.tree[index].text_style {
+            Some(_) => index,
+            None => self.inherited_text_style,
+        };
         f(&mut UI {
-            current_element: &mut anchor_element,
-        }); // handle all child elements of the anchor position
-        self.current_element.children.push(anchor_element);
+            tree: self.tree,
+            current_element: index,
+            next_key: None,
+            next_classes: None,
+            next_text_style: None,
+            inherited_text_style,
+            stylesheet: self.stylesheet,
+            theme: self.theme,
+        });
+        self.tree[index].hash = hash_element(self.tree, index); // children are built first so their hashes are already set
     }
 
-    pub fn text(&mut self, text: String, style: Option<Style>, text_style: Option<TextStyle>) {
-        let text_element = Element::new_text(text, style, text_style.unwrap_or_default());
-        self.current_element.children.push(text_element);
+    fn push(&mut self, mut element: Element, explicit: ExplicitStyle) {
+        self.assign_id(&mut element);
+        self.resolve_style(&mut element, explicit);
+        let index = self.tree.push_child(self.current_element, element);
+        self.tree[index].hash = hash_element(self.tree, index);
+    }
+
+    pub fn anchor<F>(&mut self, anchor_position: AnchorPosition, style: Option<Style>, f: F)
+    where
+        F: FnOnce(&mut UI),
+    {
+        let anchor_element = Element::new(element::ElementType::Anchor(anchor_position), style);
+        self.push_with_children(anchor_element, explicit_style(&style), f); // handle all child elements of the anchor position
+    }
+
+    // text is copied into the tree's text buffer, so both `String` and `&str` can be passed in
+    pub fn text<S: AsRef<str>>(
+        &mut self,
+        text: S,
+        style: Option<Style>,
+        text_style: Option<TextStyle>,
+    ) {
+        let explicit = ExplicitStyle {
+            style: style.is_some(),
+            text_style: text_style.is_some(),
+        };
+        let text_span = self.tree.push_text(text.as_ref());
+        let text_element = Element::new_text(text_span, style, text_style.unwrap_or_default());
+        self.push(text_element, explicit);
     }
 
     pub fn flex_row<F>(&mut self, style: Option<Style>, f: F)
     where
         F: FnOnce(&mut UI),
     {
-        let mut flex_row_element = Element::new(element::ElementType::FlexRow, style);
-        f(&mut UI {
-            current_element: &mut flex_row_element,
-        });
-        self.current_element.children.push(flex_row_element);
+        let flex_row_element = Element::new(element::ElementType::FlexRow, style);
+        self.push_with_children(flex_row_element, explicit_style(&style), f);
     }
 
     pub fn flex_column<F>(&mut self, style: Option<Style>, f: F)
     where
         F: FnOnce(&mut UI),
     {
-        let mut flex_column_element = Element::new(element::ElementType::FlexColumn, style);
-        f(&mut UI {
-            current_element: &mut flex_column_element,
-        });
-        self.current_element.children.push(flex_column_element);
+        let flex_column_element = Element::new(element::ElementType::FlexColumn, style);
+        self.push_with_children(flex_column_element, explicit_style(&style), f);
     }
 
     // pills have a closure so we can put text etc. inside of them
@@ -103,16 +401,13 @@ impl<'a> UI<'a> {
     where
         F: FnOnce(&mut UI),
     {
-        let mut pill_element = Element::new(element::ElementType::Pill, style);
-        f(&mut UI {
-            current_element: &mut pill_element,
-        });
-        self.current_element.children.push(pill_element);
+        let pill_element = Element::new(element::ElementType::Pill, style);
+        self.push_with_children(pill_element, explicit_style(&style), f);
     }
 
     pub fn image(&mut self, texture_id: Uuid, style: Option<Style>) {
         let image_element = Element::new_image(texture_id, style);
-        self.current_element.children.push(image_element);
+        self.push(image_element, explicit_style(&style));
     }
 
     pub fn divider(
@@ -122,6 +417,513 @@ impl<'a> UI<'a> {
         style: Option<Style>,
     ) {
         let divider_element = Element::new_divider(orientation, thickness, style);
-        self.current_element.children.push(divider_element);
+        self.push(divider_element, explicit_style(&style));
+    }
+
+    // a fill bar for values like cpu usage or health, `range` is the [min, max] of the value
+    // e.g. `ui.progress(cpu, [0.0, 100.0], None, Some(Style { border_radius: [4.0; 4], ..Default::default() }))`
+    pub fn progress(
+        &mut self,
+        value: f32,
+        range: [f32; 2],
+        options: Option<ProgressOptions>,
+        style: Option<Style>,
+    ) {
+        let options = options.unwrap_or_default();
+        let progress = ProgressBar {
+            fraction: range_fraction(value, range),
+            orientation: options
+                .orientation
+                .unwrap_or(DividerOrientation::Horizontal),
+            fill_color: options.fill_color.unwrap_or(self.theme.accent),
+            segments: options.segments,
+            segment_gap: options.segment_gap,
+        };
+        let progress_element = Element::new(element::ElementType::Progress(progress), style);
+        self.push(progress_element, explicit_style(&style));
+    }
+
+    // a small line chart, e.g. recent frame times next to the current value: `ui.sparkline(&frame_times, None, None)`
+    // the y range fits the values unless it is set in the options, non finite values leave a gap in the line
+    pub fn sparkline(
+        &mut self,
+        values: &[f32],
+        options: Option<SparklineOptions>,
+        style: Option<Style>,
+    ) {
+        let options = options.unwrap_or_default();
+        let range = options.range.unwrap_or_else(|| {
+            values
+                .iter()
+                .filter(|v| v.is_finite())
+                .fold(None, |range: Option<[f32; 2]>, v| match range {
+                    Some([min, max]) => Some([min.min(*v), max.max(*v)]),
+                    None => Some([*v, *v]),
+                })
+                .unwrap_or([0.0, 0.0])
+        });
+        let line_width = options.line_width.unwrap_or(1.5);
+        let sparkline = Sparkline {
+            data: self.tree.push_data(values.iter().copied()),
+            range,
+            line_color: options.line_color.unwrap_or(self.theme.accent),
+            line_width,
+            fill_color: options.fill_color,
+            dot_radius: match options.dot {
+                true => Some(options.dot_radius.unwrap_or(line_width * 2.0)),
+                false => None,
+            },
+        };
+        let sparkline_element = Element::new(element::ElementType::Sparkline(sparkline), style);
+        self.push(sparkline_element, explicit_style(&style));
+    }
+
+    // draws a vector path scaled to fit the element, the viewbox (x, y, w, h in path units) is the part of the path
+    // that is shown and defaults to the bounds of the path and its stroke
+    pub fn path(&mut self, path: &Path, viewbox: Option<[f32; 4]>, style: Option<Style>) {
+        let viewbox = viewbox.unwrap_or_else(|| {
+            let half_width = path.stroke.map_or(0.0, |stroke| stroke.width / 2.0);
+            let mut min = [f32::MAX, f32::MAX];
+            let mut max = [f32::MIN, f32::MIN];
+            path.flatten(|points, _| {
+                for [x, y] in points {
+                    min = [min[0].min(*x), min[1].min(*y)];
+                    max = [max[0].max(*x), max[1].max(*y)];
+                }
+            });
+            match min[0] <= max[0] {
+                true => [
+                    min[0] - half_width,
+                    min[1] - half_width,
+                    max[0] - min[0] + half_width * 2.0,
+                    max[1] - min[1] + half_width * 2.0,
+                ],
+                false => [0.0, 0.0, 0.0, 0.0],
+            }
+        });
+        let shape = PathShape {
+            commands: self.tree.push_path_commands(&path.commands),
+            viewbox,
+            fill: path.fill,
+            fill_rule: path.fill_rule,
+            stroke: path.stroke,
+        };
+        let path_element = Element::new(element::ElementType::Path(shape), style);
+        self.push(path_element, explicit_style(&style));
+    }
+
+    // bars for each category with one series per bar in a group (or per segment of a stack when stacked)
+    // e.g. `ui.bar_chart(&["mon", "tue"], &[BarSeries::new(&[3.0, 5.0])], None, None)`, labels use the inherited
+    // text style
+    pub fn bar_chart<S: AsRef<str>>(
+        &mut self,
+        categories: &[S],
+        series: &[BarSeries],
+        options: Option<BarChartOptions>,
+        style: Option<Style>,
+    ) {
+        self.push_bar_chart(categories, series.iter().copied(), options, style);
+    }
+
+    // the series as an iterator, so layout files can pass their bound values without collecting them every frame
+    pub(crate) fn push_bar_chart<'s, S: AsRef<str>>(
+        &mut self,
+        categories: &[S],
+        series: impl ExactSizeIterator<Item = BarSeries<'s>> + Clone,
+        options: Option<BarChartOptions>,
+        style: Option<Style>,
+    ) {
+        let options = options.unwrap_or_default();
+        let category_count = match categories.len() {
+            0 => series.clone().map(|s| s.values.len()).max().unwrap_or(0),
+            len => len,
+        };
+        // every series gets a value for each category, so they can be indexed directly
+        let values = self.tree.push_data(series.clone().flat_map(|s| {
+            (0..category_count).map(move |i| s.values.get(i).copied().unwrap_or(f32::NAN))
+        }));
+        let colors = self.tree.push_colors(
+            series
+                .clone()
+                .enumerate()
+                .map(|(i, s)| s.color.unwrap_or_else(|| self.theme.series_color(i))),
+        );
+        let labels = match options.category_axis {
+            true => Some(
+                self.tree
+                    .push_lines(&categories[..categories.len().min(category_count)]),
+            ),
+            false => None,
+        };
+        let mut chart = BarChart {
+            values,
+            colors,
+            labels,
+            category_count,
+            range: [0.0, 0.0],
+            orientation: options.orientation.unwrap_or(DividerOrientation::Vertical),
+            stacked: options.stacked,
+            category_gap: options.category_gap.unwrap_or(0.2),
+            bar_radius: options.bar_radius,
+            value_labels: options.value_labels,
+            value_decimals: options.value_decimals,
+            axis_color: options.axis_color.unwrap_or(self.theme.muted),
+        };
+        chart.range = options.range.unwrap_or_else(|| {
+            // the bars always start from zero, so it is always in the range
+            let [mut min, mut max] = [0.0f32, 0.0f32];
+            for category in 0..category_count {
+                let mut include = |value: f32| [min, max] = [min.min(value), max.max(value)];
+                match chart.stacked {
+                    true => {
+                        for value in chart::bar_stack(self.tree, &chart, category) {
+                            include(value);
+                        }
+                    }
+                    false => {
+                        for s in 0..series.len() {
+                            let value = chart::bar_series(self.tree, &chart, s)[category];
+                            if value.is_finite() {
+                                include(value);
+                            }
+                        }
+                    }
+                }
+            }
+            match min == max {
+                true => [0.0, 1.0],
+                false => [min, max],
+            }
+        });
+        let chart_element = Element::new(element::ElementType::BarChart(chart), style);
+        self.push(chart_element, explicit_style(&style));
+    }
+
+    // lines over time for one or more series, e.g. `ui.line_chart(&[LineSeries::new("cpu", &times, &load)], None, None)`
+    // times are in seconds and sorted, the axes fit the points unless they are set in the options and the labels use
+    // the inherited text style
+    pub fn line_chart(
+        &mut self,
+        series: &[LineSeries],
+        options: Option<LineChartOptions>,
+        style: Option<Style>,
+    ) {
+        self.push_line_chart(series.iter().copied(), options, style);
+    }
+
+    pub(crate) fn push_line_chart<'s>(
+        &mut self,
+        series: impl IntoIterator<Item = LineSeries<'s>> + Clone,
+        options: Option<LineChartOptions>,
+        style: Option<Style>,
+    ) {
+        let options = options.unwrap_or_default();
+        let time_range = options.time_range.unwrap_or_else(|| {
+            let times = series
+                .clone()
+                .into_iter()
+                .flat_map(|s| s.times.iter().copied());
+            match times
+                .filter(|t| t.is_finite())
+                .fold(None, |range: Option<[f64; 2]>, t| match range {
+                    Some([min, max]) => Some([min.min(t), max.max(t)]),
+                    None => Some([t, t]),
+                }) {
+                Some([min, max]) if min < max => [min, max],
+                Some([time, _]) => [time - 0.5, time + 0.5],
+                None => [0.0, 1.0],
+            }
+        });
+
+        // times are stored from the start of the range, which keeps them precise as f32 even for unix timestamps
+        let mut series_span = self.tree.push_chart_series([]);
+        for (i, s) in series.into_iter().enumerate() {
+            let points = chart::clip_to_time_range(s.times, s.values, time_range)
+                .flat_map(|(time, value)| [(time - time_range[0]) as f32, value]);
+            let chart_series = ChartSeries {
+                points: self.tree.push_data(points),
+                name: self.tree.push_text(s.name),
+                color: s.color.unwrap_or_else(|| self.theme.series_color(i)),
+                fill: s.fill,
+            };
+            series_span.end = self.tree.push_chart_series([chart_series]).end;
+        }
+
+        let range = options.range.unwrap_or_else(|| {
+            let mut fitted: Option<[f32; 2]> = None;
+            for s in self.tree.chart_series(series_span) {
+                for value in self.tree.data(s.points).iter().skip(1).step_by(2) {
+                    if value.is_finite() {
+                        fitted = Some(match fitted {
+                            Some([min, max]) => [min.min(*value), max.max(*value)],
+                            None => [*value, *value],
+                        });
+                    }
+                }
+            }
+            let padding = options.range_padding.unwrap_or(0.1);
+            match fitted {
+                Some([min, max]) if min < max => {
+                    let pad = (max - min) * padding;
+                    [min - pad, max + pad]
+                }
+                // flat lines go through the middle
+                Some([value, _]) => {
+                    let pad = (value.abs() * padding).max(1.0);
+                    [value - pad, value + pad]
+                }
+                None => [0.0, 1.0],
+            }
+        });
+
+        let chart = LineChart {
+            series: series_span,
+            time_range,
+            range,
+            time_format: options.time_format,
+            unit: match options.unit {
+                "" => None,
+                unit => Some(self.tree.push_text(unit)),
+            },
+            line_width: options.line_width.unwrap_or(1.5),
+            gridlines: options.gridlines,
+            legend: options.legend,
+            axis_color: options.axis_color.unwrap_or(self.theme.muted),
+        };
+        let chart_element = Element::new(element::ElementType::LineChart(chart), style);
+        self.push(chart_element, explicit_style(&style));
+    }
+
+    // a ring filled to where the value sits in the range, e.g. `ui.gauge(cpu, [0.0, 100.0], None, None)`
+    // square and 96 pixels across unless the style sets a size, the label is drawn in the centre
+    pub fn gauge(
+        &mut self,
+        value: f32,
+        range: [f32; 2],
+        options: Option<GaugeOptions>,
+        style: Option<Style>,
+    ) {
+        let options = options.unwrap_or_default();
+        let gauge = Gauge {
+            fraction: range_fraction(value, range),
+            angles: options.angles.unwrap_or([-135.0, 135.0]),
+            thickness: options.thickness.unwrap_or(0.2),
+            track_color: options.track_color.unwrap_or(self.theme.muted),
+            fill_color: options.fill_color.unwrap_or(self.theme.accent),
+            rounded_caps: options.rounded_caps.unwrap_or(true),
+            label: match options.label {
+                "" => None,
+                label => Some(self.tree.push_text(label)),
+            },
+        };
+        let gauge_element = Element::new(element::ElementType::Gauge(gauge), style);
+        self.push(gauge_element, explicit_style(&style));
+    }
+
+    // a ring split into slices by their share of the total, e.g. `ui.donut(&[DonutSlice::new(used), DonutSlice::new(free)], None, None)`
+    // sized like a gauge, a thickness of 1.0 in the options makes it a pie chart
+    pub fn donut(
+        &mut self,
+        slices: &[DonutSlice],
+        options: Option<DonutOptions>,
+        style: Option<Style>,
+    ) {
+        self.push_donut(slices.iter().copied(), options, style);
+    }
+
+    pub(crate) fn push_donut(
+        &mut self,
+        slices: impl Iterator<Item = DonutSlice> + Clone,
+        options: Option<DonutOptions>,
+        style: Option<Style>,
+    ) {
+        let options = options.unwrap_or_default();
+        let values = self.tree.push_data(slices.clone().map(|s| s.value));
+        let colors = self.tree.push_colors(
+            slices
+                .clone()
+                .enumerate()
+                .map(|(i, s)| s.color.unwrap_or_else(|| self.theme.series_color(i))),
+        );
+        let total = options.total.unwrap_or_else(|| {
+            slices
+                .map(|s| s.value)
+                .filter(|v| v.is_finite() && *v > 0.0)
+                .sum()
+        });
+        let donut = Donut {
+            values,
+            colors,
+            total,
+            start_angle: options.start_angle,
+            thickness: options.thickness.unwrap_or(0.35),
+            gap: options.gap.max(0.0),
+            rounded_caps: options.rounded_caps,
+            track_color: options.track_color,
+            label: match options.label {
+                "" => None,
+                label => Some(self.tree.push_text(label)),
+            },
+        };
+        let donut_element = Element::new(element::ElementType::Donut(donut), style);
+        self.push(donut_element, explicit_style(&style));
+    }
+
+    // a grid of `rows` by `columns` cells coloured by mapping their values through the colour scale, e.g. load per core
+    // over time: `ui.heatmap(cores, samples, &load, &SequentialScale::viridis([0.0, 100.0]), None, None)`
+    // values are row by row from the top left, missing or NaN values leave their cell empty. grids with more cells than
+    // fit in a u32 are rejected and take no space
+    pub fn heatmap(
+        &mut self,
+        rows: usize,
+        columns: usize,
+        values: &[f32],
+        color_scale: &impl ColorScale,
+        options: Option<HeatmapOptions>,
+        style: Option<Style>,
+    ) {
+        let options = options.unwrap_or_default();
+        let labels = [options.row_labels, options.column_labels];
+        self.push_heatmap(rows, columns, values, color_scale, labels, options, style);
+    }
+
+    // the row and column labels are passed separately (the ones in the options are ignored), so layout files can use
+    // their own strings
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn push_heatmap<L: AsRef<str>>(
+        &mut self,
+        rows: usize,
+        columns: usize,
+        values: &[f32],
+        color_scale: &impl ColorScale,
+        [row_labels, column_labels]: [&[L]; 2],
+        options: HeatmapOptions,
+        style: Option<Style>,
+    ) {
+        let cells = rows.checked_mul(columns).map(u32::try_from);
+        let (rows, columns) = match (cells, u32::try_from(rows), u32::try_from(columns)) {
+            (Some(Ok(_)), Ok(rows), Ok(columns)) => (rows, columns),
+            _ => (0, 0),
+        };
+        let colors = self.tree.push_colors((0..rows * columns).map(|i| {
+            let value = values.get(i as usize).copied().unwrap_or(f32::NAN);
+            color_scale.color(value as f64)
+        }));
+        let mut labels = |labels: &[L], count: u32| match labels.is_empty() || count == 0 {
+            true => None,
+            false => Some(
+                self.tree
+                    .push_lines(&labels[..labels.len().min(count as usize)]),
+            ),
+        };
+        let heatmap = Heatmap {
+            colors,
+            rows,
+            columns,
+            cell_size: options.cell_size,
+            gap: options.gap,
+            cell_radius: options.cell_radius,
+            row_labels: labels(row_labels, rows),
+            column_labels: labels(column_labels, columns),
+        };
+        let heatmap_element = Element::new(element::ElementType::Heatmap(heatmap), style);
+        self.push(heatmap_element, explicit_style(&style));
+    }
+
+    // rows of text in columns sized to fit their widest cell, e.g. key/ value stats:
+    // `ui.table(&[TableColumn::new(""), TableColumn::new("")], &[["fps", "60"], ["frame", "16.6 ms"]], None, None)`
+    // missing cells are left empty and cells past the last column are ignored
+    pub fn table<R: AsRef<[S]>, S: AsRef<str>>(
+        &mut self,
+        columns: &[TableColumn],
+        rows: &[R],
+        options: Option<TableOptions>,
+        style: Option<Style>,
+    ) {
+        self.push_table(columns.iter().copied(), rows.iter(), options, style);
+    }
+
+    pub(crate) fn push_table<'c, 'r, R: AsRef<[S]> + ?Sized + 'r, S: AsRef<str>>(
+        &mut self,
+        columns: impl ExactSizeIterator<Item = TableColumn<'c>> + Clone,
+        rows: impl ExactSizeIterator<Item = &'r R> + Clone,
+        options: Option<TableOptions>,
+        style: Option<Style>,
+    ) {
+        let options = options.unwrap_or_default();
+        let column_count = columns.len();
+        let header = columns.clone().any(|column| !column.header.is_empty());
+        // like heatmaps, tables with more rows or cells than fit in a u32 are left empty
+        let cell_count = (rows.len().checked_add(header as usize))
+            .and_then(|rows| rows.checked_mul(column_count))
+            .map(u32::try_from);
+        let row_count = match (cell_count, u32::try_from(rows.len())) {
+            (Some(Ok(_)), Ok(row_count)) => row_count,
+            _ => 0,
+        };
+        let rows = rows.take(row_count as usize);
+        fn cell<'a, R: AsRef<[S]> + ?Sized, S: AsRef<str> + 'a>(
+            row: &'a R,
+            column: usize,
+        ) -> &'a str {
+            row.as_ref().get(column).map_or("", |c| c.as_ref())
+        }
+        let layouts = columns.clone().enumerate().map(|(i, column)| {
+            let mut cells = rows
+                .clone()
+                .map(|row| cell(row, i))
+                .filter(|c| !c.is_empty());
+            let numeric = cells.clone().next().is_some() && cells.all(is_numeric);
+            TableColumnLayout {
+                width: column.width,
+                align: column.align.unwrap_or(match numeric {
+                    true => Align::End,
+                    false => Align::Start,
+                }),
+            }
+        });
+        let table_columns = self.tree.push_table_columns(layouts);
+        let headers = columns.map(|column| column.header);
+        let body = rows
+            .clone()
+            .flat_map(|row| (0..column_count).map(move |i| cell(row, i)));
+        let cells = match header {
+            true => self.tree.push_lines(headers.chain(body)),
+            false => self.tree.push_lines(body),
+        };
+        let table = Table {
+            columns: table_columns,
+            widths: TableColumnSpan::default(), // measured later
+            cells,
+            rows: row_count,
+            header,
+            cell_padding: options.cell_padding,
+            header_color: options.header_color,
+            header_background: options.header_background,
+            bold_header: options.bold_header.unwrap_or(true),
+            zebra_color: options.zebra_color,
+            divider_thickness: options.row_dividers,
+            divider_color: options.divider_color.unwrap_or(self.theme.muted),
+        };
+        let table_element = Element::new(element::ElementType::Table(table), style);
+        self.push(table_element, explicit_style(&style));
+    }
+}
+
+// where the value sits in a [min, max] range, for progress bars and gauges. empty, reversed and non-finite ranges and
+// values are shown as empty
+fn range_fraction(value: f32, [min, max]: [f32; 2]) -> f32 {
+    match max - min {
+        span if span > 0.0 && span.is_finite() && value.is_finite() => {
+            ((value - min) / span).clamp(0.0, 1.0)
+        }
+        _ => 0.0,
+    }
+}
+
+fn explicit_style(style: &Option<Style>) -> ExplicitStyle {
+    ExplicitStyle {
+        style: style.is_some(),
+        text_style: false,
     }
 }
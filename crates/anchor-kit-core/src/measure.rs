@@ -1,43 +1,145 @@
 use crate::{
-    element::{DividerOrientation, Element, ElementType},
+    chart::{
+        bar_chart_insets, bar_series_count, for_each_legend_item, heatmap_labels,
+        line_chart_layout, ChartMetrics,
+    },
+    element::{
+        BarChart, DividerOrientation, ElementTree, ElementType, Heatmap, LineChart, Table, TextSpan,
+    },
     style::{SizingPolicy, TextStyle},
+    table::{fit_table_column_widths, measure_table_column_widths, TableMetrics},
     FrameInfo,
 };
 
+// how text sizes are estimated, pixel renderers use the font size and terminal renderers use a character grid
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextMeasurement {
+    #[default]
+    FontSize, // based on the font size and line height of the text style
+    Cells, // one unit per character and one per line, the text style sizes are ignored
+}
+
 #[derive(Clone, Copy, Debug)]
 struct Constraints {
     pub max_size: [u32; 2], // w, h
+    pub text_measurement: TextMeasurement,
 }
 
-pub fn measure_pass(root: &mut Element, frame_info: &FrameInfo) {
+pub fn measure_pass(
+    tree: &mut ElementTree,
+    frame_info: &FrameInfo,
+    text_measurement: TextMeasurement,
+) {
+    tree.text_measurement = text_measurement; // charts measure their labels again when they are rendered
     let frame_constraints = Constraints {
         max_size: frame_info.size,
+        text_measurement,
     };
-    measure_element_size(root, &frame_constraints);
+    measure_element_size(tree, ElementTree::ROOT, &frame_constraints);
 }
 
 // returns the required size of the given element based on its content and style
-fn measure_element_size(element: &mut Element, constraints: &Constraints) -> [u32; 2] {
-    match element._type.clone() {
+fn measure_element_size(
+    tree: &mut ElementTree,
+    index: usize,
+    constraints: &Constraints,
+) -> [u32; 2] {
+    // unchanged subtrees keep their size from the previous frame (see cache::reuse_previous_measurements)
+    if tree[index].measured_constraints == Some(constraints.max_size) {
+        return tree[index].size;
+    }
+    tree[index].measured_constraints = Some(constraints.max_size);
+
+    match tree[index]._type {
         ElementType::Root => {
-            for c in element.children.iter_mut() {
-                measure_element_size(c, constraints); // we don't care about the output here since root elements keep the entire frame size
+            let mut child = tree[index].first_child;
+            while let Some(c) = child {
+                measure_element_size(tree, c, constraints); // we don't care about the output here since root elements keep the entire frame size
+                child = tree[c].next_sibling;
             }
-            element.size = constraints.max_size; // just use the top-level constraints size for root (frame size)
-            element.size
+            tree[index].size = constraints.max_size; // just use the top-level constraints size for root (frame size)
+            tree[index].size
         }
-        ElementType::Anchor(_) => measure_anchor_element_size(element, constraints),
-        ElementType::Text(text) => measure_text_element_size(&text, element, constraints),
-        ElementType::FlexRow => measure_flex_row_element_size(element, constraints),
-        ElementType::FlexColumn => measure_flex_column_size(element, constraints),
-        ElementType::Pill => measure_pill_size(element, constraints),
-        ElementType::Image(_) => measure_image_size(element, constraints),
+        ElementType::Anchor(_) => measure_anchor_element_size(tree, index, constraints),
+        ElementType::Text(_) => measure_text_element_size(tree, index, constraints),
+        ElementType::FlexRow => measure_flex_row_element_size(tree, index, constraints),
+        ElementType::FlexColumn => measure_flex_column_size(tree, index, constraints),
+        ElementType::Pill => measure_pill_size(tree, index, constraints),
+        ElementType::Image(_) => measure_image_size(tree, index, constraints),
         ElementType::Divider(orientation, thickness) => {
-            measure_divider_size(orientation, thickness, element, constraints)
+            measure_divider_size(orientation, thickness, tree, index, constraints)
+        }
+        ElementType::Progress(progress) => {
+            measure_progress_size(progress.orientation, tree, index, constraints)
+        }
+        ElementType::Sparkline(_) => measure_sparkline_size(tree, index, constraints),
+        ElementType::Path(path) => measure_path_size(path.viewbox, tree, index, constraints),
+        ElementType::BarChart(chart) => measure_bar_chart_size(&chart, tree, index, constraints),
+        ElementType::LineChart(chart) => measure_line_chart_size(&chart, tree, index, constraints),
+        ElementType::Gauge(gauge) => {
+            measure_radial_size(gauge.label, gauge.thickness, tree, index, constraints)
+        }
+        ElementType::Donut(donut) => {
+            measure_radial_size(donut.label, donut.thickness, tree, index, constraints)
         }
+        ElementType::Heatmap(heatmap) => measure_heatmap_size(&heatmap, tree, index, constraints),
+        ElementType::Table(table) => measure_table_size(&table, tree, index, constraints),
     }
 }
 
+// size of a block of text, also used for the labels that charts draw themselves
+pub(crate) fn measure_text(
+    text: &str,
+    text_style: &TextStyle,
+    text_measurement: TextMeasurement,
+) -> [u32; 2] {
+    match text_measurement {
+        TextMeasurement::FontSize => {
+            let char_w = text_style.font_size * 0.6; // this is just best effort right now, in a future update we should handle this better
+            let text_width = (text.chars().count() as f32 * char_w) as u32;
+            [text_width, text_style.line_height as u32]
+        }
+        TextMeasurement::Cells => {
+            let longest_line = text.lines().map(|l| l.chars().count()).max();
+            [
+                longest_line.unwrap_or(0) as u32,
+                text.lines().count() as u32,
+            ]
+        }
+    }
+}
+
+// the longest start of the text that still fits in `max_width` with an ellipsis after it, written to `out`
+// returns false if not even the ellipsis fits, text that already fits is copied as it is
+pub(crate) fn truncate_text(
+    out: &mut String,
+    text: &str,
+    max_width: u32,
+    text_style: &TextStyle,
+    text_measurement: TextMeasurement,
+) -> bool {
+    out.clear();
+    if measure_text(text, text_style, text_measurement)[0] <= max_width {
+        out.push_str(text);
+        return true;
+    }
+    let ellipsis = measure_text("…", text_style, text_measurement)[0];
+    if ellipsis > max_width {
+        return false;
+    }
+    for c in text.chars() {
+        out.push(c);
+        if measure_text(out, text_style, text_measurement)[0] + ellipsis > max_width {
+            out.pop();
+            break;
+        }
+    }
+    out.truncate(out.trim_end().len()); // "abc …" reads worse than "abc…"
+    out.push('…');
+    true
+}
+
 fn size_from_policy(sizing_policy: SizingPolicy, children_size: u32, parent_size: u32) -> u32 {
     match sizing_policy {
         SizingPolicy::Auto => children_size.min(parent_size), // if size of children is larger than the parent we should still go with the parent size
@@ -47,20 +149,23 @@ fn size_from_policy(sizing_policy: SizingPolicy, children_size: u32, parent_size
 }
 
 fn measure_text_element_size(
-    text: &String,
-    element: &mut Element,
+    tree: &mut ElementTree,
+    index: usize,
     constraints: &Constraints,
 ) -> [u32; 2] {
+    let element = &tree[index];
     let style = element.style;
+    let text = match element._type {
+        ElementType::Text(span) => tree.text(span),
+        _ => "",
+    };
     let text_style_default_binding = TextStyle::default();
     let text_style = element
         .text_style
         .as_ref()
         .unwrap_or(&text_style_default_binding);
 
-    let char_w = text_style.font_size * 0.6; // this is just best effort right now, in a future update we should handle this better
-    let text_width = (text.chars().count() as f32 * char_w) as u32;
-    let text_height = text_style.line_height as u32;
+    let [text_width, text_height] = measure_text(text, text_style, constraints.text_measurement);
 
     let padded_width = text_width + style.padding.left + style.padding.right;
     let padded_height = text_height + style.padding.top + style.padding.bottom;
@@ -68,12 +173,16 @@ fn measure_text_element_size(
     let element_width = size_from_policy(style.width, padded_width, constraints.max_size[0]);
     let element_height = size_from_policy(style.height, padded_height, constraints.max_size[1]);
 
-    element.size = [element_width, element_height]; // set the element size to use in the layout pass
-    element.size
+    tree[index].size = [element_width, element_height]; // set the element size to use in the layout pass
+    tree[index].size
 }
 
-fn measure_anchor_element_size(element: &mut Element, constraints: &Constraints) -> [u32; 2] {
-    let style = element.style;
+fn measure_anchor_element_size(
+    tree: &mut ElementTree,
+    index: usize,
+    constraints: &Constraints,
+) -> [u32; 2] {
+    let style = tree[index].style;
 
     // for anchors their children are either constrained by their fixed size of their parents size
     let child_constraints_w = match style.width {
@@ -86,21 +195,25 @@ fn measure_anchor_element_size(element: &mut Element, constraints: &Constraints)
     };
     let child_constraints = Constraints {
         max_size: [child_constraints_w, child_constraints_h],
+        text_measurement: constraints.text_measurement,
     };
 
     // anchor element's total width is based on the max of their children's sizes (bounding box of max size)
     let mut max_child_width = 0;
     let mut max_child_height = 0;
     // measure child elements first to get their sizes
-    for c in element.children.iter_mut() {
-        let child_size = measure_element_size(c, &child_constraints);
+    let mut child = tree[index].first_child;
+    while let Some(c) = child {
+        let child_size = measure_element_size(tree, c, &child_constraints);
+        let child_margin = tree[c].style.margin;
+        child = tree[c].next_sibling;
 
         let child_margin_width = child_size[0]
-            .saturating_add(c.style.margin.left)
-            .saturating_add(c.style.margin.right);
+            .saturating_add(child_margin.left)
+            .saturating_add(child_margin.right);
         let child_margin_height = child_size[1]
-            .saturating_add(c.style.margin.top)
-            .saturating_add(c.style.margin.bottom);
+            .saturating_add(child_margin.top)
+            .saturating_add(child_margin.bottom);
 
         max_child_width = max_child_width.max(child_margin_width);
         max_child_height = max_child_height.max(child_margin_height);
@@ -112,13 +225,17 @@ fn measure_anchor_element_size(element: &mut Element, constraints: &Constraints)
     let element_width = size_from_policy(style.width, padded_width, constraints.max_size[0]);
     let element_height = size_from_policy(style.height, padded_height, constraints.max_size[1]);
 
-    element.size = [element_width, element_height];
-    element.size
+    tree[index].size = [element_width, element_height];
+    tree[index].size
 }
 
-fn measure_flex_row_element_size(element: &mut Element, constraints: &Constraints) -> [u32; 2] {
-    let style = element.style;
-    let num_children = element.children.len();
+fn measure_flex_row_element_size(
+    tree: &mut ElementTree,
+    index: usize,
+    constraints: &Constraints,
+) -> [u32; 2] {
+    let style = tree[index].style;
+    let num_children = tree[index].child_count;
 
     let padding_between_children: u32 = 0; // TODO: this is a placeholder for now, it should be set by style
 
@@ -131,21 +248,25 @@ fn measure_flex_row_element_size(element: &mut Element, constraints: &Constraint
             max_width.saturating_sub(style.padding.left + style.padding.right),
             max_height.saturating_sub(style.padding.top + style.padding.bottom),
         ],
+        text_measurement: constraints.text_measurement,
     };
 
     // for flex row we sum all children widths
     let mut total_child_width: u32 = 0;
     let mut max_child_height: u32 = 0; // we can just use the max height of the children
 
-    for c in element.children.iter_mut() {
-        let child_size = measure_element_size(c, &child_constraints);
+    let mut child = tree[index].first_child;
+    while let Some(c) = child {
+        let child_size = measure_element_size(tree, c, &child_constraints);
+        let child_margin = tree[c].style.margin;
+        child = tree[c].next_sibling;
 
         let child_margin_width = child_size[0]
-            .saturating_add(c.style.margin.left)
-            .saturating_add(c.style.margin.right);
+            .saturating_add(child_margin.left)
+            .saturating_add(child_margin.right);
         let child_margin_height = child_size[1]
-            .saturating_add(c.style.margin.top)
-            .saturating_add(c.style.margin.bottom);
+            .saturating_add(child_margin.top)
+            .saturating_add(child_margin.bottom);
 
         // add to total width, but just take max of height
         total_child_width = total_child_width.saturating_add(child_margin_width);
@@ -164,13 +285,17 @@ fn measure_flex_row_element_size(element: &mut Element, constraints: &Constraint
     let element_width = size_from_policy(style.width, padded_width, max_width);
     let element_height = size_from_policy(style.height, padded_height, max_height);
 
-    element.size = [element_width, element_height];
-    element.size
+    tree[index].size = [element_width, element_height];
+    tree[index].size
 }
 
-fn measure_flex_column_size(element: &mut Element, constraints: &Constraints) -> [u32; 2] {
-    let style = element.style;
-    let num_children = element.children.len();
+fn measure_flex_column_size(
+    tree: &mut ElementTree,
+    index: usize,
+    constraints: &Constraints,
+) -> [u32; 2] {
+    let style = tree[index].style;
+    let num_children = tree[index].child_count;
 
     let padding_between_children: u32 = 0; // TODO: add this to style
 
@@ -182,21 +307,25 @@ fn measure_flex_column_size(element: &mut Element, constraints: &Constraints) ->
             max_width.saturating_sub(style.padding.left + style.padding.right),
             max_height.saturating_sub(style.padding.top + style.padding.bottom),
         ],
+        text_measurement: constraints.text_measurement,
     };
 
     // for flex column we sum all children heights
     let mut total_child_height: u32 = 0;
     let mut max_child_width: u32 = 0; // we can just use the max width of the children
 
-    for c in element.children.iter_mut() {
-        let child_size = measure_element_size(c, &child_constraints);
+    let mut child = tree[index].first_child;
+    while let Some(c) = child {
+        let child_size = measure_element_size(tree, c, &child_constraints);
+        let child_margin = tree[c].style.margin;
+        child = tree[c].next_sibling;
 
         let child_margin_width = child_size[0]
-            .saturating_add(c.style.margin.left)
-            .saturating_add(c.style.margin.right);
+            .saturating_add(child_margin.left)
+            .saturating_add(child_margin.right);
         let child_margin_height = child_size[1]
-            .saturating_add(c.style.margin.top)
-            .saturating_add(c.style.margin.bottom);
+            .saturating_add(child_margin.top)
+            .saturating_add(child_margin.bottom);
 
         // add to total height but just keep max width
         max_child_width = max_child_width.max(child_margin_width);
@@ -215,12 +344,12 @@ fn measure_flex_column_size(element: &mut Element, constraints: &Constraints) ->
     let element_width = size_from_policy(style.width, padded_width, max_width);
     let element_height = size_from_policy(style.height, padded_height, max_height);
 
-    element.size = [element_width, element_height];
-    element.size
+    tree[index].size = [element_width, element_height];
+    tree[index].size
 }
 
-fn measure_pill_size(element: &mut Element, constraints: &Constraints) -> [u32; 2] {
-    let style = element.style;
+fn measure_pill_size(tree: &mut ElementTree, index: usize, constraints: &Constraints) -> [u32; 2] {
+    let style = tree[index].style;
 
     // we need to account for the pills border here as well (* 2 for width and height for left/right and top/bottom)
     let child_constraints_w = constraints.max_size[0]
@@ -231,21 +360,25 @@ fn measure_pill_size(element: &mut Element, constraints: &Constraints) -> [u32;
         .saturating_sub(style.border_width as u32 * 2);
     let child_constraints = Constraints {
         max_size: [child_constraints_w, child_constraints_h],
+        text_measurement: constraints.text_measurement,
     };
 
     // pills behave like anchors in this case, where their size is based on the size of their content
     let mut max_child_width = 0;
     let mut max_child_height = 0;
     // measure child elements first to get their sizes
-    for c in element.children.iter_mut() {
-        let child_size = measure_element_size(c, &child_constraints);
+    let mut child = tree[index].first_child;
+    while let Some(c) = child {
+        let child_size = measure_element_size(tree, c, &child_constraints);
+        let child_margin = tree[c].style.margin;
+        child = tree[c].next_sibling;
 
         let child_margin_width = child_size[0]
-            .saturating_add(c.style.margin.left)
-            .saturating_add(c.style.margin.right);
+            .saturating_add(child_margin.left)
+            .saturating_add(child_margin.right);
         let child_margin_height = child_size[1]
-            .saturating_add(c.style.margin.top)
-            .saturating_add(c.style.margin.bottom);
+            .saturating_add(child_margin.top)
+            .saturating_add(child_margin.bottom);
 
         max_child_width = max_child_width.max(child_margin_width);
         max_child_height = max_child_height.max(child_margin_height);
@@ -263,29 +396,30 @@ fn measure_pill_size(element: &mut Element, constraints: &Constraints) -> [u32;
     let element_width = size_from_policy(style.width, padded_width, constraints.max_size[0]);
     let element_height = size_from_policy(style.height, padded_height, constraints.max_size[1]);
 
-    element.size = [element_width, element_height];
-    element.size
+    tree[index].size = [element_width, element_height];
+    tree[index].size
 }
 
-fn measure_image_size(element: &mut Element, constraints: &Constraints) -> [u32; 2] {
-    let style = element.style;
+fn measure_image_size(tree: &mut ElementTree, index: usize, constraints: &Constraints) -> [u32; 2] {
+    let style = tree[index].style;
 
     let default_size = 64; // just us a 64x64 size if the user doesn't set a fixed size for their image in styling
 
     let element_width = size_from_policy(style.width, default_size, constraints.max_size[0]);
     let element_height = size_from_policy(style.height, default_size, constraints.max_size[1]);
 
-    element.size = [element_width, element_height];
-    element.size
+    tree[index].size = [element_width, element_height];
+    tree[index].size
 }
 
 fn measure_divider_size(
     orientation: DividerOrientation,
     thickness: u32,
-    element: &mut Element,
+    tree: &mut ElementTree,
+    index: usize,
     constraints: &Constraints,
 ) -> [u32; 2] {
-    let style = element.style;
+    let style = tree[index].style;
 
     let element_size: [u32; 2] = match orientation {
         // takes up entire width, but only thickness height
@@ -293,7 +427,7 @@ fn measure_divider_size(
             constraints.max_size[0],
             thickness
                 .saturating_add(style.padding.top)
-                .saturating_add(style.padding.bottom)
+                .saturating_add(style.padding.bottom),
         ],
         // takes up entire height but only thickness width
         DividerOrientation::Vertical => [
@@ -303,6 +437,313 @@ fn measure_divider_size(
             constraints.max_size[1],
         ],
     };
-    element.size = element_size;
-    element.size
+    tree[index].size = element_size;
+    tree[index].size
+}
+
+fn measure_progress_size(
+    orientation: DividerOrientation,
+    tree: &mut ElementTree,
+    index: usize,
+    constraints: &Constraints,
+) -> [u32; 2] {
+    let style = tree[index].style;
+
+    // progress bars have no content, so like images they get a default size if they aren't fixed or filling
+    let (length, thickness) = match constraints.text_measurement {
+        TextMeasurement::FontSize => (120, 8),
+        TextMeasurement::Cells => (20, 1),
+    };
+    let [default_width, default_height] = match orientation {
+        DividerOrientation::Horizontal => [length, thickness],
+        DividerOrientation::Vertical => [thickness, length],
+    };
+    let border = style.border_width as u32 * 2;
+    let padded_width = default_width + style.padding.left + style.padding.right + border;
+    let padded_height = default_height + style.padding.top + style.padding.bottom + border;
+
+    let element_width = size_from_policy(style.width, padded_width, constraints.max_size[0]);
+    let element_height = size_from_policy(style.height, padded_height, constraints.max_size[1]);
+
+    tree[index].size = [element_width, element_height];
+    tree[index].size
+}
+
+fn measure_sparkline_size(
+    tree: &mut ElementTree,
+    index: usize,
+    constraints: &Constraints,
+) -> [u32; 2] {
+    let style = tree[index].style;
+
+    // sized to sit next to a line of text if it isn't fixed or filling
+    let [default_width, default_height] = match constraints.text_measurement {
+        TextMeasurement::FontSize => [80, 20],
+        TextMeasurement::Cells => [20, 2],
+    };
+    let border = style.border_width as u32 * 2;
+    let padded_width = default_width + style.padding.left + style.padding.right + border;
+    let padded_height = default_height + style.padding.top + style.padding.bottom + border;
+
+    let element_width = size_from_policy(style.width, padded_width, constraints.max_size[0]);
+    let element_height = size_from_policy(style.height, padded_height, constraints.max_size[1]);
+
+    tree[index].size = [element_width, element_height];
+    tree[index].size
+}
+
+// like an svg, the viewbox size is the natural size of the path (1 unit = 1px)
+fn measure_path_size(
+    viewbox: [f32; 4],
+    tree: &mut ElementTree,
+    index: usize,
+    constraints: &Constraints,
+) -> [u32; 2] {
+    let style = tree[index].style;
+
+    let [default_width, default_height] = [viewbox[2].ceil() as u32, viewbox[3].ceil() as u32];
+    let border = style.border_width as u32 * 2;
+    let padded_width = default_width + style.padding.left + style.padding.right + border;
+    let padded_height = default_height + style.padding.top + style.padding.bottom + border;
+
+    let element_width = size_from_policy(style.width, padded_width, constraints.max_size[0]);
+    let element_height = size_from_policy(style.height, padded_height, constraints.max_size[1]);
+
+    tree[index].size = [element_width, element_height];
+    tree[index].size
+}
+
+// enough room along the category axis for each bar to be easy to see, plus the labels around the bars
+fn measure_bar_chart_size(
+    chart: &BarChart,
+    tree: &mut ElementTree,
+    index: usize,
+    constraints: &Constraints,
+) -> [u32; 2] {
+    let style = tree[index].style;
+    let default_text_style = TextStyle::default();
+    let text_style = tree[index]
+        .text_style
+        .as_ref()
+        .unwrap_or(&default_text_style);
+    let insets = bar_chart_insets(tree, chart, text_style);
+    let [top, right, bottom, left] = insets.sides;
+
+    let bars_per_category = match chart.stacked {
+        true => 1,
+        false => bar_series_count(chart).max(1) as u32,
+    };
+    let (category_size, value_size) = match constraints.text_measurement {
+        TextMeasurement::FontSize => (8 + bars_per_category * 12, 120),
+        TextMeasurement::Cells => (1 + bars_per_category, 8),
+    };
+    // leave room for the category labels to sit side by side under vertical bars
+    let [gap_x, _] = ChartMetrics::new(constraints.text_measurement).gap;
+    let category_size = match chart.orientation {
+        DividerOrientation::Vertical if insets.category_label_size[0] > 0 => {
+            category_size.max(insets.category_label_size[0] + gap_x)
+        }
+        _ => category_size,
+    };
+    let categories = chart.category_count as u32 * category_size;
+    let [plot_width, plot_height] = match chart.orientation {
+        DividerOrientation::Vertical => [categories, value_size],
+        DividerOrientation::Horizontal => [value_size * 4 / 3, categories],
+    };
+    let border = style.border_width as u32 * 2;
+    let padded_width =
+        plot_width + left + right + style.padding.left + style.padding.right + border;
+    let padded_height =
+        plot_height + top + bottom + style.padding.top + style.padding.bottom + border;
+
+    let element_width = size_from_policy(style.width, padded_width, constraints.max_size[0]);
+    let element_height = size_from_policy(style.height, padded_height, constraints.max_size[1]);
+
+    tree[index].size = [element_width, element_height];
+    tree[index].size
+}
+
+// a plot of a fixed size plus the space the labels need around it, and at least as wide as the legend
+fn measure_line_chart_size(
+    chart: &LineChart,
+    tree: &mut ElementTree,
+    index: usize,
+    constraints: &Constraints,
+) -> [u32; 2] {
+    let style = tree[index].style;
+    let default_text_style = TextStyle::default();
+    let text_style = tree[index]
+        .text_style
+        .as_ref()
+        .unwrap_or(&default_text_style);
+
+    let [plot_width, plot_height] = match constraints.text_measurement {
+        TextMeasurement::FontSize => [240, 120],
+        TextMeasurement::Cells => [40, 8],
+    };
+    // the labels depend on the size of the plot, so lay it out again with the space they took the first time
+    let [top, right, bottom, left] =
+        line_chart_layout(tree, chart, text_style, [plot_width, plot_height]).sides;
+    let content_size = [plot_width + left + right, plot_height + top + bottom];
+    let layout = line_chart_layout(tree, chart, text_style, content_size);
+    let [top, right, bottom, left] = layout.sides;
+    let legend_width = match layout.legend_height {
+        0 => 0,
+        _ => for_each_legend_item(tree, chart, text_style, |_, _, _| {}),
+    };
+    let content_width = (plot_width + left + right).max(legend_width);
+    let border = style.border_width as u32 * 2;
+    let padded_width = content_width + style.padding.left + style.padding.right + border;
+    let padded_height =
+        plot_height + top + bottom + style.padding.top + style.padding.bottom + border;
+
+    let element_width = size_from_policy(style.width, padded_width, constraints.max_size[0]);
+    let element_height = size_from_policy(style.height, padded_height, constraints.max_size[1]);
+
+    tree[index].size = [element_width, element_height];
+    tree[index].size
+}
+
+// gauges and donuts are square unless both sides are set, big enough for the centre label to fit inside the ring
+fn measure_radial_size(
+    label: Option<TextSpan>,
+    thickness: f32,
+    tree: &mut ElementTree,
+    index: usize,
+    constraints: &Constraints,
+) -> [u32; 2] {
+    let style = tree[index].style;
+    let default_text_style = TextStyle::default();
+    let text_style = tree[index]
+        .text_style
+        .as_ref()
+        .unwrap_or(&default_text_style);
+
+    let default_diameter = match constraints.text_measurement {
+        TextMeasurement::FontSize => 96,
+        TextMeasurement::Cells => 12,
+    };
+    let label_width = label.map_or(0, |span| {
+        measure_text(tree.text(span), text_style, constraints.text_measurement)[0]
+    });
+    let inside = (1.0 - thickness).max(0.5); // pie labels sit on top of the slices
+    let diameter = default_diameter.max((label_width as f32 / inside).ceil() as u32 + 2);
+
+    let border = style.border_width as u32 * 2;
+    let padded_width = diameter + style.padding.left + style.padding.right + border;
+    let padded_height = diameter + style.padding.top + style.padding.bottom + border;
+    let mut element_width = size_from_policy(style.width, padded_width, constraints.max_size[0]);
+    let mut element_height = size_from_policy(style.height, padded_height, constraints.max_size[1]);
+
+    // a side that is set decides the size of an auto one
+    let padding_width = style.padding.left + style.padding.right + border;
+    let padding_height = style.padding.top + style.padding.bottom + border;
+    match (style.width, style.height) {
+        (SizingPolicy::Auto, SizingPolicy::Auto) => {}
+        (SizingPolicy::Auto, _) => {
+            let side = element_height.saturating_sub(padding_height);
+            element_width = (side + padding_width).min(constraints.max_size[0]);
+        }
+        (_, SizingPolicy::Auto) => {
+            let side = element_width.saturating_sub(padding_width);
+            element_height = (side + padding_height).min(constraints.max_size[1]);
+        }
+        _ => {}
+    }
+
+    tree[index].size = [element_width, element_height];
+    tree[index].size
+}
+
+// the cells at their own size plus the labels, cells are stretched or squeezed when the style sets the size instead
+fn measure_heatmap_size(
+    heatmap: &Heatmap,
+    tree: &mut ElementTree,
+    index: usize,
+    constraints: &Constraints,
+) -> [u32; 2] {
+    let style = tree[index].style;
+    let default_text_style = TextStyle::default();
+    let text_style = tree[index]
+        .text_style
+        .as_ref()
+        .unwrap_or(&default_text_style);
+    let labels = heatmap_labels(tree, heatmap, text_style);
+
+    let [cell_width, cell_height] = match heatmap.cell_size {
+        Some(cell_size) => cell_size,
+        None => {
+            let [gap_x, _] = ChartMetrics::new(constraints.text_measurement).gap;
+            let [width, height] = match constraints.text_measurement {
+                TextMeasurement::FontSize => [16, 16],
+                TextMeasurement::Cells => [2, 1],
+            };
+            // column labels sit side by side above their columns
+            match labels.column_width {
+                0 => [width, height],
+                label_width => [
+                    width.max((label_width + gap_x).saturating_sub(heatmap.gap)),
+                    height,
+                ],
+            }
+        }
+    };
+    let grid_size = |cells: u32, cell_size: u32| match cells {
+        0 => 0,
+        cells => cells * cell_size + (cells - 1) * heatmap.gap,
+    };
+    let border = style.border_width as u32 * 2;
+    let padded_width = labels.left
+        + grid_size(heatmap.columns, cell_width)
+        + style.padding.left
+        + style.padding.right
+        + border;
+    let padded_height = labels.top
+        + grid_size(heatmap.rows, cell_height)
+        + style.padding.top
+        + style.padding.bottom
+        + border;
+
+    let element_width = size_from_policy(style.width, padded_width, constraints.max_size[0]);
+    let element_height = size_from_policy(style.height, padded_height, constraints.max_size[1]);
+
+    tree[index].size = [element_width, element_height];
+    tree[index].size
+}
+
+// columns at the width of their widest cell and every row at one line of text, cells are truncated to fit when the
+// style makes the table narrower than that. the column widths are fitted to the final size here and kept in the tree
+// for the render pass
+fn measure_table_size(
+    table: &Table,
+    tree: &mut ElementTree,
+    index: usize,
+    constraints: &Constraints,
+) -> [u32; 2] {
+    let style = tree[index].style;
+    let mut table = *table;
+    let metrics = {
+        let default_text_style = TextStyle::default();
+        let text_style = tree[index]
+            .text_style
+            .as_ref()
+            .unwrap_or(&default_text_style);
+        TableMetrics::new(&table, text_style, constraints.text_measurement)
+    };
+    let columns_width = measure_table_column_widths(tree, index, &mut table);
+
+    let border = style.border_width as u32 * 2;
+    let padding_width = style.padding.left + style.padding.right + border;
+    let padded_width = columns_width + padding_width;
+    let padded_height = metrics
+        .height(&table)
+        .saturating_add(style.padding.top + style.padding.bottom + border);
+    let element_width = size_from_policy(style.width, padded_width, constraints.max_size[0]);
+    let element_height = size_from_policy(style.height, padded_height, constraints.max_size[1]);
+
+    let content_width = element_width.saturating_sub(padding_width);
+    fit_table_column_widths(tree, &table, metrics.padding[0], content_width);
+    tree[index]._type = ElementType::Table(table);
+    tree[index].size = [element_width, element_height];
+    tree[index].size
 }
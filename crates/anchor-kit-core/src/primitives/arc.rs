@@ -0,0 +1,92 @@
+use std::f32::consts::PI;
+
+use crate::primitives::color::Color;
+
+// a ring, or part of one, around a centre, e.g. gauge tracks and donut slices
+// angles are in degrees clockwise from 12 o'clock, so a gauge from -135 to 135 opens at the bottom
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Arc {
+    pub center: [f32; 2], // x, y in frame pixels
+    pub radius: f32,      // to the middle of the ring
+    pub thickness: f32,
+    pub start_angle: f32,
+    pub end_angle: f32, // a full ring if it is 360 or more past the start
+    pub color: Color,
+    pub rounded_caps: bool, // half circles on the ends, otherwise they are cut off straight along the radius
+}
+
+impl Arc {
+    // signed distance in pixels from the edge of the arc, negative inside
+    // renderers use `0.5 - distance` as the coverage of a pixel, the wgpu shader does the same math
+    pub fn distance(&self, point: [f32; 2]) -> f32 {
+        let half_thickness = self.thickness / 2.0;
+        let [x, y] = [point[0] - self.center[0], point[1] - self.center[1]];
+        let ring = ((x * x + y * y).sqrt() - self.radius).abs() - half_thickness;
+
+        // rotate so the middle of the arc points along +v, the arc is then symmetric around that axis
+        let half_sweep = self.half_sweep();
+        if half_sweep >= PI {
+            return ring;
+        }
+        let (sin, cos) = ((self.start_angle + self.end_angle) / 2.0)
+            .to_radians()
+            .sin_cos();
+        let u = (x * cos + y * sin).abs(); // across the middle
+        let v = x * sin - y * cos; // along the middle
+        let (end_sin, end_cos) = half_sweep.sin_cos();
+        if u.atan2(v) <= half_sweep {
+            // straight caps are also an edge from inside, measured across the line through the end
+            return match !self.rounded_caps && u * end_sin + v * end_cos > 0.0 {
+                true => ring.max(u * end_cos - v * end_sin),
+                false => ring,
+            };
+        }
+        // past the end, so the nearest part is the cap
+        let end = [end_sin * self.radius, end_cos * self.radius];
+        match self.rounded_caps {
+            true => ((u - end[0]).powi(2) + (v - end[1]).powi(2)).sqrt() - half_thickness,
+            false => {
+                // distance to the straight cap, the segment across the ring at the end angle
+                let along = (u * end_sin + v * end_cos - self.radius)
+                    .clamp(-half_thickness, half_thickness);
+                let nearest = [
+                    end_sin * (self.radius + along),
+                    end_cos * (self.radius + along),
+                ];
+                ((u - nearest[0]).powi(2) + (v - nearest[1]).powi(2)).sqrt()
+            }
+        }
+    }
+
+    // half of the angle the arc covers in radians, between 0 and pi
+    pub fn half_sweep(&self) -> f32 {
+        ((self.end_angle - self.start_angle).to_radians() / 2.0).clamp(0.0, PI)
+    }
+
+    // point on the middle of the ring at the given angle
+    pub fn point_at(&self, angle: f32) -> [f32; 2] {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        [
+            self.center[0] + sin * self.radius,
+            self.center[1] - cos * self.radius,
+        ]
+    }
+
+    // pixel bounds of the whole ring, as position and size like the other primitives
+    pub fn bounds(&self) -> ([u32; 2], [u32; 2]) {
+        let extent = self.radius + self.thickness / 2.0 + 1.0; // include the anti-aliased edge
+        let min = [
+            (self.center[0] - extent).max(0.0).floor(),
+            (self.center[1] - extent).max(0.0).floor(),
+        ];
+        let max = [
+            (self.center[0] + extent).max(0.0).ceil(),
+            (self.center[1] + extent).max(0.0).ceil(),
+        ];
+        (
+            [min[0] as u32, min[1] as u32],
+            [(max[0] - min[0]) as u32, (max[1] - min[1]) as u32],
+        )
+    }
+}
@@ -0,0 +1,83 @@
+use crate::primitives::color::Color;
+
+// rows and columns of coloured cells, e.g. a heatmap. the whole grid is one primitive so renderers can draw thousands
+// of cells as one batch instead of a rectangle each
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CellGrid {
+    pub position: [u32; 2], // x, y
+    pub size: [u32; 2],     // w, h of the whole grid
+    pub rows: u32,
+    pub columns: u32,
+    pub gap: u32, // between neighbouring cells
+    pub cell_radius: f32,
+    pub colors: Vec<Color>, // one for each cell, row by row from the top left
+}
+
+impl CellGrid {
+    // position and size of a cell, edges are on whole pixels and the rounding is spread over the grid so all cells
+    // are within a pixel of the same size
+    pub fn cell_bounds(&self, row: u32, column: u32) -> ([u32; 2], [u32; 2]) {
+        let [x0, x1] = cell_edges(
+            column,
+            self.columns,
+            self.position[0],
+            self.size[0],
+            self.gap,
+        );
+        let [y0, y1] = cell_edges(row, self.rows, self.position[1], self.size[1], self.gap);
+        ([x0, y0], [x1 - x0, y1 - y0])
+    }
+
+    // cells outside the grid (or past the end of the colours) are transparent
+    pub fn color(&self, row: u32, column: u32) -> Color {
+        if row >= self.rows || column >= self.columns {
+            return Color::TRANSPARENT;
+        }
+        let index = row as usize * self.columns as usize + column as usize;
+        self.colors
+            .get(index)
+            .copied()
+            .unwrap_or(Color::TRANSPARENT)
+    }
+
+    pub fn bounds(&self) -> ([u32; 2], [u32; 2]) {
+        (self.position, self.size)
+    }
+}
+
+impl Clone for CellGrid {
+    fn clone(&self) -> Self {
+        Self {
+            position: self.position,
+            size: self.size,
+            rows: self.rows,
+            columns: self.columns,
+            gap: self.gap,
+            cell_radius: self.cell_radius,
+            colors: self.colors.clone(),
+        }
+    }
+
+    // lets `RenderList::clone_from` reuse the existing colour allocations
+    fn clone_from(&mut self, source: &Self) {
+        self.position = source.position;
+        self.size = source.size;
+        self.rows = source.rows;
+        self.columns = source.columns;
+        self.gap = source.gap;
+        self.cell_radius = source.cell_radius;
+        self.colors.clone_from(&source.colors);
+    }
+}
+
+// start and end of the cell at `index` of `count` cells spread over `length` pixels from `start`, shared with the
+// heatmap's labels so they line up with the cells. a pixel belongs to the cell its centre is in, the same as sampling
+// a texture with a texel per cell, so edges exactly halfway through a pixel round down
+pub(crate) fn cell_edges(index: u32, count: u32, start: u32, length: u32, gap: u32) -> [u32; 2] {
+    let count = count.max(1);
+    let cells = length.saturating_sub(gap * (count - 1)) as f32;
+    let edge =
+        |i: u32| start + (i as f32 * cells / count as f32 - 0.5).ceil().max(0.0) as u32 + i * gap;
+    [edge(index), edge(index + 1) - gap]
+}
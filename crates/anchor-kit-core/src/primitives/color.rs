@@ -1,4 +1,7 @@
-#[derive(Clone, Copy, Debug)]
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -6,7 +9,65 @@ pub struct Color {
     pub a: u8,
 }
 
+// hue in degrees (0 - 360), everything else 0.0 - 1.0
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hsl {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+    pub a: f32,
+}
+
+// perceptual lightness (0.0 - 1.0), chroma (0.0 - ~0.4) and hue in degrees
+// https://bottosson.github.io/posts/oklab/
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Oklch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+    pub a: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorParseError {
+    pub input: String,
+}
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid colour `{}`", self.input)
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
 impl Color {
+    pub const TRANSPARENT: Color = Color::rgba(0, 0, 0, 0);
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const GRAY: Color = Color::rgb(128, 128, 128);
+    pub const RED: Color = Color::rgb(255, 0, 0);
+    pub const GREEN: Color = Color::rgb(0, 128, 0);
+    pub const LIME: Color = Color::rgb(0, 255, 0);
+    pub const BLUE: Color = Color::rgb(0, 0, 255);
+    pub const YELLOW: Color = Color::rgb(255, 255, 0);
+    pub const ORANGE: Color = Color::rgb(255, 165, 0);
+    pub const PURPLE: Color = Color::rgb(128, 0, 128);
+    pub const CYAN: Color = Color::rgb(0, 255, 255);
+    pub const MAGENTA: Color = Color::rgb(255, 0, 255);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub fn with_alpha(self, a: u8) -> Self {
+        Self { a, ..self }
+    }
+
     pub fn to_rgba_f32(&self) -> [f32; 4] {
         [
             self.r as f32 / 255.0,
@@ -15,6 +76,181 @@ impl Color {
             self.a as f32 / 255.0,
         ]
     }
+
+    // values outside of 0.0 - 1.0 are clamped
+    pub fn from_rgba_f32([r, g, b, a]: [f32; 4]) -> Self {
+        Self {
+            r: to_u8(r),
+            g: to_u8(g),
+            b: to_u8(b),
+            a: to_u8(a),
+        }
+    }
+
+    // colours are stored as srgb, this removes the gamma so colours can be mixed/ blended physically correctly
+    pub fn to_linear(&self) -> [f32; 4] {
+        let [r, g, b, a] = self.to_rgba_f32();
+        [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a]
+    }
+
+    pub fn from_linear([r, g, b, a]: [f32; 4]) -> Self {
+        Self::from_rgba_f32([linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b), a])
+    }
+
+    pub fn to_hsl(&self) -> Hsl {
+        let [r, g, b, a] = self.to_rgba_f32();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return Hsl {
+                h: 0.0,
+                s: 0.0,
+                l,
+                a,
+            };
+        }
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = match max {
+            _ if max == r => ((g - b) / d).rem_euclid(6.0),
+            _ if max == g => (b - r) / d + 2.0,
+            _ => (r - g) / d + 4.0,
+        } * 60.0;
+        Hsl { h, s, l, a }
+    }
+
+    pub fn from_hsl(hsl: Hsl) -> Self {
+        let Hsl { h, s, l, a } = hsl;
+        let h = h.rem_euclid(360.0) / 60.0;
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = l - c / 2.0;
+        Self::from_rgba_f32([r + m, g + m, b + m, a])
+    }
+
+    pub fn to_oklab(&self) -> [f32; 4] {
+        let [r, g, b, a] = self.to_linear();
+        let l = 0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b;
+        let m = 0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b;
+        let s = 0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b;
+        let (l, m, s) = (l.cbrt(), m.cbrt(), s.cbrt());
+        [
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+            a,
+        ]
+    }
+
+    // colours outside of the srgb gamut are clamped
+    pub fn from_oklab([l, a, b, alpha]: [f32; 4]) -> Self {
+        let l_ = l + 0.396_337_78 * a + 0.215_803_76 * b;
+        let m_ = l - 0.105_561_346 * a - 0.063_854_17 * b;
+        let s_ = l - 0.089_484_18 * a - 1.291_485_5 * b;
+        let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+        Self::from_linear([
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+            alpha,
+        ])
+    }
+
+    pub fn to_oklch(&self) -> Oklch {
+        let [l, a, b, alpha] = self.to_oklab();
+        Oklch {
+            l,
+            c: (a * a + b * b).sqrt(),
+            h: b.atan2(a).to_degrees().rem_euclid(360.0),
+            a: alpha,
+        }
+    }
+
+    pub fn from_oklch(oklch: Oklch) -> Self {
+        let Oklch { l, c, h, a } = oklch;
+        let (sin, cos) = h.to_radians().sin_cos();
+        Self::from_oklab([l, c * cos, c * sin, a])
+    }
+
+    // straight interpolation of the srgb values, t is clamped to 0.0 - 1.0
+    pub fn lerp(self, other: Color, t: f32) -> Self {
+        let [a, b] = [self.to_rgba_f32(), other.to_rgba_f32()];
+        Self::from_rgba_f32(lerp4(a, b, t))
+    }
+
+    // physically correct mixing of light, doesn't get as dark in the middle as `lerp`
+    pub fn lerp_linear(self, other: Color, t: f32) -> Self {
+        Self::from_linear(lerp4(self.to_linear(), other.to_linear(), t))
+    }
+
+    // perceptually even steps in lightness, good for gradients and colour scales
+    pub fn lerp_oklab(self, other: Color, t: f32) -> Self {
+        Self::from_oklab(lerp4(self.to_oklab(), other.to_oklab(), t))
+    }
+
+    // like `lerp_oklab` but goes around the hue wheel (the shorter way), so colours stay saturated in the middle
+    pub fn lerp_oklch(self, other: Color, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let (a, b) = (self.to_oklch(), other.to_oklch());
+        // greys have no meaningful hue, so use the other colour's hue for them
+        let (a_h, b_h) = match (a.c < 1e-4, b.c < 1e-4) {
+            (true, false) => (b.h, b.h),
+            (false, true) => (a.h, a.h),
+            _ => (a.h, b.h),
+        };
+        let dh = (b_h - a_h + 180.0).rem_euclid(360.0) - 180.0;
+        Self::from_oklch(Oklch {
+            l: a.l + (b.l - a.l) * t,
+            c: a.c + (b.c - a.c) * t,
+            h: a_h + dh * t,
+            a: a.a + (b.a - a.a) * t,
+        })
+    }
+
+    // css colour syntax: #rgb, #rgba, #rrggbb, #rrggbbaa, rgb(), rgba(), hsl(), hsla() and named colours
+    // both the comma and the space separated forms work, e.g. `rgb(255, 0, 0)`, `rgb(255 0 0 / 50%)`
+    pub fn parse(input: &str) -> Result<Self, ColorParseError> {
+        parse_color(input.trim()).ok_or_else(|| ColorParseError {
+            input: input.to_string(),
+        })
+    }
+
+    pub fn named(name: &str) -> Option<Self> {
+        let color = match name.to_ascii_lowercase().as_str() {
+            "transparent" => Self::TRANSPARENT,
+            "black" => Self::BLACK,
+            "white" => Self::WHITE,
+            "gray" | "grey" => Self::GRAY,
+            "red" => Self::RED,
+            "green" => Self::GREEN,
+            "lime" => Self::LIME,
+            "blue" => Self::BLUE,
+            "yellow" => Self::YELLOW,
+            "orange" => Self::ORANGE,
+            "purple" => Self::PURPLE,
+            "cyan" | "aqua" => Self::CYAN,
+            "magenta" | "fuchsia" => Self::MAGENTA,
+            _ => return None,
+        };
+        Some(color)
+    }
+
+    // #rrggbb, or #rrggbbaa if the colour isn't opaque
+    pub fn to_hex(&self) -> String {
+        match self.a {
+            255 => format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b),
+            a => format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, a),
+        }
+    }
 }
 
 impl Default for Color {
@@ -28,3 +264,138 @@ impl Default for Color {
         }
     }
 }
+
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl From<Hsl> for Color {
+    fn from(hsl: Hsl) -> Self {
+        Self::from_hsl(hsl)
+    }
+}
+
+impl From<Oklch> for Color {
+    fn from(oklch: Oklch) -> Self {
+        Self::from_oklch(oklch)
+    }
+}
+
+pub fn srgb_to_linear(c: f32) -> f32 {
+    match c <= 0.04045 {
+        true => c / 12.92,
+        false => ((c + 0.055) / 1.055).powf(2.4),
+    }
+}
+
+pub fn linear_to_srgb(c: f32) -> f32 {
+    match c <= 0.003_130_8 {
+        true => c * 12.92,
+        false => 1.055 * c.powf(1.0 / 2.4) - 0.055,
+    }
+}
+
+fn to_u8(c: f32) -> u8 {
+    (c.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn lerp4(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    let t = t.clamp(0.0, 1.0);
+    [0, 1, 2, 3].map(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+fn parse_color(input: &str) -> Option<Color> {
+    if let Some(hex) = input.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    if let Some((function, args)) = input.strip_suffix(')').and_then(|s| s.split_once('(')) {
+        let args = split_args(args)?;
+        return match function.trim().to_ascii_lowercase().as_str() {
+            "rgb" | "rgba" => parse_rgb(&args),
+            "hsl" | "hsla" => parse_hsl(&args),
+            _ => None,
+        };
+    }
+    Color::named(input)
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let digits = hex
+        .chars()
+        .map(|c| c.to_digit(16).map(|d| d as u8))
+        .collect::<Option<Vec<u8>>>()?;
+    let [r, g, b, a] = match digits[..] {
+        [r, g, b] => [r * 17, g * 17, b * 17, 255],
+        [r, g, b, a] => [r * 17, g * 17, b * 17, a * 17],
+        [r1, r2, g1, g2, b1, b2] => [r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2, 255],
+        [r1, r2, g1, g2, b1, b2, a1, a2] => {
+            [r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2, a1 * 16 + a2]
+        }
+        _ => return None,
+    };
+    Some(Color { r, g, b, a })
+}
+
+// `1, 2, 3, 0.5` or `1 2 3 / 50%`
+fn split_args(args: &str) -> Option<Vec<&str>> {
+    let (channels, alpha) = match args.split_once('/') {
+        Some((channels, alpha)) => (channels, Some(alpha.trim())),
+        None => (args, None),
+    };
+    let mut args = match channels.contains(',') {
+        true => channels.split(',').map(str::trim).collect::<Vec<_>>(),
+        false => channels.split_whitespace().collect(),
+    };
+    if let Some(alpha) = alpha {
+        if args.len() != 3 {
+            return None;
+        }
+        args.push(alpha);
+    }
+    Some(args)
+}
+
+// plain numbers, or percentages of `max`
+fn parse_component(value: &str, max: f32) -> Option<f32> {
+    match value.strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f32>().ok().map(|p| p / 100.0 * max),
+        None => value.parse::<f32>().ok(),
+    }
+    .filter(|v| v.is_finite())
+}
+
+fn parse_alpha(args: &[&str]) -> Option<f32> {
+    match args {
+        [_, _, _] => Some(1.0),
+        [_, _, _, alpha] => parse_component(alpha, 1.0),
+        _ => None,
+    }
+}
+
+fn parse_rgb(args: &[&str]) -> Option<Color> {
+    let a = parse_alpha(args)?;
+    let channel = |i: usize| parse_component(args[i], 255.0).map(|c| c / 255.0);
+    Some(Color::from_rgba_f32([
+        channel(0)?,
+        channel(1)?,
+        channel(2)?,
+        a,
+    ]))
+}
+
+fn parse_hsl(args: &[&str]) -> Option<Color> {
+    let a = parse_alpha(args)?;
+    let h = args[0]
+        .strip_suffix("deg")
+        .unwrap_or(args[0])
+        .parse::<f32>()
+        .ok()
+        .filter(|h| h.is_finite())?;
+    let s = parse_component(args[1], 1.0)?.clamp(0.0, 1.0);
+    let l = parse_component(args[2], 1.0)?.clamp(0.0, 1.0);
+    Some(Color::from_hsl(Hsl { h, s, l, a }))
+}
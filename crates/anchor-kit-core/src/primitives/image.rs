@@ -1,10 +1,12 @@
 use crate::primitives::rectangle::Rectangle;
 use uuid::Uuid;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Image {
     pub texture_id: Uuid,
     pub rectangle: Rectangle,
+    pub opacity: f32, // 0.0 - 1.0, multiplied with the texture alpha
 }
 
 impl Image {
@@ -12,6 +14,7 @@ impl Image {
         Self {
             texture_id,
             rectangle,
+            opacity: 1.0,
         }
     }
 }
@@ -1,4 +1,8 @@
+pub mod arc;
+pub mod cell_grid;
 pub mod color;
 pub mod image;
+pub mod path;
+pub mod polyline;
 pub mod rectangle;
 pub mod text;
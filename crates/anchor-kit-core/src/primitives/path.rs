@@ -0,0 +1,346 @@
+use std::f32::consts::PI;
+
+use crate::primitives::{color::Color, polyline::Stroke};
+
+// a vector shape made of lines, curves and arcs, e.g. for trend arrows, pie slices and icons
+// commands work like svg path data with absolute coordinates, in frame pixels in a render list
+#[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
+pub struct Path {
+    pub commands: Vec<PathCommand>,
+    pub fill: Option<Color>,
+    pub fill_rule: FillRule,
+    pub stroke: Option<Stroke>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PathCommand {
+    MoveTo([f32; 2]), // starts a new sub path
+    LineTo([f32; 2]),
+    QuadTo {
+        control: [f32; 2],
+        to: [f32; 2],
+    },
+    CubicTo {
+        control_1: [f32; 2],
+        control_2: [f32; 2],
+        to: [f32; 2],
+    },
+    // elliptical arc to a point, with the same flags as svg's `A` command
+    ArcTo {
+        radii: [f32; 2],
+        x_rotation: f32, // degrees
+        large_arc: bool,
+        sweep: bool, // clockwise on screen (y down)
+        to: [f32; 2],
+    },
+    Close, // draws a line back to the start of the sub path
+}
+
+// which areas of overlapping or nested sub paths are filled, same as svg's fill-rule
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule {
+    #[default]
+    NonZero,
+    EvenOdd, // e.g. for rings, where an inner sub path cuts a hole out of the outer one
+}
+
+impl FillRule {
+    // whether a point with the given winding number (see `winding_number`) is inside the fill
+    pub fn contains(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+impl Path {
+    // how far flattened curves can be from the real curve, in pixels
+    pub const TOLERANCE: f32 = 0.1;
+
+    // calls `f` with the points of each sub path with curves and arcs split into lines, and whether it is closed
+    // shared by the renderers so the shapes come out the same in every backend
+    pub fn flatten(&self, f: impl FnMut(&[[f32; 2]], bool)) {
+        flatten_commands(&self.commands, f);
+    }
+
+    // pixel bounds of the fill and stroke, as position and size like the other primitives
+    pub fn bounds(&self) -> ([u32; 2], [u32; 2]) {
+        let extent = self.stroke.map_or(0.0, |stroke| stroke.extent()) + 1.0; // include the anti-aliased edge
+        let mut min = [f32::MAX, f32::MAX];
+        let mut max = [f32::MIN, f32::MIN];
+        self.flatten(|points, _| {
+            for [x, y] in points {
+                min = [min[0].min(x - extent), min[1].min(y - extent)];
+                max = [max[0].max(x + extent), max[1].max(y + extent)];
+            }
+        });
+        if min[0] > max[0] {
+            return ([0, 0], [0, 0]);
+        }
+        let position = [min[0].max(0.0).floor(), min[1].max(0.0).floor()];
+        let size = [
+            (max[0].ceil() - position[0]).max(0.0),
+            (max[1].ceil() - position[1]).max(0.0),
+        ];
+        (
+            [position[0] as u32, position[1] as u32],
+            [size[0] as u32, size[1] as u32],
+        )
+    }
+}
+
+impl Clone for Path {
+    fn clone(&self) -> Self {
+        Self {
+            commands: self.commands.clone(),
+            fill: self.fill,
+            fill_rule: self.fill_rule,
+            stroke: self.stroke,
+        }
+    }
+
+    // lets `RenderList::clone_from` reuse the existing command allocations
+    fn clone_from(&mut self, source: &Self) {
+        self.commands.clone_from(&source.commands);
+        self.fill = source.fill;
+        self.fill_rule = source.fill_rule;
+        self.stroke = source.stroke;
+    }
+}
+
+impl PathCommand {
+    // applies the same scale to both axes (so arcs stay arcs) and then the offset, e.g. to fit a path to an element
+    pub fn transformed(self, scale: f32, offset: [f32; 2]) -> Self {
+        let point = |[x, y]: [f32; 2]| [x * scale + offset[0], y * scale + offset[1]];
+        match self {
+            PathCommand::MoveTo(to) => PathCommand::MoveTo(point(to)),
+            PathCommand::LineTo(to) => PathCommand::LineTo(point(to)),
+            PathCommand::QuadTo { control, to } => PathCommand::QuadTo {
+                control: point(control),
+                to: point(to),
+            },
+            PathCommand::CubicTo {
+                control_1,
+                control_2,
+                to,
+            } => PathCommand::CubicTo {
+                control_1: point(control_1),
+                control_2: point(control_2),
+                to: point(to),
+            },
+            PathCommand::ArcTo {
+                radii,
+                x_rotation,
+                large_arc,
+                sweep,
+                to,
+            } => PathCommand::ArcTo {
+                radii: radii.map(|r| r * scale.abs()),
+                x_rotation,
+                large_arc,
+                sweep,
+                to: point(to),
+            },
+            PathCommand::Close => PathCommand::Close,
+        }
+    }
+}
+
+// how many times the flattened sub paths wind around the point, each one treated as closed like when filling
+pub fn winding_number<'a>(
+    sub_paths: impl IntoIterator<Item = &'a [[f32; 2]]>,
+    point: [f32; 2],
+) -> i32 {
+    let mut winding = 0;
+    for points in sub_paths {
+        for (i, a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            if (a[1] <= point[1]) == (b[1] <= point[1]) {
+                continue; // doesn't cross the horizontal line through the point
+            }
+            let x = a[0] + (b[0] - a[0]) * (point[1] - a[1]) / (b[1] - a[1]);
+            if x > point[0] {
+                winding += if b[1] > a[1] { 1 } else { -1 };
+            }
+        }
+    }
+    winding
+}
+
+pub(crate) fn flatten_commands(commands: &[PathCommand], mut f: impl FnMut(&[[f32; 2]], bool)) {
+    let mut points: Vec<[f32; 2]> = vec![];
+    let mut current = [0.0, 0.0];
+    for command in commands {
+        match *command {
+            PathCommand::MoveTo(to) => {
+                if points.len() > 1 {
+                    f(&points, false);
+                }
+                points.clear();
+                points.push(to);
+                current = to;
+            }
+            PathCommand::LineTo(to) => {
+                start_sub_path(&mut points, current);
+                points.push(to);
+                current = to;
+            }
+            PathCommand::QuadTo { control, to } => {
+                start_sub_path(&mut points, current);
+                let steps = curve_steps(&[current, control, to]);
+                for step in 1..=steps {
+                    let t = step as f32 / steps as f32;
+                    let u = 1.0 - t;
+                    points.push(combine(&[
+                        (current, u * u),
+                        (control, 2.0 * u * t),
+                        (to, t * t),
+                    ]));
+                }
+                current = to;
+            }
+            PathCommand::CubicTo {
+                control_1,
+                control_2,
+                to,
+            } => {
+                start_sub_path(&mut points, current);
+                let steps = curve_steps(&[current, control_1, control_2, to]);
+                for step in 1..=steps {
+                    let t = step as f32 / steps as f32;
+                    let u = 1.0 - t;
+                    points.push(combine(&[
+                        (current, u * u * u),
+                        (control_1, 3.0 * u * u * t),
+                        (control_2, 3.0 * u * t * t),
+                        (to, t * t * t),
+                    ]));
+                }
+                current = to;
+            }
+            PathCommand::ArcTo {
+                radii,
+                x_rotation,
+                large_arc,
+                sweep,
+                to,
+            } => {
+                start_sub_path(&mut points, current);
+                flatten_arc(
+                    current,
+                    radii,
+                    x_rotation,
+                    large_arc,
+                    sweep,
+                    to,
+                    &mut points,
+                );
+                current = to;
+            }
+            PathCommand::Close => {
+                if points.len() > 1 {
+                    // the closing line is implied, so drop the last point if it is back at the start
+                    if points.len() > 2 && points.first() == points.last() {
+                        points.pop();
+                    }
+                    f(&points, true);
+                }
+                current = points.first().copied().unwrap_or(current);
+                points.clear();
+            }
+        }
+    }
+    if points.len() > 1 {
+        f(&points, false);
+    }
+}
+
+// drawing without a move first (or after a close) starts from the current point
+fn start_sub_path(points: &mut Vec<[f32; 2]>, current: [f32; 2]) {
+    if points.is_empty() {
+        points.push(current);
+    }
+}
+
+fn combine(terms: &[([f32; 2], f32)]) -> [f32; 2] {
+    terms.iter().fold([0.0, 0.0], |[x, y], ([px, py], weight)| {
+        [x + px * weight, y + py * weight]
+    })
+}
+
+// enough lines that the flattened curve stays within the tolerance, based on the length of the control polygon
+fn curve_steps(points: &[[f32; 2]]) -> u32 {
+    let length: f32 = points
+        .windows(2)
+        .map(|s| ((s[1][0] - s[0][0]).powi(2) + (s[1][1] - s[0][1]).powi(2)).sqrt())
+        .sum();
+    ((length / Path::TOLERANCE).sqrt() / 2.0)
+        .ceil()
+        .clamp(1.0, 256.0) as u32
+}
+
+// converts the svg endpoint parameters to a center, radii and angles, see
+// https://www.w3.org/TR/SVG11/implnote.html#ArcConversionEndpointToCenter
+fn flatten_arc(
+    from: [f32; 2],
+    radii: [f32; 2],
+    x_rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: [f32; 2],
+    points: &mut Vec<[f32; 2]>,
+) {
+    let [mut rx, mut ry] = radii.map(f32::abs);
+    if rx == 0.0 || ry == 0.0 || from == to {
+        points.push(to);
+        return;
+    }
+    let (sin, cos) = x_rotation.to_radians().sin_cos();
+    let half = [(from[0] - to[0]) / 2.0, (from[1] - to[1]) / 2.0];
+    let x1 = cos * half[0] + sin * half[1];
+    let y1 = -sin * half[0] + cos * half[1];
+
+    // radii that are too small to reach the end point are scaled up until they just do
+    let scale = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if scale > 1.0 {
+        rx *= scale.sqrt();
+        ry *= scale.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut factor = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        factor = -factor;
+    }
+    let center_x1 = factor * rx * y1 / ry;
+    let center_y1 = -factor * ry * x1 / rx;
+    let center = [
+        cos * center_x1 - sin * center_y1 + (from[0] + to[0]) / 2.0,
+        sin * center_x1 + cos * center_y1 + (from[1] + to[1]) / 2.0,
+    ];
+
+    let angle = |x: f32, y: f32| y.atan2(x);
+    let start = angle((x1 - center_x1) / rx, (y1 - center_y1) / ry);
+    let end = angle((-x1 - center_x1) / rx, (-y1 - center_y1) / ry);
+    let mut delta = end - start;
+    if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    } else if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    }
+
+    let radius = rx.max(ry);
+    let max_step = (2.0 * (1.0 - Path::TOLERANCE / radius.max(Path::TOLERANCE)).acos()).max(0.01);
+    let steps = (delta.abs() / max_step).ceil().clamp(1.0, 256.0) as u32;
+    for step in 1..steps {
+        let (s, c) = (start + delta * step as f32 / steps as f32).sin_cos();
+        let (x, y) = (rx * c, ry * s);
+        points.push([cos * x - sin * y + center[0], sin * x + cos * y + center[1]]);
+    }
+    points.push(to); // exactly at the end point, so following commands line up
+}
@@ -0,0 +1,169 @@
+use crate::primitives::color::Color;
+
+// a stroked line through a list of points, e.g. for sparklines, charts, connectors and underlines
+// points are in frame pixels (not rounded, so lines can be positioned precisely) and backends anti-alias the stroke
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polyline {
+    pub points: Vec<[f32; 2]>, // x, y
+    pub stroke: Stroke,
+    pub area: Option<PolylineArea>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stroke {
+    pub width: f32,
+    pub color: Color,
+    pub join: LineJoin,
+    pub cap: LineCap,
+    pub dash: Option<[f32; 2]>, // dash and gap length in pixels, repeated along the line
+}
+
+// how the corners between segments are drawn, same as svg's stroke-linejoin
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineJoin {
+    #[default]
+    Miter, // falls back to bevel if the corner is sharper than `Stroke::MITER_LIMIT` allows
+    Bevel,
+    Round,
+}
+
+// how the ends of the line (and of each dash) are drawn, same as svg's stroke-linecap
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineCap {
+    #[default]
+    Butt, // ends exactly at the end point
+    Square, // extends past the end point by half the width
+    Round,
+}
+
+// fills the area between the line and a horizontal baseline, e.g. the shaded area under a sparkline
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolylineArea {
+    pub baseline: f32, // y in frame pixels
+    pub color: Color,
+}
+
+impl Stroke {
+    // longest a miter can get, as a multiple of half the width, the same as svg's default stroke-miterlimit
+    pub const MITER_LIMIT: f32 = 4.0;
+
+    // solid line with mitered corners and butt ends
+    pub fn new(width: f32, color: Color) -> Self {
+        Self {
+            width,
+            color,
+            join: LineJoin::default(),
+            cap: LineCap::default(),
+            dash: None,
+        }
+    }
+
+    // how far the stroke can reach from the points it goes through
+    pub fn extent(&self) -> f32 {
+        let half_width = self.width / 2.0;
+        match (self.join, self.cap) {
+            (LineJoin::Miter, _) => half_width * Self::MITER_LIMIT,
+            (_, LineCap::Square) => half_width * std::f32::consts::SQRT_2,
+            _ => half_width,
+        }
+    }
+}
+
+impl Polyline {
+    // pixel bounds of the stroke (and area), as position and size like the other primitives
+    pub fn bounds(&self) -> ([u32; 2], [u32; 2]) {
+        let extent = self.stroke.extent() + 1.0; // include the anti-aliased edge
+        let mut min = [f32::MAX, f32::MAX];
+        let mut max = [f32::MIN, f32::MIN];
+        for [x, y] in &self.points {
+            min = [min[0].min(x - extent), min[1].min(y - extent)];
+            max = [max[0].max(x + extent), max[1].max(y + extent)];
+        }
+        if let Some(area) = &self.area {
+            min[1] = min[1].min(area.baseline);
+            max[1] = max[1].max(area.baseline);
+        }
+        if self.points.is_empty() {
+            return ([0, 0], [0, 0]);
+        }
+        let position = [min[0].max(0.0).floor(), min[1].max(0.0).floor()];
+        let size = [
+            (max[0].ceil() - position[0]).max(0.0),
+            (max[1].ceil() - position[1]).max(0.0),
+        ];
+        (
+            [position[0] as u32, position[1] as u32],
+            [size[0] as u32, size[1] as u32],
+        )
+    }
+
+    // calls `f` with the points of each dash, or once with all points for solid lines
+    // shared by the renderers so dashes line up the same way in every backend
+    pub fn for_each_dash(&self, f: impl FnMut(&[[f32; 2]])) {
+        split_dashes(&self.points, self.stroke.dash, f);
+    }
+}
+
+// splits a line into dashes of `[dash, gap]` pixels, e.g. for the outlines of paths
+pub fn split_dashes(points: &[[f32; 2]], dash: Option<[f32; 2]>, mut f: impl FnMut(&[[f32; 2]])) {
+    let [dash, gap] = match dash {
+        Some([dash, gap]) if dash > 0.0 && gap > 0.0 => [dash, gap],
+        _ => return f(points),
+    };
+    let mut current: Vec<[f32; 2]> = vec![];
+    let mut on = true;
+    let mut remaining = dash; // length left in the current dash or gap
+    for segment in points.windows(2) {
+        let [[ax, ay], [bx, by]] = [segment[0], segment[1]];
+        let length = ((bx - ax).powi(2) + (by - ay).powi(2)).sqrt();
+        let point_at = |distance: f32| {
+            let t = distance / length;
+            [ax + (bx - ax) * t, ay + (by - ay) * t]
+        };
+        if on && current.is_empty() {
+            current.push([ax, ay]);
+        }
+        let mut position = 0.0;
+        while length - position > remaining {
+            position += remaining;
+            if on {
+                current.push(point_at(position));
+                f(&current);
+                current.clear();
+            } else {
+                current.push(point_at(position));
+            }
+            on = !on;
+            remaining = if on { dash } else { gap };
+        }
+        remaining -= length - position;
+        if on {
+            current.push([bx, by]);
+        }
+    }
+    if on && current.len() > 1 {
+        f(&current);
+    }
+}
+
+impl Clone for Polyline {
+    fn clone(&self) -> Self {
+        Self {
+            points: self.points.clone(),
+            stroke: self.stroke,
+            area: self.area,
+        }
+    }
+
+    // lets `RenderList::clone_from` reuse the existing point allocations
+    fn clone_from(&mut self, source: &Self) {
+        self.points.clone_from(&source.points);
+        self.stroke = source.stroke;
+        self.area = source.area;
+    }
+}
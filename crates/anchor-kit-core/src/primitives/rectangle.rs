@@ -1,6 +1,7 @@
 use crate::style::Style;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rectangle {
     pub position: [u32; 2], // x, y
     pub size: [u32; 2],     // w, h
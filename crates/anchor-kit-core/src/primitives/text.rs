@@ -1,9 +1,35 @@
 use crate::style::TextStyle;
 
-#[derive(Clone, Debug)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Text {
     pub text: String,
     pub position: [u32; 2], // x, y
     pub size: [u32; 2],     // w, h
     pub text_style: TextStyle,
+    // where the text starts relative to its position, negative when its start is cut off (e.g. sliding in from the
+    // edge of the screen). renderers clip the text to its position and size
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub offset: [f32; 2],
+}
+
+impl Clone for Text {
+    fn clone(&self) -> Self {
+        Self {
+            text: self.text.clone(),
+            position: self.position,
+            size: self.size,
+            text_style: self.text_style.clone(),
+            offset: self.offset,
+        }
+    }
+
+    // lets `RenderList::clone_from` reuse the existing string allocations
+    fn clone_from(&mut self, source: &Self) {
+        self.text.clone_from(&source.text);
+        self.position = source.position;
+        self.size = source.size;
+        self.text_style.clone_from(&source.text_style);
+        self.offset = source.offset;
+    }
 }
@@ -1,65 +1,339 @@
 use crate::{
-    element::{Element, ElementType},
-    primitives::{image::Image, rectangle::Rectangle, text::Text},
+    animation::apply_animation,
+    chart::{
+        bar_chart_insets, bar_series, for_each_legend_item, format_time, format_value,
+        format_value_tick, heatmap_labels, legend_swatch_size, line_chart_layout, time_ticks,
+        value_ticks, ChartMetrics,
+    },
+    element::{
+        BarChart, DividerOrientation, Donut, ElementTree, ElementType, Gauge, Heatmap, LineChart,
+        PathShape, ProgressBar, Sparkline, Table, TextSpan,
+    },
+    measure::{measure_text, truncate_text},
+    primitives::{
+        arc::Arc,
+        cell_grid::{cell_edges, CellGrid},
+        color::Color,
+        image::Image,
+        path::{FillRule, Path, PathCommand},
+        polyline::{LineCap, LineJoin, Polyline, PolylineArea, Stroke},
+        rectangle::Rectangle,
+        text::Text,
+    },
+    style::{Align, Style, TextStyle},
+    table::{table_cells, table_header_style, TableMetrics},
+    time_series::MinMaxBuckets,
 };
 
-#[derive(Clone, Default, Debug)]
+#[derive(Default, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RenderList {
     pub rectangles: Vec<Rectangle>,
     pub text: Vec<Text>,
     pub images: Vec<Image>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    // render lists recorded before polylines existed
+    pub polylines: Vec<Polyline>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub paths: Vec<Path>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub arcs: Vec<Arc>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cell_grids: Vec<CellGrid>,
 }
 
-pub fn render_pass(root: &Element, render_list: &mut RenderList) {
-    for c in root.children.iter() {
-        handle_element_render(c, render_list)
+impl Clone for RenderList {
+    fn clone(&self) -> Self {
+        Self {
+            rectangles: self.rectangles.clone(),
+            text: self.text.clone(),
+            images: self.images.clone(),
+            polylines: self.polylines.clone(),
+            paths: self.paths.clone(),
+            arcs: self.arcs.clone(),
+            cell_grids: self.cell_grids.clone(),
+        }
+    }
+
+    // reuses the existing buffers, so copying between frames of a similar size doesn't allocate
+    fn clone_from(&mut self, source: &Self) {
+        self.rectangles.clone_from(&source.rectangles);
+        self.text.clone_from(&source.text);
+        self.images.clone_from(&source.images);
+        self.polylines.clone_from(&source.polylines);
+        self.paths.clone_from(&source.paths);
+        self.arcs.clone_from(&source.arcs);
+        self.cell_grids.clone_from(&source.cell_grids);
     }
 }
 
-pub fn handle_element_render(element: &Element, render_list: &mut RenderList) {
-    match &element._type {
-        ElementType::Root => {
-            for c in element.children.iter() {
-                handle_element_render(c, render_list);
-            }
+// writes primitives into an existing render list, reusing the text, polyline, path and cell grid primitives (and their
+// strings, points, commands and colours) from the last frame
+pub(crate) struct RenderWriter<'a> {
+    render_list: &'a mut RenderList,
+    text_len: usize,      // number of text primitives written so far this frame
+    polyline_len: usize,  // number of polylines written so far this frame
+    path_len: usize,      // number of paths written so far this frame
+    cell_grid_len: usize, // number of cell grids written so far this frame
+}
+
+impl<'a> RenderWriter<'a> {
+    pub fn new(render_list: &'a mut RenderList) -> Self {
+        render_list.rectangles.clear();
+        render_list.images.clear();
+        render_list.arcs.clear();
+        Self {
+            render_list,
+            text_len: 0,
+            polyline_len: 0,
+            path_len: 0,
+            cell_grid_len: 0,
         }
-        ElementType::Anchor(_) => {
-            for c in element.children.iter() {
-                handle_element_render(c, render_list);
+    }
+
+    pub fn push_rectangle(&mut self, rectangle: Rectangle) {
+        self.render_list.rectangles.push(rectangle);
+    }
+
+    pub fn push_image(&mut self, image: Image) {
+        self.render_list.images.push(image);
+    }
+
+    pub fn push_arc(&mut self, arc: Arc) {
+        self.render_list.arcs.push(arc);
+    }
+
+    pub fn push_text(
+        &mut self,
+        text: &str,
+        position: [u32; 2],
+        size: [u32; 2],
+        text_style: &TextStyle,
+    ) {
+        match self.render_list.text.get_mut(self.text_len) {
+            Some(text_prim) => {
+                text_prim.text.clear();
+                text_prim.text.push_str(text);
+                text_prim.position = position;
+                text_prim.size = size;
+                text_prim.text_style.clone_from(text_style);
+                text_prim.offset = [0.0, 0.0];
             }
+            None => self.render_list.text.push(Text {
+                text: text.to_string(),
+                position,
+                size,
+                text_style: text_style.clone(),
+                offset: [0.0, 0.0],
+            }),
         }
-        ElementType::Text(_) => {
-            handle_text_element(element, render_list);
+        self.text_len += 1;
+    }
+
+    pub fn push_polyline(
+        &mut self,
+        points: impl IntoIterator<Item = [f32; 2]>,
+        stroke: Stroke,
+        area: Option<PolylineArea>,
+    ) {
+        match self.render_list.polylines.get_mut(self.polyline_len) {
+            Some(polyline) => {
+                polyline.points.clear();
+                polyline.points.extend(points);
+                polyline.stroke = stroke;
+                polyline.area = area;
+            }
+            None => self.render_list.polylines.push(Polyline {
+                points: points.into_iter().collect(),
+                stroke,
+                area,
+            }),
         }
-        ElementType::FlexRow => {
-            for c in element.children.iter() {
-                handle_element_render(c, render_list);
+        self.polyline_len += 1;
+    }
+
+    pub fn push_path(
+        &mut self,
+        commands: impl IntoIterator<Item = PathCommand>,
+        fill: Option<Color>,
+        fill_rule: FillRule,
+        stroke: Option<Stroke>,
+    ) {
+        match self.render_list.paths.get_mut(self.path_len) {
+            Some(path) => {
+                path.commands.clear();
+                path.commands.extend(commands);
+                path.fill = fill;
+                path.fill_rule = fill_rule;
+                path.stroke = stroke;
             }
+            None => self.render_list.paths.push(Path {
+                commands: commands.into_iter().collect(),
+                fill,
+                fill_rule,
+                stroke,
+            }),
         }
-        ElementType::FlexColumn => {
-            for c in element.children.iter() {
-                handle_element_render(c, render_list);
+        self.path_len += 1;
+    }
+
+    pub fn push_cell_grid(
+        &mut self,
+        position: [u32; 2],
+        size: [u32; 2],
+        [rows, columns]: [u32; 2],
+        gap: u32,
+        cell_radius: f32,
+        colors: &[Color],
+    ) {
+        match self.render_list.cell_grids.get_mut(self.cell_grid_len) {
+            Some(grid) => {
+                grid.position = position;
+                grid.size = size;
+                grid.rows = rows;
+                grid.columns = columns;
+                grid.gap = gap;
+                grid.cell_radius = cell_radius;
+                grid.colors.clear();
+                grid.colors.extend_from_slice(colors);
             }
+            None => self.render_list.cell_grids.push(CellGrid {
+                position,
+                size,
+                rows,
+                columns,
+                gap,
+                cell_radius,
+                colors: colors.to_vec(),
+            }),
+        }
+        self.cell_grid_len += 1;
+    }
+
+    // adds all primitives from another render list (e.g. an animated subtree)
+    pub fn append(&mut self, render_list: &mut RenderList) {
+        self.render_list
+            .rectangles
+            .append(&mut render_list.rectangles);
+        self.render_list.images.append(&mut render_list.images);
+        self.render_list.arcs.append(&mut render_list.arcs);
+        for text_prim in render_list.text.drain(..) {
+            self.render_list.text.truncate(self.text_len);
+            self.render_list.text.push(text_prim);
+            self.text_len += 1;
+        }
+        for polyline in render_list.polylines.drain(..) {
+            self.render_list.polylines.truncate(self.polyline_len);
+            self.render_list.polylines.push(polyline);
+            self.polyline_len += 1;
+        }
+        for path in render_list.paths.drain(..) {
+            self.render_list.paths.truncate(self.path_len);
+            self.render_list.paths.push(path);
+            self.path_len += 1;
+        }
+        for grid in render_list.cell_grids.drain(..) {
+            self.render_list.cell_grids.truncate(self.cell_grid_len);
+            self.render_list.cell_grids.push(grid);
+            self.cell_grid_len += 1;
+        }
+    }
+}
+
+impl Drop for RenderWriter<'_> {
+    // any leftover text, polyline, path and cell grid primitives from the last frame are no longer needed
+    fn drop(&mut self) {
+        self.render_list.text.truncate(self.text_len);
+        self.render_list.polylines.truncate(self.polyline_len);
+        self.render_list.paths.truncate(self.path_len);
+        self.render_list.cell_grids.truncate(self.cell_grid_len);
+    }
+}
+
+pub fn render_pass(tree: &ElementTree, render_list: &mut RenderList) {
+    let mut writer = RenderWriter::new(render_list);
+    for c in tree.children(ElementTree::ROOT) {
+        handle_element_render(tree, c, &mut writer)
+    }
+}
+
+pub(crate) fn handle_element_render(tree: &ElementTree, index: usize, writer: &mut RenderWriter) {
+    // animated elements are rendered into their own list first so the animation only applies to their primitives
+    if let Some(animation) = &tree[index].animation {
+        let mut animated_render_list = RenderList::default();
+        {
+            let mut animated_writer = RenderWriter::new(&mut animated_render_list);
+            render_element_content(tree, index, &mut animated_writer);
+        }
+        apply_animation(animation, &tree[index], &mut animated_render_list);
+        writer.append(&mut animated_render_list);
+        return;
+    }
+    render_element_content(tree, index, writer);
+}
+
+fn render_element_content(tree: &ElementTree, index: usize, writer: &mut RenderWriter) {
+    match &tree[index]._type {
+        ElementType::Root
+        | ElementType::Anchor(_)
+        | ElementType::FlexRow
+        | ElementType::FlexColumn => {
+            render_children(tree, index, writer);
+        }
+        ElementType::Text(_) => {
+            handle_text_element(tree, index, writer);
         }
         ElementType::Pill => {
-            handle_pill_element(element, render_list);
-            for c in element.children.iter() {
-                handle_element_render(c, render_list);
-            }
+            handle_pill_element(tree, index, writer);
+            render_children(tree, index, writer);
         }
         ElementType::Image(_) => {
-            handle_image_element(element, render_list);
+            handle_image_element(tree, index, writer);
         }
         ElementType::Divider(_, _) => {
-            handle_divider_element(element, render_list);
+            handle_divider_element(tree, index, writer);
+        }
+        ElementType::Progress(progress) => {
+            handle_progress_element(tree, index, progress, writer);
+        }
+        ElementType::Sparkline(sparkline) => {
+            handle_sparkline_element(tree, index, sparkline, writer);
+        }
+        ElementType::Path(path) => {
+            handle_path_element(tree, index, path, writer);
+        }
+        ElementType::BarChart(chart) => {
+            handle_bar_chart_element(tree, index, chart, writer);
+        }
+        ElementType::LineChart(chart) => {
+            handle_line_chart_element(tree, index, chart, writer);
+        }
+        ElementType::Gauge(gauge) => {
+            handle_gauge_element(tree, index, gauge, writer);
+        }
+        ElementType::Donut(donut) => {
+            handle_donut_element(tree, index, donut, writer);
+        }
+        ElementType::Heatmap(heatmap) => {
+            handle_heatmap_element(tree, index, heatmap, writer);
+        }
+        ElementType::Table(table) => {
+            handle_table_element(tree, index, table, writer);
         }
     }
 }
 
-fn handle_text_element(element: &Element, render_list: &mut RenderList) {
+fn render_children(tree: &ElementTree, index: usize, writer: &mut RenderWriter) {
+    for c in tree.children(index) {
+        handle_element_render(tree, c, writer);
+    }
+}
+
+fn handle_text_element(tree: &ElementTree, index: usize, writer: &mut RenderWriter) {
+    let element = &tree[index];
     // for each of these we skip rendering the text if the values are None
-    let text = match &element._type {
-        ElementType::Text(s) => s.to_string(),
+    let text = match element._type {
+        ElementType::Text(span) => tree.text(span),
         _ => return,
     };
     let position = match &element.frame_position {
@@ -67,29 +341,27 @@ fn handle_text_element(element: &Element, render_list: &mut RenderList) {
         None => return,
     };
 
-    let text_prim = Text {
-        text,
-        position,
-        size: element.size,
-        text_style: element.text_style.clone().unwrap_or_default(),
-    };
-    render_list.text.push(text_prim);
+    let default_text_style = TextStyle::default();
+    let text_style = element.text_style.as_ref().unwrap_or(&default_text_style);
+    writer.push_text(text, position, element.size, text_style);
 }
 
-fn handle_pill_element(element: &Element, render_list: &mut RenderList) {
+fn handle_pill_element(tree: &ElementTree, index: usize, writer: &mut RenderWriter) {
+    let element = &tree[index];
     let position = match &element.frame_position {
         Some(pos) => *pos,
         None => return,
     };
 
-    render_list.rectangles.push(Rectangle {
+    writer.push_rectangle(Rectangle {
         position,
         size: element.size,
         style: element.style, // TODO: for pill we should probably default to rounded corners
     });
 }
 
-fn handle_image_element(element: &Element, render_list: &mut RenderList) {
+fn handle_image_element(tree: &ElementTree, index: usize, writer: &mut RenderWriter) {
+    let element = &tree[index];
     let texture_id = match &element._type {
         ElementType::Image(texture_id) => texture_id,
         _ => return,
@@ -99,25 +371,1020 @@ fn handle_image_element(element: &Element, render_list: &mut RenderList) {
         None => return,
     };
 
-    let image_prim = Image {
-        texture_id: *texture_id,
-        rectangle: Rectangle {
+    let image_prim = Image::new(
+        *texture_id,
+        Rectangle {
             position,
             size: element.size,
             style: element.style,
         },
-    };
-    render_list.images.push(image_prim);
+    );
+    writer.push_image(image_prim);
 }
 
-fn handle_divider_element(element: &Element, render_list: &mut RenderList) {
+fn handle_divider_element(tree: &ElementTree, index: usize, writer: &mut RenderWriter) {
+    let element = &tree[index];
     let position = match &element.frame_position {
         Some(pos) => *pos,
         None => return,
     };
-    render_list.rectangles.push(Rectangle {
+    writer.push_rectangle(Rectangle {
         position,
         size: element.size,
         style: element.style,
     });
 }
+
+// the track is drawn with the element's style, then the fill (or each filled segment) inside its padding and border
+fn handle_progress_element(
+    tree: &ElementTree,
+    index: usize,
+    progress: &ProgressBar,
+    writer: &mut RenderWriter,
+) {
+    let element = &tree[index];
+    let position = match &element.frame_position {
+        Some(pos) => *pos,
+        None => return,
+    };
+    let style = element.style;
+    writer.push_rectangle(Rectangle {
+        position,
+        size: element.size,
+        style,
+    });
+
+    let border = style.border_width as u32;
+    let content_position = [
+        position[0] + style.padding.left + border,
+        position[1] + style.padding.top + border,
+    ];
+    let content_size = [
+        element.size[0].saturating_sub(style.padding.left + style.padding.right + border * 2),
+        element.size[1].saturating_sub(style.padding.top + style.padding.bottom + border * 2),
+    ];
+    // work along the length of the bar, then swap back for vertical bars
+    let (length, thickness) = match progress.orientation {
+        DividerOrientation::Horizontal => (content_size[0], content_size[1]),
+        DividerOrientation::Vertical => (content_size[1], content_size[0]),
+    };
+    if length == 0 || thickness == 0 {
+        return;
+    }
+
+    let segments = progress.segments.max(1);
+    let gaps = match progress.segments {
+        0 => 0,
+        _ => progress.segment_gap * (segments - 1),
+    };
+    let segment_length = length.saturating_sub(gaps) as f32 / segments as f32;
+    let filled_segments = progress.fraction.clamp(0.0, 1.0) * segments as f32;
+
+    for i in 0..segments {
+        let fill = (filled_segments - i as f32).clamp(0.0, 1.0);
+        let fill_length = (segment_length * fill).round() as u32;
+        if fill_length == 0 {
+            continue;
+        }
+        let start = (i as f32 * (segment_length + progress.segment_gap as f32)).round() as u32;
+        let (rect_position, rect_size) = match progress.orientation {
+            DividerOrientation::Horizontal => (
+                [content_position[0] + start, content_position[1]],
+                [fill_length, thickness],
+            ),
+            // vertical bars fill from the bottom up
+            DividerOrientation::Vertical => (
+                [
+                    content_position[0],
+                    content_position[1] + length.saturating_sub(start + fill_length),
+                ],
+                [thickness, fill_length],
+            ),
+        };
+
+        // the fill shares the track's corner rounding, limited so short fills stay round instead of overlapping
+        let max_radius = rect_size[0].min(rect_size[1]) as f32 / 2.0;
+        writer.push_rectangle(Rectangle {
+            position: rect_position,
+            size: rect_size,
+            style: Style {
+                background_color: progress.fill_color,
+                border_radius: style.border_radius.map(|r| r.min(max_radius)),
+                ..Default::default()
+            },
+        });
+    }
+}
+
+// values are spread evenly across the content area and scaled to the sparkline's range, non finite values leave a gap
+fn handle_sparkline_element(
+    tree: &ElementTree,
+    index: usize,
+    sparkline: &Sparkline,
+    writer: &mut RenderWriter,
+) {
+    let element = &tree[index];
+    let position = match &element.frame_position {
+        Some(pos) => *pos,
+        None => return,
+    };
+    let style = element.style;
+    writer.push_rectangle(Rectangle {
+        position,
+        size: element.size,
+        style,
+    });
+
+    let values = tree.data(sparkline.data);
+    let border = style.border_width as u32;
+    let content_position = [
+        (position[0] + style.padding.left + border) as f32,
+        (position[1] + style.padding.top + border) as f32,
+    ];
+    let content_size = [
+        element.size[0].saturating_sub(style.padding.left + style.padding.right + border * 2)
+            as f32,
+        element.size[1].saturating_sub(style.padding.top + style.padding.bottom + border * 2)
+            as f32,
+    ];
+    if values.is_empty() || content_size[0] == 0.0 || content_size[1] == 0.0 {
+        return;
+    }
+
+    // keep the stroke and the dot inside the content area
+    let inset = (sparkline.line_width / 2.0)
+        .max(sparkline.dot_radius.unwrap_or(0.0))
+        .min(content_size[0].min(content_size[1]) / 2.0);
+    let [min, max] = sparkline.range;
+    let point = |i: usize, value: f32| -> [f32; 2] {
+        let x = match values.len() {
+            1 => content_size[0] / 2.0,
+            len => inset + (content_size[0] - inset * 2.0) * i as f32 / (len - 1) as f32,
+        };
+        let t = match max - min {
+            span if span != 0.0 => ((value - min) / span).clamp(0.0, 1.0),
+            _ => 0.5, // flat lines go through the middle
+        };
+        let y = content_size[1] - inset - (content_size[1] - inset * 2.0) * t;
+        [content_position[0] + x, content_position[1] + y]
+    };
+
+    let area = sparkline.fill_color.map(|color| PolylineArea {
+        baseline: content_position[1] + content_size[1],
+        color,
+    });
+    let stroke = Stroke {
+        join: LineJoin::Round,
+        cap: LineCap::Round,
+        ..Stroke::new(sparkline.line_width, sparkline.line_color)
+    };
+    let mut start = 0;
+    while start < values.len() {
+        if !values[start].is_finite() {
+            start += 1;
+            continue;
+        }
+        let end = values[start..]
+            .iter()
+            .position(|v| !v.is_finite())
+            .map_or(values.len(), |len| start + len);
+        writer.push_polyline((start..end).map(|i| point(i, values[i])), stroke, area);
+        start = end;
+    }
+
+    let last = values.iter().rposition(|v| v.is_finite());
+    if let (Some(radius), Some(last)) = (sparkline.dot_radius, last) {
+        let [x, y] = point(last, values[last]);
+        let diameter = (radius * 2.0).round().max(1.0);
+        writer.push_rectangle(Rectangle {
+            position: [
+                (x - diameter / 2.0).round().max(0.0) as u32,
+                (y - diameter / 2.0).round().max(0.0) as u32,
+            ],
+            size: [diameter as u32; 2],
+            style: Style {
+                background_color: sparkline.line_color,
+                border_radius: [diameter / 2.0; 4],
+                ..Default::default()
+            },
+        });
+    }
+}
+
+// the viewbox is scaled by the same amount on both axes to fit the content area and centered in it, like an svg's
+// default `xMidYMid meet`
+fn handle_path_element(
+    tree: &ElementTree,
+    index: usize,
+    path: &PathShape,
+    writer: &mut RenderWriter,
+) {
+    let element = &tree[index];
+    let position = match &element.frame_position {
+        Some(pos) => *pos,
+        None => return,
+    };
+    let style = element.style;
+    writer.push_rectangle(Rectangle {
+        position,
+        size: element.size,
+        style,
+    });
+
+    let border = style.border_width as u32;
+    let content_position = [
+        (position[0] + style.padding.left + border) as f32,
+        (position[1] + style.padding.top + border) as f32,
+    ];
+    let content_size = [
+        element.size[0].saturating_sub(style.padding.left + style.padding.right + border * 2)
+            as f32,
+        element.size[1].saturating_sub(style.padding.top + style.padding.bottom + border * 2)
+            as f32,
+    ];
+    let [view_x, view_y, view_width, view_height] = path.viewbox;
+    if view_width <= 0.0 || view_height <= 0.0 || content_size[0] == 0.0 || content_size[1] == 0.0 {
+        return;
+    }
+
+    let scale = (content_size[0] / view_width).min(content_size[1] / view_height);
+    let offset = [
+        content_position[0] + (content_size[0] - view_width * scale) / 2.0 - view_x * scale,
+        content_position[1] + (content_size[1] - view_height * scale) / 2.0 - view_y * scale,
+    ];
+    let stroke = path.stroke.map(|stroke| Stroke {
+        width: stroke.width * scale,
+        dash: stroke.dash.map(|dash| dash.map(|length| length * scale)),
+        ..stroke
+    });
+    let commands = tree.path_commands(path.commands);
+    writer.push_path(
+        commands
+            .iter()
+            .map(|command| command.transformed(scale, offset)),
+        path.fill,
+        path.fill_rule,
+        stroke,
+    );
+}
+
+// bars are laid out along the category axis in equal bands, grouped side by side or stacked, from the zero line (or
+// the nearest end of the range) to their value
+fn handle_bar_chart_element(
+    tree: &ElementTree,
+    index: usize,
+    chart: &BarChart,
+    writer: &mut RenderWriter,
+) {
+    let element = &tree[index];
+    let position = match &element.frame_position {
+        Some(pos) => *pos,
+        None => return,
+    };
+    let style = element.style;
+    writer.push_rectangle(Rectangle {
+        position,
+        size: element.size,
+        style,
+    });
+
+    let default_text_style = TextStyle::default();
+    let text_style = element.text_style.as_ref().unwrap_or(&default_text_style);
+    let metrics = ChartMetrics::new(tree.text_measurement);
+    let insets = bar_chart_insets(tree, chart, text_style);
+    let [top, right, bottom, left] = insets.sides;
+    let border = style.border_width as u32;
+    let plot_position = [
+        (position[0] + style.padding.left + border + left) as f32,
+        (position[1] + style.padding.top + border + top) as f32,
+    ];
+    let plot_size = [
+        element.size[0]
+            .saturating_sub(style.padding.left + style.padding.right + border * 2 + left + right)
+            as f32,
+        element.size[1]
+            .saturating_sub(style.padding.top + style.padding.bottom + border * 2 + top + bottom)
+            as f32,
+    ];
+    if chart.category_count == 0 || plot_size[0] == 0.0 || plot_size[1] == 0.0 {
+        return;
+    }
+
+    // work in (category axis, value axis) coordinates from the plot's origin, then swap back for vertical bars
+    let vertical = matches!(chart.orientation, DividerOrientation::Vertical);
+    let (category_length, value_length) = match vertical {
+        true => (plot_size[0], plot_size[1]),
+        false => (plot_size[1], plot_size[0]),
+    };
+    let [min, max] = chart.range;
+    let value_offset = |value: f32| match max - min {
+        span if span > 0.0 => ((value - min) / span).clamp(0.0, 1.0) * value_length,
+        _ => 0.0,
+    };
+    let to_frame = |category: f32, value: f32| -> [f32; 2] {
+        match vertical {
+            true => [
+                plot_position[0] + category,
+                plot_position[1] + value_length - value,
+            ],
+            false => [plot_position[0] + value, plot_position[1] + category],
+        }
+    };
+    // edges are rounded separately so neighbouring bars never overlap or leave a gap
+    let area = |category: [f32; 2], value: [f32; 2]| -> ([u32; 2], [u32; 2]) {
+        let [x0, y0] = to_frame(category[0], value[0]).map(f32::round);
+        let [x1, y1] = to_frame(category[1], value[1]).map(f32::round);
+        (
+            [x0.min(x1) as u32, y0.min(y1) as u32],
+            [(x1 - x0).abs() as u32, (y1 - y0).abs() as u32],
+        )
+    };
+    let bar = |writer: &mut RenderWriter, category: [f32; 2], value: [f32; 2], color: Color| {
+        let (position, size) = area(category, value);
+        if size[0] > 0 && size[1] > 0 {
+            writer.push_rectangle(Rectangle {
+                position,
+                size,
+                style: Style {
+                    background_color: color,
+                    border_radius: [chart.bar_radius; 4],
+                    ..Default::default()
+                },
+            });
+        }
+    };
+    let measure = |text: &str| measure_text(text, text_style, tree.text_measurement);
+    let [gap_x, gap_y] = metrics.gap.map(|gap| gap as f32);
+    // labels sit just past the end of each bar (`end` on the value axis), below it for negative values
+    let mut text = tree.take_scratch_text();
+    let mut value_label = |writer: &mut RenderWriter, end: f32, band: [f32; 2], value: f32| {
+        if !chart.value_labels {
+            return;
+        }
+        format_value(&mut text, value, chart.value_decimals);
+        let size = measure(&text);
+        let [w, h] = size.map(|s| s as f32);
+        let outward: f32 = match value < 0.0 {
+            true => -1.0,
+            false => 1.0,
+        };
+        let center = (band[0] + band[1]) / 2.0;
+        let [x, y] = match vertical {
+            true => {
+                let [x, y] = to_frame(center, end + outward * gap_y);
+                [x - w / 2.0, if outward > 0.0 { y - h } else { y }]
+            }
+            false => {
+                let [x, y] = to_frame(center, end + outward * gap_x);
+                [if outward > 0.0 { x } else { x - w }, y - h / 2.0]
+            }
+        };
+        let position = [x.round().max(0.0) as u32, y.round().max(0.0) as u32];
+        writer.push_text(&text, position, size, text_style);
+    };
+
+    let colors = tree.colors(chart.colors); // one per series
+    let band = category_length / chart.category_count as f32;
+    let group = band * (1.0 - chart.category_gap.clamp(0.0, 1.0));
+    let base = value_offset(0.0);
+    for category in 0..chart.category_count {
+        let start = band * category as f32 + (band - group) / 2.0;
+        match chart.stacked {
+            true => {
+                let mut ends = [0.0, 0.0]; // positive and negative
+                let mut any_finite = false;
+                for (series, &color) in colors.iter().enumerate() {
+                    let value = bar_series(tree, chart, series)[category];
+                    if !value.is_finite() {
+                        continue;
+                    }
+                    let end = &mut ends[(value < 0.0) as usize];
+                    bar(
+                        writer,
+                        [start, start + group],
+                        [value_offset(*end), value_offset(*end + value)],
+                        color,
+                    );
+                    *end += value;
+                    any_finite = true;
+                }
+                // the total goes at the end of the positive part, unless the stack is only negative values
+                let end = match ends {
+                    [positive, negative] if positive == 0.0 && negative < 0.0 => negative,
+                    [positive, _] => positive,
+                };
+                if any_finite {
+                    let total = ends[0] + ends[1];
+                    value_label(writer, value_offset(end), [start, start + group], total);
+                }
+            }
+            false => {
+                let width = group / colors.len().max(1) as f32;
+                for (series, &color) in colors.iter().enumerate() {
+                    let value = bar_series(tree, chart, series)[category];
+                    if !value.is_finite() {
+                        continue;
+                    }
+                    let bar_start = start + width * series as f32;
+                    let band = [bar_start, bar_start + width];
+                    bar(writer, band, [base, value_offset(value)], color);
+                    value_label(writer, value_offset(value), band, value);
+                }
+            }
+        }
+    }
+    tree.return_scratch_text(text);
+
+    let Some(labels) = chart.labels else {
+        return;
+    };
+    // the axis line runs along the zero line (or the end of the range nearest to it), the labels sit outside the plot
+    let axis_width = metrics.axis_width as f32;
+    if axis_width > 0.0 {
+        let (position, size) = area([0.0, category_length], [base, base - axis_width]);
+        writer.push_rectangle(Rectangle {
+            position,
+            size,
+            style: Style {
+                background_color: chart.axis_color,
+                ..Default::default()
+            },
+        });
+    }
+    let category_offset = insets.category_offset as f32;
+    for (category, label) in tree.text(labels).split('\n').enumerate() {
+        let size = measure(label);
+        let [w, h] = size.map(|s| s as f32);
+        let [x, y] = to_frame(band * (category as f32 + 0.5), 0.0);
+        let [x, y] = match vertical {
+            true => [x - w / 2.0, y + category_offset + gap_y],
+            false => [x - category_offset - gap_x - w, y - h / 2.0],
+        };
+        let position = [x.round().max(0.0) as u32, y.round().max(0.0) as u32];
+        writer.push_text(label, position, size, text_style);
+    }
+}
+
+// the gridlines and axes go under the series lines, the legend sits above the plot and the tick labels outside it
+fn handle_line_chart_element(
+    tree: &ElementTree,
+    index: usize,
+    chart: &LineChart,
+    writer: &mut RenderWriter,
+) {
+    let element = &tree[index];
+    let position = match &element.frame_position {
+        Some(pos) => *pos,
+        None => return,
+    };
+    let style = element.style;
+    writer.push_rectangle(Rectangle {
+        position,
+        size: element.size,
+        style,
+    });
+
+    let default_text_style = TextStyle::default();
+    let text_style = element.text_style.as_ref().unwrap_or(&default_text_style);
+    let metrics = ChartMetrics::new(tree.text_measurement);
+    let border = style.border_width as u32;
+    let content_position = [
+        position[0] + style.padding.left + border,
+        position[1] + style.padding.top + border,
+    ];
+    let content_size = [
+        element.size[0].saturating_sub(style.padding.left + style.padding.right + border * 2),
+        element.size[1].saturating_sub(style.padding.top + style.padding.bottom + border * 2),
+    ];
+    let layout = line_chart_layout(tree, chart, text_style, content_size);
+    let [top, right, bottom, left] = layout.sides;
+    let plot_position = [
+        (content_position[0] + left) as f32,
+        (content_position[1] + top) as f32,
+    ];
+    let plot_size = [
+        content_size[0].saturating_sub(left + right) as f32,
+        content_size[1].saturating_sub(top + bottom) as f32,
+    ];
+    if plot_size[0] == 0.0 || plot_size[1] == 0.0 {
+        return;
+    }
+
+    let [start, end] = chart.time_range;
+    let [min, max] = chart.range;
+    let time_x = |offset: f64| match end - start {
+        span if span > 0.0 => {
+            plot_position[0] + (offset / span).clamp(0.0, 1.0) as f32 * plot_size[0]
+        }
+        _ => plot_position[0],
+    };
+    let value_y = |value: f32| match max - min {
+        span if span > 0.0 => {
+            plot_position[1] + plot_size[1] - ((value - min) / span).clamp(0.0, 1.0) * plot_size[1]
+        }
+        _ => plot_position[1] + plot_size[1] / 2.0,
+    };
+    let plot_bottom = plot_position[1] + plot_size[1];
+
+    // terminals leave out the lines like they do for the axes
+    let line_width = metrics.axis_width;
+    let mut line = |position: [f32; 2], size: [f32; 2], color: Color| {
+        writer.push_rectangle(Rectangle {
+            position: position.map(|p| p.round().max(0.0) as u32),
+            size: size.map(|s| s.round().max(0.0) as u32),
+            style: Style {
+                background_color: color,
+                ..Default::default()
+            },
+        });
+    };
+    if chart.gridlines && line_width > 0 {
+        let color = chart.axis_color.with_alpha(chart.axis_color.a / 3);
+        for value in value_ticks(chart.range, layout.value_step) {
+            let y = value_y(value).round().min(plot_bottom - line_width as f32);
+            line(
+                [plot_position[0], y],
+                [plot_size[0], line_width as f32],
+                color,
+            );
+        }
+        for time in time_ticks(chart, layout.time_step) {
+            let x = time_x(time - start)
+                .round()
+                .min(plot_position[0] + plot_size[0] - 1.0);
+            line(
+                [x, plot_position[1]],
+                [line_width as f32, plot_size[1]],
+                color,
+            );
+        }
+    }
+    if line_width > 0 {
+        let width = line_width as f32;
+        line(
+            [plot_position[0] - width, plot_position[1]],
+            [width, plot_size[1] + width],
+            chart.axis_color,
+        );
+        line(
+            [plot_position[0], plot_bottom],
+            [plot_size[0], width],
+            chart.axis_color,
+        );
+    }
+
+    // filled areas go down to the zero line, or the end of the range nearest to it
+    let baseline = value_y(0.0f32.clamp(min, max));
+    for series in tree.chart_series(chart.series) {
+        let points = tree.data(series.points);
+        let point = |i: usize| [time_x(points[i * 2] as f64), value_y(points[i * 2 + 1])];
+        let area = series.fill.map(|color| PolylineArea { baseline, color });
+        let stroke = Stroke {
+            join: LineJoin::Round,
+            cap: LineCap::Round,
+            ..Stroke::new(chart.line_width, series.color)
+        };
+        // non finite values split the line like in sparklines
+        let count = points.len() / 2;
+        let mut first = 0;
+        while first < count {
+            if !points[first * 2 + 1].is_finite() {
+                first += 1;
+                continue;
+            }
+            let last = (first..count)
+                .find(|i| !points[i * 2 + 1].is_finite())
+                .unwrap_or(count);
+            // more than a couple of points per pixel column can't be seen, so dense runs keep each column's extremes
+            let columns = plot_size[0] as usize;
+            match last - first > columns * 2 {
+                true => {
+                    let sample =
+                        |i: usize| (points[(first + i) * 2] as f64, points[(first + i) * 2 + 1]);
+                    let buckets =
+                        MinMaxBuckets::new(last - first, [0.0, end - start], columns, sample);
+                    let to_frame = |(time, value): (f64, f32)| [time_x(time), value_y(value)];
+                    writer.push_polyline(buckets.map(to_frame), stroke, area);
+                }
+                false => writer.push_polyline((first..last).map(point), stroke, area),
+            }
+            first = last;
+        }
+    }
+
+    let measure = |text: &str| measure_text(text, text_style, tree.text_measurement);
+    let [gap_x, gap_y] = metrics.gap.map(|gap| gap as f32);
+    let content_right = (content_position[0] + content_size[0]) as f32;
+    let mut text = tree.take_scratch_text();
+    let unit = chart.unit.map_or("", |span| tree.text(span));
+    for value in value_ticks(chart.range, layout.value_step) {
+        format_value_tick(&mut text, value, layout.value_step, unit);
+        let size = measure(&text);
+        let [w, h] = size.map(|s| s as f32);
+        let x = plot_position[0] - line_width as f32 - gap_x - w;
+        let y = (value_y(value) - h / 2.0).max(content_position[1] as f32);
+        writer.push_text(
+            &text,
+            [x.round().max(0.0) as u32, y.round() as u32],
+            size,
+            text_style,
+        );
+    }
+    for time in time_ticks(chart, layout.time_step) {
+        format_time(&mut text, time, layout.time_step, chart);
+        let size = measure(&text);
+        let [w, _] = size.map(|s| s as f32);
+        let x = (time_x(time - start) - w / 2.0)
+            .min(content_right - w)
+            .max(content_position[0] as f32);
+        let y = plot_bottom + line_width as f32 + gap_y;
+        writer.push_text(
+            &text,
+            [x.round() as u32, y.round() as u32],
+            size,
+            text_style,
+        );
+    }
+    tree.return_scratch_text(text);
+
+    if layout.legend_height == 0 {
+        return;
+    }
+    // aligned with the plot, unless it only fits from the left of the content area
+    let swatch = legend_swatch_size(tree, text_style);
+    let legend_width = for_each_legend_item(tree, chart, text_style, |_, _, _| {});
+    let legend_x = match legend_width <= plot_size[0] as u32 {
+        true => plot_position[0] as u32,
+        false => content_position[0],
+    };
+    let legend_y = content_position[1];
+    for_each_legend_item(tree, chart, text_style, |x, series, size| {
+        let x = legend_x + x;
+        writer.push_rectangle(Rectangle {
+            position: [x, legend_y + (layout.legend_height - swatch) / 2],
+            size: [swatch, swatch],
+            style: Style {
+                background_color: series.color,
+                ..Default::default()
+            },
+        });
+        let name_position = [x + swatch + gap_x as u32, legend_y];
+        writer.push_text(tree.text(series.name), name_position, size, text_style);
+    });
+}
+
+// the track is the whole sweep, the fill covers the gauge's fraction of it from the start angle
+fn handle_gauge_element(
+    tree: &ElementTree,
+    index: usize,
+    gauge: &Gauge,
+    writer: &mut RenderWriter,
+) {
+    let Some((center, outer_radius)) = render_radial_frame(tree, index, writer) else {
+        return;
+    };
+    let thickness = outer_radius * gauge.thickness.clamp(0.0, 1.0);
+    let track = Arc {
+        center,
+        radius: outer_radius - thickness / 2.0,
+        thickness,
+        start_angle: gauge.angles[0],
+        end_angle: gauge.angles[1],
+        color: gauge.track_color,
+        rounded_caps: gauge.rounded_caps,
+    };
+    writer.push_arc(track);
+
+    let fraction = gauge.fraction.clamp(0.0, 1.0);
+    if fraction > 0.0 {
+        writer.push_arc(Arc {
+            end_angle: track.start_angle + (track.end_angle - track.start_angle) * fraction,
+            color: gauge.fill_color,
+            ..track
+        });
+    }
+    render_radial_label(tree, index, gauge.label, center, writer);
+}
+
+// slices follow each other clockwise from the start angle. the gap is taken out of both ends of every slice so
+// neighbours don't touch, and rounded caps are pulled in by their radius so they don't reach into the next slice
+fn handle_donut_element(
+    tree: &ElementTree,
+    index: usize,
+    donut: &Donut,
+    writer: &mut RenderWriter,
+) {
+    let Some((center, outer_radius)) = render_radial_frame(tree, index, writer) else {
+        return;
+    };
+    let thickness = outer_radius * donut.thickness.clamp(0.0, 1.0);
+    let track = Arc {
+        center,
+        radius: outer_radius - thickness / 2.0,
+        thickness,
+        start_angle: donut.start_angle,
+        end_angle: donut.start_angle + 360.0,
+        color: donut.track_color.unwrap_or(Color::TRANSPARENT),
+        rounded_caps: false,
+    };
+    if donut.track_color.is_some() {
+        writer.push_arc(track);
+    }
+
+    let values = tree.data(donut.values);
+    let colors = tree.colors(donut.colors);
+    let visible = values.iter().filter(|v| v.is_finite() && **v > 0.0).count();
+    let mut pad = match track.radius > 0.0 {
+        true => (donut.gap / 2.0 / track.radius).to_degrees(),
+        false => 0.0,
+    };
+    if donut.rounded_caps && track.radius > 0.0 {
+        pad += (thickness / 2.0 / track.radius).to_degrees();
+    }
+    let mut angle = donut.start_angle;
+    for (value, color) in values.iter().zip(colors) {
+        if !value.is_finite() || *value <= 0.0 || donut.total <= 0.0 {
+            continue;
+        }
+        let sweep = 360.0 * (value / donut.total).min(1.0);
+        let (start, end) = match visible == 1 && sweep >= 360.0 {
+            // a single slice of the whole total is a full ring, there is no neighbour to leave a gap for
+            true => (angle, angle + 360.0),
+            false if sweep > pad * 2.0 => (angle + pad, angle + sweep - pad),
+            // too small for the gap, a dot in the middle of its share with rounded caps and nothing without
+            false => (angle + sweep / 2.0, angle + sweep / 2.0),
+        };
+        angle += sweep;
+        if start == end && !donut.rounded_caps {
+            continue;
+        }
+        writer.push_arc(Arc {
+            start_angle: start,
+            end_angle: end,
+            color: *color,
+            rounded_caps: donut.rounded_caps,
+            ..track
+        });
+    }
+    render_radial_label(tree, index, donut.label, center, writer);
+}
+
+// pushes the element's background and returns the centre and radius of the largest circle inside its content area
+fn render_radial_frame(
+    tree: &ElementTree,
+    index: usize,
+    writer: &mut RenderWriter,
+) -> Option<([f32; 2], f32)> {
+    let element = &tree[index];
+    let position = element.frame_position?;
+    let style = element.style;
+    writer.push_rectangle(Rectangle {
+        position,
+        size: element.size,
+        style,
+    });
+
+    let border = style.border_width as u32;
+    let content_position = [
+        position[0] + style.padding.left + border,
+        position[1] + style.padding.top + border,
+    ];
+    let content_size = [
+        element.size[0].saturating_sub(style.padding.left + style.padding.right + border * 2),
+        element.size[1].saturating_sub(style.padding.top + style.padding.bottom + border * 2),
+    ];
+    let radius = content_size[0].min(content_size[1]) as f32 / 2.0;
+    if radius <= 0.0 {
+        return None;
+    }
+    let center = [
+        content_position[0] as f32 + content_size[0] as f32 / 2.0,
+        content_position[1] as f32 + content_size[1] as f32 / 2.0,
+    ];
+    Some((center, radius))
+}
+
+fn render_radial_label(
+    tree: &ElementTree,
+    index: usize,
+    label: Option<TextSpan>,
+    center: [f32; 2],
+    writer: &mut RenderWriter,
+) {
+    let Some(label) = label else {
+        return;
+    };
+    let default_text_style = TextStyle::default();
+    let text_style = tree[index]
+        .text_style
+        .as_ref()
+        .unwrap_or(&default_text_style);
+    let text = tree.text(label);
+    let size = measure_text(text, text_style, tree.text_measurement);
+    let position = [
+        (center[0] - size[0] as f32 / 2.0).round().max(0.0) as u32,
+        (center[1] - size[1] as f32 / 2.0).round().max(0.0) as u32,
+    ];
+    writer.push_text(text, position, size, text_style);
+}
+
+// the cells fill the content area past the labels, row labels are right aligned next to their row and column labels
+// centred above their column
+fn handle_heatmap_element(
+    tree: &ElementTree,
+    index: usize,
+    heatmap: &Heatmap,
+    writer: &mut RenderWriter,
+) {
+    let element = &tree[index];
+    let position = match &element.frame_position {
+        Some(pos) => *pos,
+        None => return,
+    };
+    let style = element.style;
+    writer.push_rectangle(Rectangle {
+        position,
+        size: element.size,
+        style,
+    });
+
+    let default_text_style = TextStyle::default();
+    let text_style = element.text_style.as_ref().unwrap_or(&default_text_style);
+    let [gap_x, gap_y] = ChartMetrics::new(tree.text_measurement).gap;
+    let labels = heatmap_labels(tree, heatmap, text_style);
+    let border = style.border_width as u32;
+    let grid_position = [
+        position[0] + style.padding.left + border + labels.left,
+        position[1] + style.padding.top + border + labels.top,
+    ];
+    let grid_size = [
+        element.size[0]
+            .saturating_sub(style.padding.left + style.padding.right + border * 2 + labels.left),
+        element.size[1]
+            .saturating_sub(style.padding.top + style.padding.bottom + border * 2 + labels.top),
+    ];
+    if heatmap.rows == 0 || heatmap.columns == 0 || grid_size[0] == 0 || grid_size[1] == 0 {
+        return;
+    }
+    writer.push_cell_grid(
+        grid_position,
+        grid_size,
+        [heatmap.rows, heatmap.columns],
+        heatmap.gap,
+        heatmap.cell_radius,
+        tree.colors(heatmap.colors),
+    );
+
+    if let Some(row_labels) = heatmap.row_labels {
+        for (row, label) in tree.text(row_labels).split('\n').enumerate() {
+            let [y0, y1] = cell_edges(
+                row as u32,
+                heatmap.rows,
+                grid_position[1],
+                grid_size[1],
+                heatmap.gap,
+            );
+            let size = measure_text(label, text_style, tree.text_measurement);
+            let x = grid_position[0].saturating_sub(gap_x + size[0]);
+            let y = ((y0 + y1) as f32 / 2.0 - size[1] as f32 / 2.0)
+                .round()
+                .max(0.0) as u32;
+            writer.push_text(label, [x, y], size, text_style);
+        }
+    }
+    if let Some(column_labels) = heatmap.column_labels {
+        for (column, label) in tree.text(column_labels).split('\n').enumerate() {
+            let [x0, x1] = cell_edges(
+                column as u32,
+                heatmap.columns,
+                grid_position[0],
+                grid_size[0],
+                heatmap.gap,
+            );
+            let size = measure_text(label, text_style, tree.text_measurement);
+            let x = ((x0 + x1) as f32 / 2.0 - size[0] as f32 / 2.0)
+                .round()
+                .max(0.0) as u32;
+            let y = grid_position[1].saturating_sub(gap_y + size[1]);
+            writer.push_text(label, [x, y], size, text_style);
+        }
+    }
+}
+
+// row backgrounds and dividers first, then the cells left to right in each row. rows that don't fit in the content
+// area are left out, and cells that don't fit in their column are cut short with an ellipsis
+fn handle_table_element(
+    tree: &ElementTree,
+    index: usize,
+    table: &Table,
+    writer: &mut RenderWriter,
+) {
+    let element = &tree[index];
+    let position = match &element.frame_position {
+        Some(pos) => *pos,
+        None => return,
+    };
+    let style = element.style;
+    writer.push_rectangle(Rectangle {
+        position,
+        size: element.size,
+        style,
+    });
+
+    let default_text_style = TextStyle::default();
+    let text_style = element.text_style.as_ref().unwrap_or(&default_text_style);
+    let header_style = table_header_style(table, text_style);
+    let metrics = TableMetrics::new(table, text_style, tree.text_measurement);
+    let border = style.border_width as u32;
+    let content_position = [
+        position[0] + style.padding.left + border,
+        position[1] + style.padding.top + border,
+    ];
+    let content_size = [
+        element.size[0].saturating_sub(style.padding.left + style.padding.right + border * 2),
+        element.size[1].saturating_sub(style.padding.top + style.padding.bottom + border * 2),
+    ];
+    let row_pitch = metrics.row_height.saturating_add(table.divider_thickness);
+    let visible_rows = match metrics.row_height {
+        0 => 0,
+        _ => content_size[1].saturating_add(table.divider_thickness) / row_pitch.max(1),
+    }
+    .min(metrics.row_count);
+    if visible_rows == 0 || content_size[0] == 0 {
+        return;
+    }
+    let row_y = |row: u32| content_position[1] + row * row_pitch;
+    let fill = |y: u32, height: u32, color| Rectangle {
+        position: [content_position[0], y],
+        size: [content_size[0], height],
+        style: Style {
+            background_color: color,
+            ..Default::default()
+        },
+    };
+
+    for row in 0..visible_rows {
+        let background = match (table.header, row) {
+            (true, 0) => table.header_background,
+            // counted from the first row below the header so the striping doesn't depend on having one
+            (header, row) if (row - header as u32) % 2 == 1 => table.zebra_color,
+            _ => None,
+        };
+        if let Some(color) = background {
+            writer.push_rectangle(fill(row_y(row), metrics.row_height, color));
+        }
+        // drawn like a horizontal `ui.divider`, a rectangle of the divider's thickness across the table
+        if table.divider_thickness > 0 && row + 1 < visible_rows {
+            let y = row_y(row) + metrics.row_height;
+            writer.push_rectangle(fill(y, table.divider_thickness, table.divider_color));
+        }
+    }
+
+    let widths = tree.table_column_widths(table.widths);
+    let columns = tree.table_columns(table.columns);
+    let right = content_position[0] + content_size[0];
+    let [padding_x, padding_y] = metrics.padding;
+    let mut text = tree.take_scratch_text();
+    let mut x = content_position[0];
+    for (row, column, cell) in table_cells(tree.text(table.cells), table) {
+        if row >= visible_rows {
+            break;
+        }
+        if column == 0 {
+            x = content_position[0];
+        }
+        let cell_x = x + padding_x;
+        x += widths[column];
+        let cell_style = match table.header && row == 0 {
+            true => &header_style,
+            false => text_style,
+        };
+        let available = widths[column]
+            .saturating_sub(padding_x * 2)
+            .min(right.saturating_sub(cell_x));
+        if cell.is_empty()
+            || !truncate_text(
+                &mut text,
+                cell,
+                available,
+                cell_style,
+                tree.text_measurement,
+            )
+        {
+            continue;
+        }
+        let size = measure_text(&text, cell_style, tree.text_measurement);
+        let offset = match columns[column].align {
+            Align::Start => 0,
+            Align::Middle => available.saturating_sub(size[0]) / 2,
+            Align::End => available.saturating_sub(size[0]),
+        };
+        writer.push_text(
+            &text,
+            [cell_x + offset, row_y(row) + padding_y],
+            size,
+            cell_style,
+        );
+    }
+    tree.return_scratch_text(text);
+}
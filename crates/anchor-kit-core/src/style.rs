@@ -1,20 +1,24 @@
-use crate::primitives::color::Color;
+use crate::{animation::Transition, primitives::color::Color};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SizingPolicy {
     Auto,       // hug to child elements
     Fixed(u32), // individual policy for width and height so only need 1 u32 here
     FillParent, // take up entire space of parent element
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Align {
     Start, // left for rows, top for cols
     Middle,
     End, // right for rows, bottom for cols
 }
 
-#[derive(Default, Debug, Clone, Copy)]
+#[derive(Default, Debug, Clone, Copy, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
 pub struct Insets {
     pub top: u32,
     pub right: u32,
@@ -22,7 +26,9 @@ pub struct Insets {
     pub left: u32,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
 pub struct Style {
     pub padding: Insets,
     pub margin: Insets,
@@ -36,6 +42,7 @@ pub struct Style {
     pub border_color: Color,
     pub border_radius: [f32; 4], // top-left, top-right, bottom-right, bottom-left (clockwise)
     pub border_width: f32,       // thickness of border
+    pub transition: Option<Transition>, // enter/ exit animation for when the element appears or disappears
 }
 
 impl Default for Style {
@@ -53,11 +60,13 @@ impl Default for Style {
             border_color: Color::default(),
             border_radius: [0.0, 0.0, 0.0, 0.0],
             border_width: 0.0,
+            transition: None,
         }
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FontFamily {
     Name(String),
     Serif,
@@ -67,7 +76,29 @@ pub enum FontFamily {
     Monospace,
 }
 
-#[derive(Clone, Debug)]
+impl Clone for FontFamily {
+    fn clone(&self) -> Self {
+        match self {
+            FontFamily::Name(name) => FontFamily::Name(name.clone()),
+            FontFamily::Serif => FontFamily::Serif,
+            FontFamily::SansSerif => FontFamily::SansSerif,
+            FontFamily::Cursive => FontFamily::Cursive,
+            FontFamily::Fantasy => FontFamily::Fantasy,
+            FontFamily::Monospace => FontFamily::Monospace,
+        }
+    }
+
+    // reuse the existing name allocation when copying text styles into last frame's render list
+    fn clone_from(&mut self, source: &Self) {
+        match (self, source) {
+            (FontFamily::Name(name), FontFamily::Name(source_name)) => name.clone_from(source_name),
+            (this, source) => *this = source.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FontWeight {
     Thin,
     ExtraLight,
@@ -80,7 +111,8 @@ pub enum FontWeight {
     Black,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FontStyle {
     Normal,
     Italic,
@@ -89,7 +121,9 @@ pub enum FontStyle {
 
 // text style is pretty different (specific to text rendering) so we should keep it seperate
 // the items in this struct will be generic, and then integrate with glyphon in the wgpu integration (to allow support for other rendering frameworks in the future)
-#[derive(Clone, Debug)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
 pub struct TextStyle {
     pub font_size: f32,
     pub line_height: f32,
@@ -99,6 +133,28 @@ pub struct TextStyle {
     pub text_color: Color,
 }
 
+impl Clone for TextStyle {
+    fn clone(&self) -> Self {
+        Self {
+            font_size: self.font_size,
+            line_height: self.line_height,
+            font_family: self.font_family.clone(),
+            font_weight: self.font_weight.clone(),
+            font_style: self.font_style.clone(),
+            text_color: self.text_color,
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.font_size = source.font_size;
+        self.line_height = source.line_height;
+        self.font_family.clone_from(&source.font_family);
+        self.font_weight = source.font_weight.clone();
+        self.font_style = source.font_style.clone();
+        self.text_color = source.text_color;
+    }
+}
+
 impl Default for TextStyle {
     fn default() -> Self {
         Self {
@@ -111,3 +167,41 @@ impl Default for TextStyle {
         }
     }
 }
+
+// text style properties that are inherited by all text inside a container, unset properties come from the parent
+// e.g. `ui.text_style(PartialTextStyle { text_color: Some(white), ..Default::default() }).pill(None, |ui| ...)`
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))] // fields can be left out in layout files
+pub struct PartialTextStyle {
+    pub font_size: Option<f32>,
+    pub line_height: Option<f32>,
+    pub font_family: Option<FontFamily>,
+    pub font_weight: Option<FontWeight>,
+    pub font_style: Option<FontStyle>,
+    pub text_color: Option<Color>,
+}
+
+impl PartialTextStyle {
+    // overwrites the properties that are set in this partial style
+    pub fn apply_to(&self, text_style: &mut TextStyle) {
+        if let Some(font_size) = self.font_size {
+            text_style.font_size = font_size;
+        }
+        if let Some(line_height) = self.line_height {
+            text_style.line_height = line_height;
+        }
+        if let Some(font_family) = &self.font_family {
+            text_style.font_family.clone_from(font_family);
+        }
+        if let Some(font_weight) = &self.font_weight {
+            text_style.font_weight = font_weight.clone();
+        }
+        if let Some(font_style) = &self.font_style {
+            text_style.font_style = font_style.clone();
+        }
+        if let Some(text_color) = self.text_color {
+            text_style.text_color = text_color;
+        }
+    }
+}
@@ -0,0 +1,735 @@
+use std::fmt;
+
+use crate::{
+    element::{Element, ElementTree, ElementType},
+    primitives::color::Color,
+    style::{Align, FontFamily, FontStyle, FontWeight, Insets, SizingPolicy, Style, TextStyle},
+    theme::{ColorToken, SpaceToken, Theme, TypeToken},
+};
+
+// css-like rules that set style properties based on element types and classes, e.g.
+//
+// pill.card { background-color: #202030; border-radius: 8; padding: 6 10; }
+// .card text { color: #ffffff; font-weight: bold; }
+//
+// selectors are made of element types (the `UI` builder names, e.g. `pill`, `flex_row`, `text`), classes and `*`,
+// with spaces for descendants. rules apply in order of specificity (classes, then types) and then source order
+// elements built with an explicit style (`Some(Style {...})`) keep the fields it sets, matching rules only fill in the
+// fields that are still at their `Style::default()` value. so `ui.class("card").pill(Some(Style { width: Fixed(200),
+// ..Default::default() }), ...)` is 200 wide with the card's colours and padding, but an explicit style can't reset a
+// rule's property back to the default value
+// text properties on containers are inherited by the text inside them, e.g. `.card { color: #fff; }`
+// theme tokens can be used instead of raw values, e.g. `background-color: var(--surface); padding: var(--space-md);`
+// or `font-size: var(--text-title)` (which also sets the line height), they are resolved with the current theme
+#[derive(Clone, Debug, Default)]
+pub struct Stylesheet {
+    rules: Vec<Rule>, // sorted by specificity and then source order, so later rules win
+    next_order: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    selector: Selector,
+    declarations: Vec<Declaration>,
+    specificity: (usize, usize), // classes, types
+    order: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Selector {
+    compounds: Vec<Compound>, // ancestors first, the last compound matches the element itself
+}
+
+#[derive(Clone, Debug)]
+struct Compound {
+    element_type: Option<&'static str>, // None for `*` or class only selectors
+    classes: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+enum Declaration {
+    Padding([Option<Space>; 4]), // top, right, bottom, left (None keeps the current value)
+    Margin([Option<Space>; 4]),
+    Width(SizingPolicy),
+    Height(SizingPolicy),
+    AlignX(Align),
+    AlignY(Align),
+    JustifyX(Align),
+    JustifyY(Align),
+    BackgroundColor(ColorValue),
+    BorderColor(ColorValue),
+    BorderRadius([Option<f32>; 4]), // top-left, top-right, bottom-right, bottom-left (clockwise)
+    BorderWidth(f32),
+    FontSize(f32),
+    TypeSize(TypeToken), // font size and line height from the theme's type scale
+    LineHeight(f32),
+    FontFamily(FontFamily),
+    FontWeight(FontWeight),
+    FontStyle(FontStyle),
+    TextColor(ColorValue),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ColorValue {
+    Color(Color),
+    Token(ColorToken),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Space {
+    Px(u32),
+    Token(SpaceToken),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StylesheetError {
+    pub line: usize, // 1 based
+    pub message: String,
+}
+
+impl fmt::Display for StylesheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "stylesheet error on line {}: {}",
+            self.line, self.message
+        )
+    }
+}
+
+impl std::error::Error for StylesheetError {}
+
+// whether the element was built with its own style, in which case the stylesheet doesn't change it
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ExplicitStyle {
+    pub style: bool,
+    pub text_style: bool,
+}
+
+impl Stylesheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(source: &str) -> Result<Self, StylesheetError> {
+        let mut stylesheet = Self::new();
+        stylesheet.add(source)?;
+        Ok(stylesheet)
+    }
+
+    // adds the rules from the source after the existing ones, nothing is added if there is an error
+    pub fn add(&mut self, source: &str) -> Result<(), StylesheetError> {
+        let mut rules = parse_rules(source, self.next_order)?;
+        self.next_order += rules.len();
+        self.rules.append(&mut rules);
+        self.rules.sort_by_key(|r| (r.specificity, r.order)); // stable, and cheap since the rules are mostly sorted
+        Ok(())
+    }
+
+    // shorthand for a single class rule, e.g. `add_class("card", "background-color: #202030; padding: 8")`
+    pub fn add_class(&mut self, class: &str, declarations: &str) -> Result<(), StylesheetError> {
+        self.add(&format!(".{class} {{ {declarations} }}"))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.rules.clear();
+        self.next_order = 0;
+    }
+
+    // sets the style of an element that is about to be added to the parent
+    pub(crate) fn apply(
+        &self,
+        tree: &ElementTree,
+        parent: usize,
+        element: &mut Element,
+        explicit: ExplicitStyle,
+        theme: &Theme,
+    ) {
+        let element_type = type_name(&element._type);
+        let classes = element.classes.map(|span| tree.text(span)).unwrap_or("");
+        // rules for an element with an explicit style are applied to a default style first, and merged in afterwards
+        let mut rule_style = explicit.style.then(Style::default);
+        for rule in &self.rules {
+            if !rule.selector.matches(tree, element_type, classes, parent) {
+                continue;
+            }
+            for declaration in &rule.declarations {
+                let style = match &mut rule_style {
+                    Some(rule_style) => rule_style,
+                    None => &mut element.style,
+                };
+                let text_style = match explicit.text_style {
+                    true => None,
+                    false => element.text_style.as_mut(),
+                };
+                declaration.apply(theme, Some(style), text_style);
+            }
+        }
+        if let Some(rule_style) = rule_style {
+            merge_unset_fields(&mut element.style, &rule_style);
+        }
+    }
+}
+
+// takes the fields of an explicit style that are still at their default value from the rules
+fn merge_unset_fields(style: &mut Style, rule_style: &Style) {
+    fn merge<T: PartialEq + Copy>(field: &mut T, unset: T, rule: T) {
+        if *field == unset {
+            *field = rule;
+        }
+    }
+    let unset = Style::default();
+    // destructured so new fields can't be forgotten here
+    let Style {
+        padding,
+        margin,
+        width,
+        height,
+        align_x,
+        align_y,
+        justify_x,
+        justify_y,
+        background_color,
+        border_color,
+        border_radius,
+        border_width,
+        transition,
+    } = style;
+    merge(padding, unset.padding, rule_style.padding);
+    merge(margin, unset.margin, rule_style.margin);
+    merge(width, unset.width, rule_style.width);
+    merge(height, unset.height, rule_style.height);
+    merge(align_x, unset.align_x, rule_style.align_x);
+    merge(align_y, unset.align_y, rule_style.align_y);
+    merge(justify_x, unset.justify_x, rule_style.justify_x);
+    merge(justify_y, unset.justify_y, rule_style.justify_y);
+    merge(
+        background_color,
+        unset.background_color,
+        rule_style.background_color,
+    );
+    merge(border_color, unset.border_color, rule_style.border_color);
+    merge(border_radius, unset.border_radius, rule_style.border_radius);
+    merge(border_width, unset.border_width, rule_style.border_width);
+    merge(transition, unset.transition, rule_style.transition);
+}
+
+// the selector name of every element type, also the list of names a selector can use so the two can't get out of step
+macro_rules! type_names {
+    ($($pattern:pat => $name:literal,)*) => {
+        fn type_name(element_type: &ElementType) -> &'static str {
+            match element_type {
+                $($pattern => $name,)*
+            }
+        }
+
+        const TYPE_NAMES: &[&str] = &[$($name,)*];
+    };
+}
+
+type_names! {
+    ElementType::Root => "root",
+    ElementType::Anchor(_) => "anchor",
+    ElementType::Text(_) => "text",
+    ElementType::FlexRow => "flex_row",
+    ElementType::FlexColumn => "flex_column",
+    ElementType::Pill => "pill",
+    ElementType::Image(_) => "image",
+    ElementType::Divider(_, _) => "divider",
+    ElementType::Progress(_) => "progress",
+    ElementType::Sparkline(_) => "sparkline",
+    ElementType::Path(_) => "path",
+    ElementType::BarChart(_) => "bar_chart",
+    ElementType::LineChart(_) => "line_chart",
+    ElementType::Gauge(_) => "gauge",
+    ElementType::Donut(_) => "donut",
+    ElementType::Heatmap(_) => "heatmap",
+    ElementType::Table(_) => "table",
+}
+
+impl Selector {
+    // only descendant combinators are supported, so matching the closest ancestor for each compound is enough
+    fn matches(
+        &self,
+        tree: &ElementTree,
+        element_type: &str,
+        classes: &str,
+        parent: usize,
+    ) -> bool {
+        let Some((last, ancestors)) = self.compounds.split_last() else {
+            return false;
+        };
+        if !last.matches(element_type, classes) {
+            return false;
+        }
+
+        let mut current = Some(parent);
+        for compound in ancestors.iter().rev() {
+            loop {
+                let Some(index) = current else {
+                    return false;
+                };
+                current = tree[index].parent;
+                let ancestor = &tree[index];
+                let ancestor_classes = ancestor.classes.map(|span| tree.text(span)).unwrap_or("");
+                if compound.matches(type_name(&ancestor._type), ancestor_classes) {
+                    break;
+                }
+            }
+        }
+        true
+    }
+}
+
+impl Compound {
+    fn matches(&self, element_type: &str, classes: &str) -> bool {
+        if let Some(t) = self.element_type {
+            if t != element_type {
+                return false;
+            }
+        }
+        self.classes
+            .iter()
+            .all(|class| classes.split_whitespace().any(|c| c == class))
+    }
+}
+
+impl Declaration {
+    fn apply(&self, theme: &Theme, style: Option<&mut Style>, text_style: Option<&mut TextStyle>) {
+        match self {
+            Declaration::FontSize(_)
+            | Declaration::TypeSize(_)
+            | Declaration::LineHeight(_)
+            | Declaration::FontFamily(_)
+            | Declaration::FontWeight(_)
+            | Declaration::FontStyle(_)
+            | Declaration::TextColor(_) => {
+                if let Some(text_style) = text_style {
+                    self.apply_to_text_style(theme, text_style);
+                }
+            }
+            _ => {
+                if let Some(style) = style {
+                    self.apply_to_style(theme, style);
+                }
+            }
+        }
+    }
+
+    fn apply_to_style(&self, theme: &Theme, style: &mut Style) {
+        match self {
+            Declaration::Padding(sides) => apply_sides(theme, sides, &mut style.padding),
+            Declaration::Margin(sides) => apply_sides(theme, sides, &mut style.margin),
+            Declaration::Width(width) => style.width = *width,
+            Declaration::Height(height) => style.height = *height,
+            Declaration::AlignX(align) => style.align_x = *align,
+            Declaration::AlignY(align) => style.align_y = *align,
+            Declaration::JustifyX(align) => style.justify_x = *align,
+            Declaration::JustifyY(align) => style.justify_y = *align,
+            Declaration::BackgroundColor(color) => style.background_color = color.resolve(theme),
+            Declaration::BorderColor(color) => style.border_color = color.resolve(theme),
+            Declaration::BorderRadius(corners) => {
+                for (r, corner) in style.border_radius.iter_mut().zip(corners) {
+                    if let Some(corner) = corner {
+                        *r = *corner;
+                    }
+                }
+            }
+            Declaration::BorderWidth(width) => style.border_width = *width,
+            _ => {}
+        }
+    }
+
+    fn apply_to_text_style(&self, theme: &Theme, text_style: &mut TextStyle) {
+        match self {
+            Declaration::FontSize(size) => text_style.font_size = *size,
+            Declaration::TypeSize(token) => {
+                let type_size = theme.type_size(*token);
+                text_style.font_size = type_size.font_size;
+                text_style.line_height = type_size.line_height;
+            }
+            Declaration::LineHeight(height) => text_style.line_height = *height,
+            Declaration::FontFamily(family) => text_style.font_family.clone_from(family),
+            Declaration::FontWeight(weight) => text_style.font_weight = weight.clone(),
+            Declaration::FontStyle(font_style) => text_style.font_style = font_style.clone(),
+            Declaration::TextColor(color) => text_style.text_color = color.resolve(theme),
+            _ => {}
+        }
+    }
+}
+
+impl ColorValue {
+    fn resolve(&self, theme: &Theme) -> Color {
+        match self {
+            ColorValue::Color(color) => *color,
+            ColorValue::Token(token) => theme.color(*token),
+        }
+    }
+}
+
+impl Space {
+    fn resolve(&self, theme: &Theme) -> u32 {
+        match self {
+            Space::Px(px) => *px,
+            Space::Token(token) => theme.space(*token),
+        }
+    }
+}
+
+fn apply_sides(theme: &Theme, sides: &[Option<Space>; 4], insets: &mut Insets) {
+    let insets = [
+        &mut insets.top,
+        &mut insets.right,
+        &mut insets.bottom,
+        &mut insets.left,
+    ];
+    for (inset, side) in insets.into_iter().zip(sides) {
+        if let Some(side) = side {
+            *inset = side.resolve(theme);
+        }
+    }
+}
+
+fn parse_rules(source: &str, first_order: usize) -> Result<Vec<Rule>, StylesheetError> {
+    let source = strip_comments(source);
+    let mut rules = vec![];
+    let mut rest = source.as_str();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        let offset = source.len() - rest.len();
+        let error = |message: String| StylesheetError {
+            line: line_at(&source, offset),
+            message,
+        };
+
+        let open = rest
+            .find('{')
+            .ok_or_else(|| error("expected `{` after selector".to_string()))?;
+        let close = rest
+            .find('}')
+            .ok_or_else(|| error("expected `}` to close the rule".to_string()))?;
+        if close < open {
+            return Err(error("unexpected `}`".to_string()));
+        }
+
+        let selectors = rest[..open]
+            .split(',')
+            .map(parse_selector)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(error)?;
+        let declarations = rest[open + 1..close]
+            .split(';')
+            .filter(|d| !d.trim().is_empty())
+            .map(parse_declaration)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|message| StylesheetError {
+                line: line_at(&source, offset + open),
+                message,
+            })?;
+
+        for selector in selectors {
+            let specificity = selector.compounds.iter().fold((0, 0), |(c, t), compound| {
+                (
+                    c + compound.classes.len(),
+                    t + compound.element_type.is_some() as usize,
+                )
+            });
+            rules.push(Rule {
+                selector,
+                declarations: declarations.clone(),
+                specificity,
+                order: first_order + rules.len(),
+            });
+        }
+        rest = &rest[close + 1..];
+    }
+    Ok(rules)
+}
+
+// comments are replaced with spaces (keeping new lines) so error line numbers still match the source
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find("*/")
+            .map_or(rest.len(), |e| start + e + 2);
+        out.extend(rest[start..end].chars().filter(|c| *c == '\n'));
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn line_at(source: &str, offset: usize) -> usize {
+    source[..offset].matches('\n').count() + 1
+}
+
+fn parse_selector(source: &str) -> Result<Selector, String> {
+    let compounds = source
+        .split_whitespace()
+        .map(parse_compound)
+        .collect::<Result<Vec<_>, _>>()?;
+    if compounds.is_empty() {
+        return Err("empty selector".to_string());
+    }
+    Ok(Selector { compounds })
+}
+
+fn parse_compound(source: &str) -> Result<Compound, String> {
+    let mut parts = source.split('.');
+    let element_type = match parts.next().unwrap_or("") {
+        "" | "*" => None,
+        name => Some(
+            *TYPE_NAMES
+                .iter()
+                .find(|t| **t == name)
+                .ok_or_else(|| format!("unknown element type `{name}`"))?,
+        ),
+    };
+    let classes = parts
+        .map(|class| match is_identifier(class) {
+            true => Ok(class.to_string()),
+            false => Err(format!("invalid class name `{class}` in `{source}`")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Compound {
+        element_type,
+        classes,
+    })
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn parse_declaration(source: &str) -> Result<Declaration, String> {
+    let (property, value) = source
+        .split_once(':')
+        .ok_or_else(|| format!("expected `property: value`, found `{}`", source.trim()))?;
+    let property = property.trim();
+    let value = value.trim();
+    let invalid = || format!("invalid value `{value}` for `{property}`");
+
+    let declaration = match property {
+        "padding" => Declaration::Padding(parse_sides(value).ok_or_else(invalid)?),
+        "margin" => Declaration::Margin(parse_sides(value).ok_or_else(invalid)?),
+        "padding-top" | "padding-right" | "padding-bottom" | "padding-left" | "margin-top"
+        | "margin-right" | "margin-bottom" | "margin-left" => {
+            let mut sides = [None; 4];
+            let side = match property.split_once('-').map(|(_, side)| side) {
+                Some("top") => 0,
+                Some("right") => 1,
+                Some("bottom") => 2,
+                _ => 3,
+            };
+            sides[side] = Some(parse_space(value).ok_or_else(invalid)?);
+            match property.starts_with("padding") {
+                true => Declaration::Padding(sides),
+                false => Declaration::Margin(sides),
+            }
+        }
+        "width" => Declaration::Width(parse_sizing(value).ok_or_else(invalid)?),
+        "height" => Declaration::Height(parse_sizing(value).ok_or_else(invalid)?),
+        "align-x" => Declaration::AlignX(parse_align(value).ok_or_else(invalid)?),
+        "align-y" => Declaration::AlignY(parse_align(value).ok_or_else(invalid)?),
+        "justify-x" => Declaration::JustifyX(parse_align(value).ok_or_else(invalid)?),
+        "justify-y" => Declaration::JustifyY(parse_align(value).ok_or_else(invalid)?),
+        "background-color" | "background" => {
+            Declaration::BackgroundColor(parse_color_value(value).ok_or_else(invalid)?)
+        }
+        "border-color" => Declaration::BorderColor(parse_color_value(value).ok_or_else(invalid)?),
+        "border-width" => Declaration::BorderWidth(parse_number(value).ok_or_else(invalid)?),
+        "border-radius" => {
+            let corners = parse_list(value, parse_number).ok_or_else(invalid)?;
+            let corners = match corners[..] {
+                [all] => [all; 4],
+                [top_left_bottom_right, top_right_bottom_left] => [
+                    top_left_bottom_right,
+                    top_right_bottom_left,
+                    top_left_bottom_right,
+                    top_right_bottom_left,
+                ],
+                [top_left, top_right, bottom_right, bottom_left] => {
+                    [top_left, top_right, bottom_right, bottom_left]
+                }
+                _ => return Err(invalid()),
+            };
+            Declaration::BorderRadius(corners.map(Some))
+        }
+        "border-top-left-radius"
+        | "border-top-right-radius"
+        | "border-bottom-right-radius"
+        | "border-bottom-left-radius" => {
+            let mut corners = [None; 4];
+            let corner = match property {
+                "border-top-left-radius" => 0,
+                "border-top-right-radius" => 1,
+                "border-bottom-right-radius" => 2,
+                _ => 3,
+            };
+            corners[corner] = Some(parse_number(value).ok_or_else(invalid)?);
+            Declaration::BorderRadius(corners)
+        }
+        "font-size" => match parse_variable(value) {
+            Some(name) => Declaration::TypeSize(type_token(name).ok_or_else(invalid)?),
+            None => Declaration::FontSize(parse_number(value).ok_or_else(invalid)?),
+        },
+        "line-height" => Declaration::LineHeight(parse_number(value).ok_or_else(invalid)?),
+        "font-family" => Declaration::FontFamily(parse_font_family(value).ok_or_else(invalid)?),
+        "font-weight" => Declaration::FontWeight(parse_font_weight(value).ok_or_else(invalid)?),
+        "font-style" => Declaration::FontStyle(match value {
+            "normal" => FontStyle::Normal,
+            "italic" => FontStyle::Italic,
+            "oblique" => FontStyle::Oblique,
+            _ => return Err(invalid()),
+        }),
+        "color" => Declaration::TextColor(parse_color_value(value).ok_or_else(invalid)?),
+        _ => return Err(format!("unknown property `{property}`")),
+    };
+    Ok(declaration)
+}
+
+fn parse_list<T>(value: &str, parse: impl Fn(&str) -> Option<T>) -> Option<Vec<T>> {
+    value.split_whitespace().map(parse).collect()
+}
+
+// same shorthand as css: 1 value for all sides, 2 for vertical/ horizontal, 3 for top/ horizontal/ bottom or 4 clockwise
+fn parse_sides(value: &str) -> Option<[Option<Space>; 4]> {
+    let sides = parse_list(value, parse_space)?;
+    let [top, right, bottom, left] = match sides[..] {
+        [all] => [all; 4],
+        [vertical, horizontal] => [vertical, horizontal, vertical, horizontal],
+        [top, horizontal, bottom] => [top, horizontal, bottom, horizontal],
+        [top, right, bottom, left] => [top, right, bottom, left],
+        _ => return None,
+    };
+    Some([Some(top), Some(right), Some(bottom), Some(left)])
+}
+
+// `var(--name)` references a theme token
+fn parse_variable(value: &str) -> Option<&str> {
+    value
+        .strip_prefix("var(")?
+        .strip_suffix(')')?
+        .trim()
+        .strip_prefix("--")
+}
+
+fn parse_space(value: &str) -> Option<Space> {
+    match parse_variable(value) {
+        Some(name) => {
+            let token = match name {
+                "space-xs" => SpaceToken::Xs,
+                "space-sm" => SpaceToken::Sm,
+                "space-md" => SpaceToken::Md,
+                "space-lg" => SpaceToken::Lg,
+                "space-xl" => SpaceToken::Xl,
+                _ => return None,
+            };
+            Some(Space::Token(token))
+        }
+        None => parse_number(value).map(|n| Space::Px(n as u32)),
+    }
+}
+
+fn type_token(name: &str) -> Option<TypeToken> {
+    let token = match name {
+        "text-caption" => TypeToken::Caption,
+        "text-body" => TypeToken::Body,
+        "text-title" => TypeToken::Title,
+        "text-headline" => TypeToken::Headline,
+        "text-display" => TypeToken::Display,
+        _ => return None,
+    };
+    Some(token)
+}
+
+fn parse_color_value(value: &str) -> Option<ColorValue> {
+    match parse_variable(value) {
+        Some(name) => {
+            let token = match name {
+                "surface" => ColorToken::Surface,
+                "on-surface" => ColorToken::OnSurface,
+                "accent" => ColorToken::Accent,
+                "warning" => ColorToken::Warning,
+                "danger" => ColorToken::Danger,
+                "muted" => ColorToken::Muted,
+                _ => return None,
+            };
+            Some(ColorValue::Token(token))
+        }
+        None => Color::parse(value).ok().map(ColorValue::Color), // any css colour, see `Color::parse`
+    }
+}
+
+// plain numbers are pixels, the `px` suffix is optional
+fn parse_number(value: &str) -> Option<f32> {
+    let number = value.strip_suffix("px").unwrap_or(value);
+    number
+        .parse::<f32>()
+        .ok()
+        .filter(|n| n.is_finite() && *n >= 0.0)
+}
+
+fn parse_sizing(value: &str) -> Option<SizingPolicy> {
+    match value {
+        "auto" => Some(SizingPolicy::Auto),
+        "fill" | "fill-parent" | "100%" => Some(SizingPolicy::FillParent),
+        _ => parse_number(value).map(|n| SizingPolicy::Fixed(n as u32)),
+    }
+}
+
+fn parse_align(value: &str) -> Option<Align> {
+    match value {
+        "start" => Some(Align::Start),
+        "middle" | "center" => Some(Align::Middle),
+        "end" => Some(Align::End),
+        _ => None,
+    }
+}
+
+fn parse_font_family(value: &str) -> Option<FontFamily> {
+    let family = match value {
+        "serif" => FontFamily::Serif,
+        "sans-serif" => FontFamily::SansSerif,
+        "cursive" => FontFamily::Cursive,
+        "fantasy" => FontFamily::Fantasy,
+        "monospace" => FontFamily::Monospace,
+        _ => {
+            let name = value.trim_matches(|c| c == '"' || c == '\'');
+            if name.is_empty() {
+                return None;
+            }
+            FontFamily::Name(name.to_string())
+        }
+    };
+    Some(family)
+}
+
+fn parse_font_weight(value: &str) -> Option<FontWeight> {
+    let weight = match value {
+        "thin" | "100" => FontWeight::Thin,
+        "extra-light" | "200" => FontWeight::ExtraLight,
+        "light" | "300" => FontWeight::Light,
+        "normal" | "400" => FontWeight::Normal,
+        "medium" | "500" => FontWeight::Medium,
+        "semi-bold" | "600" => FontWeight::SemiBold,
+        "bold" | "700" => FontWeight::Bold,
+        "extra-bold" | "800" => FontWeight::ExtraBold,
+        "black" | "900" => FontWeight::Black,
+        _ => return None,
+    };
+    Some(weight)
+}
@@ -0,0 +1,512 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use uuid::Uuid;
+
+use crate::{
+    primitives::{
+        arc::Arc,
+        cell_grid::CellGrid,
+        color::Color,
+        image::Image,
+        path::{FillRule, Path, PathCommand},
+        polyline::{LineCap, LineJoin, Polyline, Stroke},
+        rectangle::Rectangle,
+        text::Text,
+    },
+    render::RenderList,
+    style::{FontFamily, FontStyle, FontWeight, Style},
+};
+
+// encoded image data to embed in the svg, images without data are linked by their texture id instead
+#[derive(Clone, Debug)]
+pub struct SvgImage {
+    pub mime_type: String, // e.g. "image/png"
+    pub data: Vec<u8>,
+}
+
+impl RenderList {
+    // standalone svg document of the render list, drawn in the same order as the renderers (rectangles, cell grids,
+    // polylines, paths, arcs, images, text)
+    // the output only depends on the render list, so it can also be used for golden tests (see tests/svg.rs)
+    pub fn to_svg(&self, size: [u32; 2], images: &HashMap<Uuid, SvgImage>) -> String {
+        let mut svg = String::new();
+        self.write_svg(&mut svg, size, images)
+            .expect("writing to a string can't fail");
+        svg
+    }
+
+    pub fn write_svg(
+        &self,
+        out: &mut impl fmt::Write,
+        size: [u32; 2],
+        images: &HashMap<Uuid, SvgImage>,
+    ) -> fmt::Result {
+        let [w, h] = size;
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#
+        )?;
+        for rect in &self.rectangles {
+            write_rectangle(out, rect)?;
+        }
+        for grid in &self.cell_grids {
+            write_cell_grid(out, grid)?;
+        }
+        for polyline in &self.polylines {
+            write_polyline(out, polyline)?;
+        }
+        for path in &self.paths {
+            write_path(out, path)?;
+        }
+        for arc in &self.arcs {
+            write_arc(out, arc)?;
+        }
+        for (i, image) in self.images.iter().enumerate() {
+            write_image(out, image, i, images.get(&image.texture_id))?;
+        }
+        for (i, text) in self.text.iter().enumerate() {
+            write_text(out, text, i)?;
+        }
+        writeln!(out, "</svg>")
+    }
+}
+
+fn write_rectangle(out: &mut impl fmt::Write, rect: &Rectangle) -> fmt::Result {
+    if rect.size[0] == 0 || rect.size[1] == 0 {
+        return Ok(());
+    }
+    let border_width = border_width(rect);
+    let inner = inset(rect, border_width);
+    if rect.style.background_color.a > 0 {
+        write!(out, r#"  <path d=""#)?;
+        write_rounded_path(out, inner.0, inner.1, inner.2)?;
+        writeln!(out, r#"" {}/>"#, Paint("fill", rect.style.background_color))?;
+    }
+    write_border(out, rect, border_width, inner)
+}
+
+// a rectangle for each cell, empty cells (transparent colours) are left out
+fn write_cell_grid(out: &mut impl fmt::Write, grid: &CellGrid) -> fmt::Result {
+    for row in 0..grid.rows {
+        for column in 0..grid.columns {
+            let (position, size) = grid.cell_bounds(row, column);
+            let cell = Rectangle {
+                position,
+                size,
+                style: Style {
+                    background_color: grid.color(row, column),
+                    border_radius: [grid.cell_radius; 4],
+                    ..Default::default()
+                },
+            };
+            write_rectangle(out, &cell)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_polyline(out: &mut impl fmt::Write, polyline: &Polyline) -> fmt::Result {
+    let (Some(first), Some(last)) = (polyline.points.first(), polyline.points.last()) else {
+        return Ok(());
+    };
+    if let Some(area) = &polyline.area {
+        if area.color.a > 0 {
+            write!(out, r#"  <polygon points="{},{}"#, first[0], area.baseline)?;
+            for [x, y] in &polyline.points {
+                write!(out, " {x},{y}")?;
+            }
+            writeln!(
+                out,
+                r#" {},{}" {}/>"#,
+                last[0],
+                area.baseline,
+                Paint("fill", area.color)
+            )?;
+        }
+    }
+    let stroke = &polyline.stroke;
+    if stroke.width <= 0.0 || stroke.color.a == 0 {
+        return Ok(());
+    }
+    write!(out, r#"  <polyline points=""#)?;
+    for (i, [x, y]) in polyline.points.iter().enumerate() {
+        if i > 0 {
+            write!(out, " ")?;
+        }
+        write!(out, "{x},{y}")?;
+    }
+    write!(out, r#"" fill="none""#)?;
+    write_stroke(out, stroke)?;
+    writeln!(out, "/>")
+}
+
+fn write_path(out: &mut impl fmt::Write, path: &Path) -> fmt::Result {
+    let fill = path.fill.filter(|fill| fill.a > 0);
+    let stroke = path
+        .stroke
+        .filter(|stroke| stroke.width > 0.0 && stroke.color.a > 0);
+    if path.commands.is_empty() || (fill.is_none() && stroke.is_none()) {
+        return Ok(());
+    }
+    write!(out, r#"  <path d=""#)?;
+    for (i, command) in path.commands.iter().enumerate() {
+        if i > 0 {
+            write!(out, " ")?;
+        }
+        match *command {
+            PathCommand::MoveTo([x, y]) => write!(out, "M{x},{y}")?,
+            PathCommand::LineTo([x, y]) => write!(out, "L{x},{y}")?,
+            PathCommand::QuadTo {
+                control: [cx, cy],
+                to: [x, y],
+            } => write!(out, "Q{cx},{cy} {x},{y}")?,
+            PathCommand::CubicTo {
+                control_1: [c1x, c1y],
+                control_2: [c2x, c2y],
+                to: [x, y],
+            } => write!(out, "C{c1x},{c1y} {c2x},{c2y} {x},{y}")?,
+            PathCommand::ArcTo {
+                radii: [rx, ry],
+                x_rotation,
+                large_arc,
+                sweep,
+                to: [x, y],
+            } => write!(
+                out,
+                "A{rx},{ry} {x_rotation} {} {} {x},{y}",
+                large_arc as u8, sweep as u8
+            )?,
+            PathCommand::Close => write!(out, "Z")?,
+        }
+    }
+    write!(out, "\"")?;
+    match fill {
+        Some(fill) => {
+            let rule = match path.fill_rule {
+                FillRule::NonZero => "nonzero",
+                FillRule::EvenOdd => "evenodd",
+            };
+            write!(out, r#" fill-rule="{rule}" {}"#, Paint("fill", fill))?;
+        }
+        None => write!(out, r#" fill="none""#)?,
+    }
+    if let Some(stroke) = &stroke {
+        write_stroke(out, stroke)?;
+    }
+    writeln!(out, "/>")
+}
+
+// a stroked circle or circular arc along the middle of the ring
+fn write_arc(out: &mut impl fmt::Write, arc: &Arc) -> fmt::Result {
+    if arc.thickness <= 0.0 || arc.color.a == 0 {
+        return Ok(());
+    }
+    let [cx, cy] = arc.center;
+    let half_sweep = arc.half_sweep();
+    if half_sweep >= std::f32::consts::PI {
+        write!(
+            out,
+            r#"  <circle cx="{cx}" cy="{cy}" r="{}" fill="none""#,
+            arc.radius
+        )?;
+    } else {
+        let [x0, y0] = arc.point_at(arc.start_angle);
+        let end_angle = arc.start_angle + half_sweep.to_degrees() * 2.0;
+        let [x1, y1] = arc.point_at(end_angle);
+        let cap = match arc.rounded_caps {
+            true => "round",
+            false => "butt",
+        };
+        match half_sweep > 0.0 {
+            true => {
+                let large_arc = (half_sweep > std::f32::consts::FRAC_PI_2) as u8;
+                let r = arc.radius;
+                write!(
+                    out,
+                    r#"  <path d="M{x0},{y0} A{r},{r} 0 {large_arc} 1 {x1},{y1}""#
+                )?;
+            }
+            // a round cap on its own, like a dot
+            false => write!(out, r#"  <path d="M{x0},{y0} L{x1},{y1}""#)?,
+        }
+        write!(out, r#" fill="none" stroke-linecap="{cap}""#)?;
+    }
+    writeln!(
+        out,
+        r#" stroke-width="{}" {}/>"#,
+        arc.thickness,
+        Paint("stroke", arc.color)
+    )
+}
+
+fn write_stroke(out: &mut impl fmt::Write, stroke: &Stroke) -> fmt::Result {
+    let join = match stroke.join {
+        LineJoin::Miter => "miter",
+        LineJoin::Bevel => "bevel",
+        LineJoin::Round => "round",
+    };
+    let cap = match stroke.cap {
+        LineCap::Butt => "butt",
+        LineCap::Square => "square",
+        LineCap::Round => "round",
+    };
+    write!(
+        out,
+        r#" stroke-width="{}" stroke-linejoin="{join}" stroke-miterlimit="{}" stroke-linecap="{cap}""#,
+        stroke.width,
+        Stroke::MITER_LIMIT,
+    )?;
+    if let Some([dash, gap]) = stroke.dash {
+        write!(out, r#" stroke-dasharray="{dash} {gap}""#)?;
+    }
+    write!(out, " {}", Paint("stroke", stroke.color))
+}
+
+fn write_image(
+    out: &mut impl fmt::Write,
+    image: &Image,
+    index: usize,
+    data: Option<&SvgImage>,
+) -> fmt::Result {
+    let rect = &image.rectangle;
+    if rect.size[0] == 0 || rect.size[1] == 0 {
+        return Ok(());
+    }
+    let border_width = border_width(rect);
+    let inner = inset(rect, border_width);
+
+    // the image is clipped to the inside of the border (same as the fill of a rectangle)
+    let clip_id = format!("anchor-kit-image-clip-{index}");
+    write!(out, r#"  <clipPath id="{clip_id}"><path d=""#)?;
+    write_rounded_path(out, inner.0, inner.1, inner.2)?;
+    writeln!(out, r#""/></clipPath>"#)?;
+
+    let [x, y] = rect.position;
+    let [w, h] = rect.size;
+    write!(
+        out,
+        r#"  <image x="{x}" y="{y}" width="{w}" height="{h}" preserveAspectRatio="none" clip-path="url(#{clip_id})""#
+    )?;
+    if image.opacity < 1.0 {
+        write!(out, r#" opacity="{}""#, image.opacity.max(0.0))?;
+    }
+    match data {
+        Some(data) => {
+            write!(out, r#" href="data:{};base64,"#, Escaped(&data.mime_type))?;
+            write_base64(out, &data.data)?;
+            writeln!(out, r#""/>"#)?;
+        }
+        None => writeln!(out, r#" href="{}"/>"#, image.texture_id)?,
+    }
+
+    write_border(out, rect, border_width, inner)
+}
+
+// each line is a `tspan` one line height below the last, svg doesn't break lines at newlines. the text is clipped to
+// its bounds like in the renderers
+fn write_text(out: &mut impl fmt::Write, text: &Text, index: usize) -> fmt::Result {
+    let [w, h] = text.size;
+    if w == 0 || h == 0 || text.text.trim().is_empty() {
+        return Ok(());
+    }
+    let text_style = &text.text_style;
+    let [x, y] = text.position;
+    let clip_id = format!("anchor-kit-text-clip-{index}");
+    writeln!(
+        out,
+        r#"  <clipPath id="{clip_id}"><rect x="{x}" y="{y}" width="{w}" height="{h}"/></clipPath>"#
+    )?;
+    write!(
+        out,
+        r#"  <text dominant-baseline="central" font-family="{}" font-size="{}" font-weight="{}""#,
+        FontFamilyName(&text_style.font_family),
+        text_style.font_size,
+        font_weight_value(&text_style.font_weight),
+    )?;
+    match text_style.font_style {
+        FontStyle::Normal => {}
+        FontStyle::Italic => write!(out, r#" font-style="italic""#)?,
+        FontStyle::Oblique => write!(out, r#" font-style="oblique""#)?,
+    }
+    write!(
+        out,
+        r#" {} clip-path="url(#{clip_id})" xml:space="preserve">"#,
+        Paint("fill", text_style.text_color),
+    )?;
+    let line_x = x as f32 + text.offset[0];
+    for (i, line) in text.text.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        // renderers center each line in its line height
+        let line_y = y as f32 + text.offset[1] + text_style.line_height * (i as f32 + 0.5);
+        write!(
+            out,
+            r#"<tspan x="{line_x}" y="{line_y}">{}</tspan>"#,
+            Escaped(line)
+        )?;
+    }
+    writeln!(out, "</text>")
+}
+
+// borders are drawn inside the rectangle (like the shader), as the area between the outer and inner shape
+fn write_border(
+    out: &mut impl fmt::Write,
+    rect: &Rectangle,
+    border_width: f32,
+    inner: ([f32; 2], [f32; 2], [f32; 4]),
+) -> fmt::Result {
+    if border_width <= 0.0 || rect.style.border_color.a == 0 {
+        return Ok(());
+    }
+    let outer = inset(rect, 0.0);
+    write!(out, r#"  <path d=""#)?;
+    write_rounded_path(out, outer.0, outer.1, outer.2)?;
+    write!(out, " ")?;
+    write_rounded_path(out, inner.0, inner.1, inner.2)?;
+    writeln!(
+        out,
+        r#"" fill-rule="evenodd" {}/>"#,
+        Paint("fill", rect.style.border_color)
+    )
+}
+
+fn border_width(rect: &Rectangle) -> f32 {
+    let [w, h] = rect.size;
+    rect.style.border_width.clamp(0.0, w.min(h) as f32 / 2.0)
+}
+
+// position, size and corner radii of the rectangle shrunk by the given amount on all sides
+fn inset(rect: &Rectangle, amount: f32) -> ([f32; 2], [f32; 2], [f32; 4]) {
+    let [x, y] = rect.position;
+    let [w, h] = rect.size;
+    let max_radius = w.min(h) as f32 / 2.0; // same limit as the renderers to avoid impossible rounded corners
+    let mut radius = rect.style.border_radius;
+    for r in radius.iter_mut() {
+        *r = (r.min(max_radius) - amount).max(0.0);
+    }
+    (
+        [x as f32 + amount, y as f32 + amount],
+        [w as f32 - amount * 2.0, h as f32 - amount * 2.0],
+        radius,
+    )
+}
+
+// radii are top-left, top-right, bottom-right, bottom-left (clockwise)
+fn write_rounded_path(
+    out: &mut impl fmt::Write,
+    position: [f32; 2],
+    size: [f32; 2],
+    radius: [f32; 4],
+) -> fmt::Result {
+    let [x0, y0] = position;
+    let [x1, y1] = [x0 + size[0], y0 + size[1]];
+    let [tl, tr, br, bl] = radius;
+    write!(out, "M{} {}H{}", x0 + tl, y0, x1 - tr)?;
+    if tr > 0.0 {
+        write!(out, "A{tr} {tr} 0 0 1 {x1} {}", y0 + tr)?;
+    }
+    write!(out, "V{}", y1 - br)?;
+    if br > 0.0 {
+        write!(out, "A{br} {br} 0 0 1 {} {y1}", x1 - br)?;
+    }
+    write!(out, "H{}", x0 + bl)?;
+    if bl > 0.0 {
+        write!(out, "A{bl} {bl} 0 0 1 {x0} {}", y1 - bl)?;
+    }
+    write!(out, "V{}", y0 + tl)?;
+    if tl > 0.0 {
+        write!(out, "A{tl} {tl} 0 0 1 {} {y0}", x0 + tl)?;
+    }
+    write!(out, "Z")
+}
+
+// writes a colour attribute, with the alpha as a seperate opacity attribute if it isn't opaque
+struct Paint(&'static str, Color);
+
+impl fmt::Display for Paint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Paint(attribute, color) = self;
+        write!(
+            f,
+            r##"{attribute}="#{:02x}{:02x}{:02x}""##,
+            color.r, color.g, color.b
+        )?;
+        if color.a < 255 {
+            write!(f, r#" {attribute}-opacity="{}""#, color.a as f32 / 255.0)?;
+        }
+        Ok(())
+    }
+}
+
+struct FontFamilyName<'a>(&'a FontFamily);
+
+impl fmt::Display for FontFamilyName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            FontFamily::Name(name) => write!(f, "'{}'", Escaped(name)),
+            FontFamily::Serif => write!(f, "serif"),
+            FontFamily::SansSerif => write!(f, "sans-serif"),
+            FontFamily::Cursive => write!(f, "cursive"),
+            FontFamily::Fantasy => write!(f, "fantasy"),
+            FontFamily::Monospace => write!(f, "monospace"),
+        }
+    }
+}
+
+fn font_weight_value(font_weight: &FontWeight) -> u16 {
+    match font_weight {
+        FontWeight::Thin => 100,
+        FontWeight::ExtraLight => 200,
+        FontWeight::Light => 300,
+        FontWeight::Normal => 400,
+        FontWeight::Medium => 500,
+        FontWeight::SemiBold => 600,
+        FontWeight::Bold => 700,
+        FontWeight::ExtraBold => 800,
+        FontWeight::Black => 900,
+    }
+}
+
+// escapes text for use in xml content and attributes
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&apos;")?,
+                _ => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_base64(out: &mut impl fmt::Write, data: &[u8]) -> fmt::Result {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let sextet = (n >> (18 - i * 6)) & 0x3f;
+                out.write_char(ALPHABET[sextet as usize] as char)?;
+            } else {
+                out.write_char('=')?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,197 @@
+use crate::{
+    element::{ElementTree, Table},
+    measure::{measure_text, TextMeasurement},
+    style::{FontWeight, SizingPolicy, TextStyle},
+};
+
+// shared by the measure and render passes, so the space measured for every row and column matches where it is drawn
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TableMetrics {
+    pub(crate) padding: [u32; 2], // x, y on each side of every cell
+    pub(crate) row_height: u32,   // one line of text plus the padding, without the divider below it
+    pub(crate) row_count: u32,    // including the header
+}
+
+impl TableMetrics {
+    pub(crate) fn new(
+        table: &Table,
+        text_style: &TextStyle,
+        text_measurement: TextMeasurement,
+    ) -> Self {
+        let padding = table.cell_padding.unwrap_or(match text_measurement {
+            TextMeasurement::FontSize => [6, 3],
+            TextMeasurement::Cells => [1, 0],
+        });
+        let line_height = match text_measurement {
+            TextMeasurement::FontSize => text_style.line_height as u32,
+            TextMeasurement::Cells => 1,
+        };
+        Self {
+            padding,
+            row_height: line_height.saturating_add(padding[1].saturating_mul(2)),
+            row_count: table.rows + table.header as u32,
+        }
+    }
+
+    // rows and the dividers between them, as tall as possible if that doesn't fit in a u32
+    pub(crate) fn height(&self, table: &Table) -> u32 {
+        let rows = self.row_count;
+        match rows {
+            0 => 0,
+            _ => rows
+                .checked_mul(self.row_height)
+                .zip((rows - 1).checked_mul(table.divider_thickness))
+                .and_then(|(rows, dividers)| rows.checked_add(dividers))
+                .unwrap_or(u32::MAX),
+        }
+    }
+}
+
+// the header row is drawn with this style, so it is measured with it too
+pub(crate) fn table_header_style(table: &Table, text_style: &TextStyle) -> TextStyle {
+    let mut header_style = text_style.clone();
+    if table.bold_header {
+        header_style.font_weight = FontWeight::Bold;
+    }
+    if let Some(color) = table.header_color {
+        header_style.text_color = color;
+    }
+    header_style
+}
+
+// every cell with its row and column, the header row first. the builder pads rows that were given fewer cells than
+// there are columns, so the cells can be found by their position alone
+pub(crate) fn table_cells<'a>(
+    cells: &'a str,
+    table: &Table,
+) -> impl Iterator<Item = (u32, usize, &'a str)> {
+    let columns = table.columns.end - table.columns.start;
+    let count = (table.rows + table.header as u32) as usize * columns;
+    cells
+        .split('\n')
+        .take(count)
+        .enumerate()
+        .map(move |(i, cell)| ((i / columns) as u32, i % columns, cell))
+}
+
+// measures the width of every column including the cell padding into the tree's table width buffer, reusing the
+// table's part of it if the table is measured again, and returns the width of all of them together
+pub(crate) fn measure_table_column_widths(
+    tree: &mut ElementTree,
+    index: usize,
+    table: &mut Table,
+) -> u32 {
+    let column_count = table.columns.end - table.columns.start;
+    if table.widths.end - table.widths.start != column_count {
+        table.widths = tree.push_table_column_widths((0..column_count).map(|_| 0));
+    }
+    // the text and columns are read while the widths are written, so the tree's buffers are borrowed separately
+    let ElementTree {
+        elements,
+        text,
+        table_columns,
+        table_column_widths,
+        text_measurement,
+        ..
+    } = tree;
+    let default_text_style = TextStyle::default();
+    let text_style = elements[index]
+        .text_style
+        .as_ref()
+        .unwrap_or(&default_text_style);
+    let header_style = table.header.then(|| table_header_style(table, text_style));
+    let padding = TableMetrics::new(table, text_style, *text_measurement).padding[0] * 2;
+    let columns = &table_columns[table.columns.start..table.columns.end];
+    let widths = &mut table_column_widths[table.widths.start..table.widths.end];
+    for (width, column) in widths.iter_mut().zip(columns) {
+        *width = match column.width {
+            SizingPolicy::Fixed(width) => width,
+            _ => padding,
+        };
+    }
+    let cells = &text[table.cells.start..table.cells.end];
+    for (row, column, cell) in table_cells(cells, table) {
+        if !matches!(columns[column].width, SizingPolicy::Fixed(_)) {
+            let cell_style = match (row, &header_style) {
+                (0, Some(header_style)) => header_style,
+                _ => text_style,
+            };
+            let cell_width = measure_text(cell, cell_style, *text_measurement)[0];
+            widths[column] = widths[column].max(cell_width + padding);
+        }
+    }
+    widths.iter().sum()
+}
+
+// fits the measured widths to `available`: fill parent columns share any space left over, and when there isn't
+// enough the widest auto and fill parent columns are narrowed first
+pub(crate) fn fit_table_column_widths(
+    tree: &mut ElementTree,
+    table: &Table,
+    padding_x: u32, // on each side of every cell
+    available: u32,
+) {
+    let columns = &tree.table_columns[table.columns.start..table.columns.end];
+    let widths = &mut tree.table_column_widths[table.widths.start..table.widths.end];
+    let padding = padding_x * 2;
+    let total: u32 = widths.iter().sum();
+    let flexible = |i: usize| !matches!(columns[i].width, SizingPolicy::Fixed(_));
+    if total < available {
+        let fill_count = columns
+            .iter()
+            .filter(|column| column.width == SizingPolicy::FillParent)
+            .count() as u32;
+        let extra = available - total;
+        if let Some(share) = extra.checked_div(fill_count) {
+            let fill_widths = widths
+                .iter_mut()
+                .zip(columns)
+                .filter(|(_, column)| column.width == SizingPolicy::FillParent);
+            for (n, (width, _)) in fill_widths.enumerate() {
+                // the remainder goes to the first columns so the table ends exactly at the edge
+                *width += share + ((n as u32) < extra % fill_count) as u32;
+            }
+        }
+    } else if total > available {
+        // the largest cap on the flexible columns that fits, found by bisection since there are only a few columns
+        let fixed: u32 = (0..widths.len())
+            .filter(|i| !flexible(*i))
+            .map(|i| widths[i])
+            .sum();
+        let space = available.saturating_sub(fixed);
+        let capped = |widths: &[u32], cap: u32| -> u32 {
+            (0..widths.len())
+                .filter(|i| flexible(*i))
+                .map(|i| widths[i].min(cap.max(padding)))
+                .sum()
+        };
+        let (mut low, mut high) = (padding, widths.iter().copied().max().unwrap_or(0));
+        while low < high {
+            let cap = (low + high).div_ceil(2);
+            match capped(widths, cap) <= space {
+                true => low = cap,
+                false => high = cap - 1,
+            }
+        }
+        // what's left is less than one pixel for each capped column, it goes to the first ones
+        let mut spare = space.saturating_sub(capped(widths, low));
+        for (i, width) in widths.iter_mut().enumerate() {
+            if flexible(i) && *width > low {
+                *width = low + (spare > 0) as u32;
+                spare = spare.saturating_sub(1);
+            }
+        }
+    }
+}
+
+// columns are right aligned by default when every cell that isn't empty starts with a number,
+// e.g. "12", "-3.5", "1,024", "98%" or "16.6 ms"
+pub(crate) fn is_numeric(cell: &str) -> bool {
+    let number = cell.split_whitespace().next().unwrap_or("");
+    let number = number.trim_end_matches(|c: char| c.is_alphabetic() || c == '%' || c == '/');
+    number.chars().any(|c| c.is_ascii_digit())
+        && number
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | ','))
+}
@@ -0,0 +1,232 @@
+use crate::{
+    primitives::color::Color,
+    style::{Insets, PartialTextStyle, TextStyle},
+};
+
+// semantic colours, spacing and text sizes that styles refer to instead of raw values, so the whole ui can switch
+// themes at runtime with `UIState::set_theme`. tokens are resolved while the frame is built, either in the builder
+// closures (`ui.theme().color(ColorToken::Accent)`) or in stylesheets (`background-color: var(--accent)`)
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Theme {
+    pub surface: Color,    // backgrounds of cards, pills, panels
+    pub on_surface: Color, // text and icons on top of surface
+    pub accent: Color,
+    pub warning: Color,
+    pub danger: Color,
+    pub muted: Color, // secondary text, dividers, disabled elements
+    pub spacing: SpacingScale,
+    pub type_scale: TypeScale,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorToken {
+    Surface,
+    OnSurface,
+    Accent,
+    Warning,
+    Danger,
+    Muted,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpaceToken {
+    Xs,
+    Sm,
+    Md,
+    Lg,
+    Xl,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TypeToken {
+    Caption,
+    Body,
+    Title,
+    Headline,
+    Display,
+}
+
+// pixels, smallest to largest
+#[derive(Clone, Copy, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpacingScale {
+    pub xs: u32,
+    pub sm: u32,
+    pub md: u32,
+    pub lg: u32,
+    pub xl: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeScale {
+    pub caption: TypeSize,
+    pub body: TypeSize,
+    pub title: TypeSize,
+    pub headline: TypeSize,
+    pub display: TypeSize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeSize {
+    pub font_size: f32,
+    pub line_height: f32,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    // light text on dark translucent surfaces, for overlays on top of video
+    pub fn dark() -> Self {
+        Self {
+            surface: Color::rgba(18, 20, 28, 220),
+            on_surface: Color::rgba(236, 238, 244, 255),
+            accent: Color::rgba(64, 156, 255, 255),
+            warning: Color::rgba(255, 184, 48, 255),
+            danger: Color::rgba(240, 72, 72, 255),
+            muted: Color::rgba(140, 146, 160, 255),
+            spacing: SpacingScale::default(),
+            type_scale: TypeScale::default(),
+        }
+    }
+
+    // black on white with saturated accents, for bright screens and accessibility
+    pub fn high_contrast_light() -> Self {
+        Self {
+            surface: Color::rgba(255, 255, 255, 255),
+            on_surface: Color::rgba(0, 0, 0, 255),
+            accent: Color::rgba(0, 64, 200, 255),
+            warning: Color::rgba(150, 80, 0, 255),
+            danger: Color::rgba(180, 0, 0, 255),
+            muted: Color::rgba(70, 70, 70, 255),
+            spacing: SpacingScale::default(),
+            type_scale: TypeScale::default(),
+        }
+    }
+
+    pub fn color(&self, token: ColorToken) -> Color {
+        match token {
+            ColorToken::Surface => self.surface,
+            ColorToken::OnSurface => self.on_surface,
+            ColorToken::Accent => self.accent,
+            ColorToken::Warning => self.warning,
+            ColorToken::Danger => self.danger,
+            ColorToken::Muted => self.muted,
+        }
+    }
+
+    // default colours for the series of a chart: the accent first, then its hue rotated by the golden angle so any
+    // number of series stay distinct while keeping the accent's lightness and chroma
+    pub fn series_color(&self, index: usize) -> Color {
+        let mut oklch = self.accent.to_oklch();
+        oklch.h = (oklch.h + index as f32 * 137.5).rem_euclid(360.0);
+        Color::from_oklch(oklch)
+    }
+
+    pub fn space(&self, token: SpaceToken) -> u32 {
+        match token {
+            SpaceToken::Xs => self.spacing.xs,
+            SpaceToken::Sm => self.spacing.sm,
+            SpaceToken::Md => self.spacing.md,
+            SpaceToken::Lg => self.spacing.lg,
+            SpaceToken::Xl => self.spacing.xl,
+        }
+    }
+
+    // the same spacing on all sides, e.g. `padding: ui.theme().insets(SpaceToken::Md)`
+    pub fn insets(&self, token: SpaceToken) -> Insets {
+        let space = self.space(token);
+        Insets {
+            top: space,
+            right: space,
+            bottom: space,
+            left: space,
+        }
+    }
+
+    pub fn type_size(&self, token: TypeToken) -> TypeSize {
+        match token {
+            TypeToken::Caption => self.type_scale.caption,
+            TypeToken::Body => self.type_scale.body,
+            TypeToken::Title => self.type_scale.title,
+            TypeToken::Headline => self.type_scale.headline,
+            TypeToken::Display => self.type_scale.display,
+        }
+    }
+
+    // full text style from the type scale and a colour token
+    pub fn text_style(&self, size: TypeToken, color: ColorToken) -> TextStyle {
+        let TypeSize {
+            font_size,
+            line_height,
+        } = self.type_size(size);
+        TextStyle {
+            font_size,
+            line_height,
+            text_color: self.color(color),
+            ..Default::default()
+        }
+    }
+
+    // for `ui.text_style(...)`, so containers can set the size and colour of the text inside them
+    pub fn partial_text_style(&self, size: TypeToken, color: ColorToken) -> PartialTextStyle {
+        let TypeSize {
+            font_size,
+            line_height,
+        } = self.type_size(size);
+        PartialTextStyle {
+            font_size: Some(font_size),
+            line_height: Some(line_height),
+            text_color: Some(self.color(color)),
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for SpacingScale {
+    fn default() -> Self {
+        Self {
+            xs: 2,
+            sm: 4,
+            md: 8,
+            lg: 16,
+            xl: 32,
+        }
+    }
+}
+
+impl Default for TypeScale {
+    fn default() -> Self {
+        Self {
+            caption: TypeSize {
+                font_size: 12.0,
+                line_height: 16.0,
+            },
+            body: TypeSize {
+                font_size: 16.0,
+                line_height: 20.0,
+            },
+            title: TypeSize {
+                font_size: 20.0,
+                line_height: 26.0,
+            },
+            headline: TypeSize {
+                font_size: 28.0,
+                line_height: 34.0,
+            },
+            display: TypeSize {
+                font_size: 40.0,
+                line_height: 48.0,
+            },
+        }
+    }
+}
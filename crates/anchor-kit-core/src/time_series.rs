@@ -0,0 +1,304 @@
+use std::cell::Cell;
+
+// the most recent samples of a metric (e.g. frame times or cpu load) with their times in seconds, for charts that
+// update every frame: `ui.line_chart(&[LineSeries::from_time_series("cpu", &cpu)], None, None)` or
+// `ui.sparkline(cpu.values(), None, None)`. pushing past the capacity drops the oldest sample, nothing allocates
+// after `new`
+#[derive(Clone, Debug)]
+pub struct TimeSeries {
+    // each sample is written at its slot and again one capacity later, so the samples are always one contiguous slice
+    times: Vec<f64>,
+    values: Vec<f32>,
+    capacity: usize,
+    start: usize, // slot of the oldest sample
+    len: usize,
+    // running totals of the finite values, min and max are recomputed when one of them is dropped
+    finite_count: usize,
+    sum: f64,
+    sum_squares: f64,
+    extremes: Cell<Option<[f32; 2]>>, // None when it needs recomputing
+}
+
+// summary of the finite values of a series, non finite values (gaps) are left out
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SeriesStats {
+    pub count: usize,
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    pub std_dev: f32, // population standard deviation
+}
+
+impl TimeSeries {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            times: vec![0.0; capacity * 2],
+            values: vec![0.0; capacity * 2],
+            capacity,
+            start: 0,
+            len: 0,
+            finite_count: 0,
+            sum: 0.0,
+            sum_squares: 0.0,
+            extremes: Cell::new(None),
+        }
+    }
+
+    // times should not go backwards, the window queries and charts expect the samples in time order
+    pub fn push(&mut self, time: f64, value: f32) {
+        if self.len == self.capacity {
+            let dropped = self.values[self.start];
+            if dropped.is_finite() {
+                self.finite_count -= 1;
+                self.sum -= dropped as f64;
+                self.sum_squares -= dropped as f64 * dropped as f64;
+                if let Some([min, max]) = self.extremes.get() {
+                    if dropped <= min || dropped >= max {
+                        self.extremes.set(None);
+                    }
+                }
+            }
+            self.start = (self.start + 1) % self.capacity;
+            self.len -= 1;
+            // start over from the samples once per lap, so rounding errors in the sums don't build up
+            if self.start == 0 {
+                self.recompute_sums();
+            }
+        }
+
+        let slot = (self.start + self.len) % self.capacity;
+        for index in [slot, slot + self.capacity] {
+            self.times[index] = time;
+            self.values[index] = value;
+        }
+        self.len += 1;
+
+        if value.is_finite() {
+            self.finite_count += 1;
+            self.sum += value as f64;
+            self.sum_squares += value as f64 * value as f64;
+            match (self.extremes.get(), self.finite_count) {
+                (_, 1) => self.extremes.set(Some([value, value])),
+                (Some([min, max]), _) => self.extremes.set(Some([min.min(value), max.max(value)])),
+                (None, _) => {}
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.len = 0;
+        self.finite_count = 0;
+        self.sum = 0.0;
+        self.sum_squares = 0.0;
+        self.extremes.set(None);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // oldest first
+    pub fn times(&self) -> &[f64] {
+        &self.times[self.start..self.start + self.len]
+    }
+
+    pub fn values(&self) -> &[f32] {
+        &self.values[self.start..self.start + self.len]
+    }
+
+    pub fn last(&self) -> Option<(f64, f32)> {
+        let times = self.times();
+        times
+            .last()
+            .map(|time| (*time, self.values()[times.len() - 1]))
+    }
+
+    // the times and values of the samples from `start` to `end` (inclusive)
+    pub fn window(&self, start: f64, end: f64) -> (&[f64], &[f32]) {
+        let times = self.times();
+        let first = times.partition_point(|t| *t < start);
+        let last = times.partition_point(|t| *t <= end).max(first);
+        (&times[first..last], &self.values()[first..last])
+    }
+
+    // the samples from `duration` seconds before the newest one, e.g. the last minute of a longer history
+    pub fn latest(&self, duration: f64) -> (&[f64], &[f32]) {
+        match self.last() {
+            Some((time, _)) => self.window(time - duration, time),
+            None => (&[], &[]),
+        }
+    }
+
+    // stats of every sample in the series, kept up to date as samples are pushed
+    pub fn stats(&self) -> Option<SeriesStats> {
+        if self.finite_count == 0 {
+            return None;
+        }
+        let [min, max] = match self.extremes.get() {
+            Some(extremes) => extremes,
+            None => {
+                let extremes =
+                    SeriesStats::of(self.values()).map_or([0.0, 0.0], |s| [s.min, s.max]);
+                self.extremes.set(Some(extremes));
+                extremes
+            }
+        };
+        let count = self.finite_count as f64;
+        let mean = self.sum / count;
+        let variance = (self.sum_squares / count - mean * mean).max(0.0);
+        Some(SeriesStats {
+            count: self.finite_count,
+            min,
+            max,
+            mean: mean as f32,
+            std_dev: variance.sqrt() as f32,
+        })
+    }
+
+    // the samples in the time range reduced to the smallest and largest value of each of `buckets` equal slices of
+    // the range, in time order. with a bucket per pixel column a chart of any number of samples stays cheap to draw
+    // and keeps its spikes, a bucket with a gap in it keeps the gap
+    pub fn downsample(
+        &self,
+        start: f64,
+        end: f64,
+        buckets: usize,
+    ) -> impl Iterator<Item = (f64, f32)> + '_ {
+        let (times, values) = self.window(start, end);
+        MinMaxBuckets::new(times.len(), [start, end], buckets, |i| {
+            (times[i], values[i])
+        })
+    }
+
+    fn recompute_sums(&mut self) {
+        let (mut finite_count, mut sum, mut sum_squares) = (0, 0.0, 0.0);
+        for value in self.values().iter().filter(|v| v.is_finite()) {
+            finite_count += 1;
+            sum += *value as f64;
+            sum_squares += *value as f64 * *value as f64;
+        }
+        (self.finite_count, self.sum, self.sum_squares) = (finite_count, sum, sum_squares);
+    }
+}
+
+impl SeriesStats {
+    // stats of any slice of values, e.g. a window of a time series
+    pub fn of(values: &[f32]) -> Option<Self> {
+        let mut count = 0;
+        let [mut min, mut max] = [f32::MAX, f32::MIN];
+        let (mut sum, mut sum_squares) = (0.0f64, 0.0f64);
+        for value in values.iter().copied().filter(|v| v.is_finite()) {
+            count += 1;
+            [min, max] = [min.min(value), max.max(value)];
+            sum += value as f64;
+            sum_squares += value as f64 * value as f64;
+        }
+        if count == 0 {
+            return None;
+        }
+        let mean = sum / count as f64;
+        let variance = (sum_squares / count as f64 - mean * mean).max(0.0);
+        Some(Self {
+            count,
+            min,
+            max,
+            mean: mean as f32,
+            std_dev: variance.sqrt() as f32,
+        })
+    }
+}
+
+// min/max decimation of samples sorted by time, shared by `TimeSeries::downsample` and the line chart's render pass
+// yields up to three samples per bucket: the smallest, the largest and the first non finite one, in time order
+pub(crate) struct MinMaxBuckets<F> {
+    sample: F,
+    next: usize,
+    len: usize,
+    range: [f64; 2],
+    buckets: usize,
+    pending: [(f64, f32); 3],
+    pending_len: usize,
+    pending_next: usize,
+}
+
+impl<F: Fn(usize) -> (f64, f32)> MinMaxBuckets<F> {
+    pub(crate) fn new(len: usize, range: [f64; 2], buckets: usize, sample: F) -> Self {
+        Self {
+            sample,
+            next: 0,
+            len,
+            range,
+            buckets: buckets.max(1),
+            pending: [(0.0, 0.0); 3],
+            pending_len: 0,
+            pending_next: 0,
+        }
+    }
+
+    fn bucket(&self, time: f64) -> usize {
+        let [start, end] = self.range;
+        let t = match end - start {
+            span if span > 0.0 => (time - start) / span,
+            _ => 0.0,
+        };
+        ((t * self.buckets as f64).floor().max(0.0) as usize).min(self.buckets - 1)
+    }
+}
+
+impl<F: Fn(usize) -> (f64, f32)> Iterator for MinMaxBuckets<F> {
+    type Item = (f64, f32);
+
+    fn next(&mut self) -> Option<(f64, f32)> {
+        if self.pending_next == self.pending_len {
+            if self.next >= self.len {
+                return None;
+            }
+            let bucket = self.bucket((self.sample)(self.next).0);
+            let (mut min, mut max, mut gap): (Option<usize>, Option<usize>, Option<usize>) =
+                (None, None, None);
+            while self.next < self.len {
+                let (time, value) = (self.sample)(self.next);
+                if self.bucket(time) != bucket {
+                    break;
+                }
+                match value.is_finite() {
+                    true => {
+                        if min.is_none_or(|i| value < (self.sample)(i).1) {
+                            min = Some(self.next);
+                        }
+                        if max.is_none_or(|i| value > (self.sample)(i).1) {
+                            max = Some(self.next);
+                        }
+                    }
+                    false => gap = gap.or(Some(self.next)),
+                }
+                self.next += 1;
+            }
+            let mut indices = [min, max, gap];
+            indices.sort_by_key(|i| i.unwrap_or(usize::MAX));
+            self.pending_len = 0;
+            self.pending_next = 0;
+            let mut previous = None;
+            for i in indices.into_iter().flatten() {
+                if previous != Some(i) {
+                    self.pending[self.pending_len] = (self.sample)(i);
+                    self.pending_len += 1;
+                    previous = Some(i);
+                }
+            }
+        }
+        let sample = self.pending[self.pending_next];
+        self.pending_next += 1;
+        Some(sample)
+    }
+}
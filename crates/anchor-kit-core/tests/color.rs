@@ -0,0 +1,200 @@
+use anchor_kit_core::primitives::color::{Color, Hsl, Oklch};
+
+fn parse(input: &str) -> Color {
+    Color::parse(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{actual} is not within {tolerance} of {expected}"
+    );
+}
+
+// how far apart two hues are around the wheel, in degrees
+fn hue_distance(a: f32, b: f32) -> f32 {
+    let d = (a - b).rem_euclid(360.0);
+    d.min(360.0 - d)
+}
+
+// a spread of colours, including greys and the corners of the rgb cube
+const COLORS: [Color; 10] = [
+    Color::BLACK,
+    Color::WHITE,
+    Color::GRAY,
+    Color::RED,
+    Color::LIME,
+    Color::BLUE,
+    Color::ORANGE,
+    Color::PURPLE,
+    Color::rgb(12, 200, 180),
+    Color::rgba(250, 128, 114, 64),
+];
+
+#[test]
+fn hex_colors_are_parsed() {
+    assert_eq!(parse("#f00"), Color::RED);
+    assert_eq!(parse("#f008"), Color::rgba(255, 0, 0, 136));
+    assert_eq!(parse("#FF8000"), Color::rgb(255, 128, 0));
+    assert_eq!(parse("#ff000080"), Color::rgba(255, 0, 0, 128));
+    assert_eq!(parse("  #0000ff  "), Color::BLUE);
+    for color in COLORS {
+        assert_eq!(parse(&color.to_hex()), color);
+    }
+    assert_eq!(Color::rgba(1, 2, 3, 4).to_hex(), "#01020304");
+}
+
+#[test]
+fn rgb_and_hsl_functions_are_parsed() {
+    assert_eq!(parse("rgb(255, 0, 0)"), Color::RED);
+    assert_eq!(parse("rgba(255, 0, 0, 0.5)"), Color::rgba(255, 0, 0, 128));
+    assert_eq!(parse("rgb(255 0 0 / 50%)"), Color::rgba(255, 0, 0, 128));
+    assert_eq!(parse("RGB(100% 50% 0%)"), Color::rgb(255, 128, 0));
+    assert_eq!(parse("rgb(300, -20, 0)"), Color::RED); // channels are clamped
+    assert_eq!(parse("hsl(120deg, 100%, 50%)"), Color::LIME);
+    assert_eq!(parse("hsl(240 100% 50%)"), Color::BLUE);
+    assert_eq!(parse("hsla(0, 100%, 50%, 25%)"), Color::rgba(255, 0, 0, 64));
+    assert_eq!(parse("hsl(-120, 1, 0.5)"), Color::BLUE); // hues wrap around
+}
+
+#[test]
+fn named_colors_are_parsed() {
+    assert_eq!(parse("red"), Color::RED);
+    assert_eq!(parse("Grey"), Color::GRAY);
+    assert_eq!(parse("aqua"), Color::CYAN);
+    assert_eq!(parse("transparent"), Color::TRANSPARENT);
+    assert_eq!("fuchsia".parse::<Color>(), Ok(Color::MAGENTA));
+}
+
+#[test]
+fn invalid_colors_are_errors() {
+    for input in [
+        "",
+        "#",
+        "#ff",
+        "#ff00000",
+        "#gg0000",
+        "rgb(1, 2)",
+        "rgb(1, 2, 3, 4, 5)",
+        "rgb(1 2 / 3)",
+        "rgb(1 2 3 /)",
+        "rgb(red, 0, 0)",
+        "rgb(1, 2, 3",
+        "hsl(red, 100%, 50%)",
+        "hsl(inf, 100%, 50%)",
+        "rgb(NaN, 0, 0)",
+        "cmyk(0, 0, 0, 0)",
+        "reddish",
+    ] {
+        let err = Color::parse(input).unwrap_err();
+        assert_eq!(err.input, input);
+        assert_eq!(err.to_string(), format!("invalid colour `{input}`"));
+    }
+}
+
+#[test]
+fn hsl_conversions_round_trip() {
+    let red = Color::RED.to_hsl();
+    assert_eq!(
+        red,
+        Hsl {
+            h: 0.0,
+            s: 1.0,
+            l: 0.5,
+            a: 1.0
+        }
+    );
+    assert_close(Color::LIME.to_hsl().h, 120.0, 1e-4);
+    assert_close(Color::BLUE.to_hsl().h, 240.0, 1e-4);
+    assert_eq!(Color::GRAY.to_hsl().s, 0.0);
+    for color in COLORS {
+        assert_eq!(Color::from_hsl(color.to_hsl()), color);
+        assert_eq!(Color::from(color.to_hsl()), color);
+    }
+}
+
+#[test]
+fn oklab_and_oklch_conversions_round_trip() {
+    // reference values from https://bottosson.github.io/posts/oklab/
+    let [l, a, b, alpha] = Color::WHITE.to_oklab();
+    assert_close(l, 1.0, 1e-3);
+    assert_close(a, 0.0, 1e-3);
+    assert_close(b, 0.0, 1e-3);
+    assert_eq!(alpha, 1.0);
+    let [l, a, b, _] = Color::RED.to_oklab();
+    assert_close(l, 0.628, 1e-3);
+    assert_close(a, 0.225, 1e-3);
+    assert_close(b, 0.126, 1e-3);
+
+    let red = Color::RED.to_oklch();
+    assert_close(red.c, 0.258, 1e-3);
+    assert_close(red.h, 29.2, 0.1);
+    for color in COLORS {
+        assert_eq!(Color::from_oklab(color.to_oklab()), color);
+        assert_eq!(Color::from_oklch(color.to_oklch()), color);
+        assert_eq!(Color::from(color.to_oklch()), color);
+    }
+    // colours outside the srgb gamut are clamped
+    let out_of_gamut = Color::from_oklch(Oklch {
+        l: 0.9,
+        c: 0.4,
+        h: 140.0,
+        a: 1.0,
+    });
+    assert_eq!(out_of_gamut.a, 255);
+}
+
+#[test]
+fn lerps_clamp_t_and_keep_the_ends() {
+    let (from, to) = (Color::RED, Color::BLUE);
+    for lerp in [
+        Color::lerp,
+        Color::lerp_linear,
+        Color::lerp_oklab,
+        Color::lerp_oklch,
+    ] {
+        assert_eq!(lerp(from, to, 0.0), from);
+        assert_eq!(lerp(from, to, 1.0), to);
+        assert_eq!(lerp(from, to, -1.0), from);
+        assert_eq!(lerp(from, to, 2.0), to);
+    }
+    assert_eq!(
+        Color::BLACK.lerp(Color::WHITE, 0.5),
+        Color::rgb(128, 128, 128)
+    );
+    // mixing light is brighter in the middle than mixing srgb values
+    assert!(Color::BLACK.lerp_linear(Color::WHITE, 0.5).r > 128);
+    assert_eq!(
+        Color::TRANSPARENT.lerp(Color::WHITE, 0.5).a,
+        Color::rgba(0, 0, 0, 128).a
+    );
+}
+
+#[test]
+fn oklch_lerp_takes_the_shorter_way_around_the_hue_wheel() {
+    let at = |h: f32| {
+        Color::from_oklch(Oklch {
+            l: 0.7,
+            c: 0.1,
+            h,
+            a: 1.0,
+        })
+    };
+    let (from, to) = (at(340.0), at(20.0));
+    let [from_h, to_h] = [from.to_oklch().h, to.to_oklch().h];
+    // through 0, not through 180
+    let middle = from.lerp_oklch(to, 0.5).to_oklch();
+    assert!(hue_distance(middle.h, 0.0) < 2.0, "{middle:?}");
+    assert!(hue_distance(middle.h, from_h) < hue_distance(from_h, to_h));
+    assert_close(middle.c, 0.1, 0.01);
+    // the same the other way around
+    let middle = to.lerp_oklch(from, 0.5).to_oklch();
+    assert!(hue_distance(middle.h, 0.0) < 2.0, "{middle:?}");
+
+    // greys don't have a hue, so the colour keeps the other end's hue all the way
+    let red_h = Color::RED.to_oklch().h;
+    for t in [0.25, 0.5, 0.75] {
+        let mixed = Color::WHITE.lerp_oklch(Color::RED, t).to_oklch();
+        assert!(hue_distance(mixed.h, red_h) < 2.0, "{mixed:?}");
+    }
+}
@@ -0,0 +1,121 @@
+use anchor_kit_core::{
+    color_scale::{ColorScale, DivergingScale, Palette, SequentialScale, ThresholdScale},
+    primitives::color::Color,
+};
+
+#[test]
+fn palettes_are_sampled_between_their_stops() {
+    let palette = Palette::new(vec![Color::BLACK, Color::WHITE, Color::RED]);
+    assert_eq!(palette.sample(0.0), Color::BLACK);
+    assert_eq!(palette.sample(0.5), Color::WHITE);
+    assert_eq!(palette.sample(1.0), Color::RED);
+    assert_eq!(palette.sample(-1.0), Color::BLACK);
+    assert_eq!(palette.sample(2.0), Color::RED);
+    assert_eq!(
+        palette.sample(0.25),
+        Color::BLACK.lerp_oklab(Color::WHITE, 0.5)
+    );
+    assert_eq!(
+        palette.reversed().stops(),
+        [Color::RED, Color::WHITE, Color::BLACK]
+    );
+
+    assert_eq!(Palette::new(vec![]).sample(0.5), Color::TRANSPARENT);
+    assert_eq!(Palette::new(vec![Color::BLUE]).sample(0.9), Color::BLUE);
+}
+
+#[test]
+fn sequential_scales_clamp_to_their_domain() {
+    let scale = SequentialScale::viridis([10.0, 20.0]);
+    let stops = Palette::viridis().stops().to_vec();
+    assert_eq!(scale.color(10.0), stops[0]);
+    assert_eq!(scale.color(20.0), stops[10]);
+    assert_eq!(scale.color(-5.0), stops[0]);
+    assert_eq!(scale.color(f64::INFINITY), stops[10]);
+    assert_eq!(scale.color(15.0), stops[5]);
+    assert_eq!(scale.color(f64::NAN), Color::TRANSPARENT);
+
+    // reversed domains run the palette backwards, empty ones use its middle
+    assert_eq!(SequentialScale::viridis([20.0, 10.0]).color(20.0), stops[0]);
+    assert_eq!(SequentialScale::viridis([5.0, 5.0]).color(100.0), stops[5]);
+}
+
+// the midpoint gets the neutral middle stop even when it isn't in the middle of the domain
+#[test]
+fn diverging_scales_are_neutral_at_the_midpoint() {
+    let palette = Palette::red_blue();
+    let scale = DivergingScale::red_blue([-20.0, 0.0, 50.0]);
+    let neutral = palette.stops()[5];
+    assert_eq!(scale.color(0.0), neutral);
+    assert_eq!(scale.color(-20.0), palette.stops()[0]);
+    assert_eq!(scale.color(50.0), palette.stops()[10]);
+    // each side is stretched over its own half of the palette
+    assert_eq!(scale.color(-10.0), palette.sample(0.25));
+    assert_eq!(scale.color(25.0), palette.sample(0.75));
+    assert_eq!(scale.color(-100.0), palette.stops()[0]);
+    assert_eq!(scale.color(100.0), palette.stops()[10]);
+    assert_eq!(scale.color(f64::NAN), Color::TRANSPARENT);
+
+    // a midpoint at one end of the domain still maps to the neutral colour, values past it go to the far end
+    let one_sided = DivergingScale::red_yellow_green([0.0, 0.0, 10.0]);
+    let neutral = Palette::red_yellow_green().stops()[5];
+    assert_eq!(one_sided.color(0.0), neutral);
+    assert_eq!(
+        one_sided.color(-1.0),
+        Palette::red_yellow_green().stops()[0]
+    );
+    assert_eq!(
+        one_sided.color(10.0),
+        Palette::red_yellow_green().stops()[10]
+    );
+    let empty = DivergingScale::red_blue([5.0, 5.0, 5.0]);
+    assert_eq!(empty.color(5.0), palette.stops()[5]);
+    assert_eq!(empty.color(6.0), palette.stops()[10]);
+}
+
+// each colour applies from its threshold, so values exactly on a threshold get that threshold's colour
+#[test]
+fn thresholds_are_inclusive() {
+    let scale = ThresholdScale::new(
+        Color::LIME,
+        // sorted by the constructor
+        vec![(100.0, Color::RED), (50.0, Color::YELLOW)],
+    );
+    assert_eq!(scale.thresholds[0].0, 50.0);
+    for (value, color) in [
+        (f64::NEG_INFINITY, Color::LIME),
+        (0.0, Color::LIME),
+        (49.999, Color::LIME),
+        (50.0, Color::YELLOW),
+        (99.999, Color::YELLOW),
+        (100.0, Color::RED),
+        (f64::INFINITY, Color::RED),
+        (f64::NAN, Color::TRANSPARENT),
+    ] {
+        assert_eq!(scale.color(value), color, "{value}");
+    }
+
+    // with the same threshold twice the later colour wins, since both apply from there
+    let repeated = ThresholdScale::new(Color::BLACK, vec![(1.0, Color::RED), (1.0, Color::BLUE)]);
+    assert_eq!(repeated.color(1.0), Color::BLUE);
+    assert_eq!(
+        ThresholdScale::new(Color::BLACK, vec![]).color(1e9),
+        Color::BLACK
+    );
+}
+
+#[test]
+fn quantized_scales_have_even_bins() {
+    let palette = Palette::new(vec![Color::BLACK, Color::WHITE]);
+    let scale = ThresholdScale::quantize([0.0, 100.0], 4, &palette);
+    let thresholds: Vec<f64> = scale.thresholds.iter().map(|(t, _)| *t).collect();
+    assert_eq!(thresholds, [25.0, 50.0, 75.0]);
+    // each bin is coloured by the middle of its range
+    assert_eq!(scale.color(0.0), palette.sample(0.125));
+    assert_eq!(scale.color(25.0), palette.sample(0.375));
+    assert_eq!(scale.color(100.0), palette.sample(0.875));
+
+    let single = ThresholdScale::quantize([0.0, 100.0], 0, &palette);
+    assert!(single.thresholds.is_empty());
+    assert_eq!(single.color(75.0), palette.sample(0.5));
+}
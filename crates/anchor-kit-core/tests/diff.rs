@@ -0,0 +1,131 @@
+use anchor_kit_core::{
+    anchor::AnchorPosition,
+    diff::{DirtyRegion, RenderListDiff},
+    primitives::{color::Color, rectangle::Rectangle},
+    render::RenderList,
+    style::Style,
+    FrameInfo, UIState,
+};
+
+fn rectangle(position: [u32; 2], color: Color) -> Rectangle {
+    Rectangle::new(
+        position,
+        [100, 100],
+        Some(Style {
+            background_color: color,
+            ..Default::default()
+        }),
+    )
+}
+
+// the same two overlapping rectangles drawn in the opposite order look different where they overlap
+#[test]
+fn z_order_swap_is_dirty() {
+    let red = rectangle([0, 0], Color::rgb(255, 0, 0));
+    let blue = rectangle([50, 50], Color::rgb(0, 0, 255));
+    let previous = RenderList {
+        rectangles: vec![red, blue],
+        ..Default::default()
+    };
+    let current = RenderList {
+        rectangles: vec![blue, red],
+        ..Default::default()
+    };
+
+    let diff = current.diff(&previous);
+    assert!(!diff.is_empty());
+    assert!(diff.added.is_empty() && diff.removed.is_empty());
+    // the overlap has to be redrawn
+    let overlap = DirtyRegion {
+        position: [50, 50],
+        size: [50, 50],
+    };
+    assert!(diff
+        .dirty_regions
+        .iter()
+        .any(|region| region.union(&overlap) == *region));
+}
+
+#[test]
+fn same_order_is_not_dirty() {
+    let previous = RenderList {
+        rectangles: vec![
+            rectangle([0, 0], Color::rgb(255, 0, 0)),
+            rectangle([50, 50], Color::rgb(0, 0, 255)),
+        ],
+        ..Default::default()
+    };
+    let diff = previous.clone().diff(&previous);
+    assert!(diff.is_empty());
+    assert!(diff.dirty_regions.is_empty());
+}
+
+// the first frame is all new, after that only what changed is dirty
+#[test]
+fn frames_diff_against_the_previous_frame() {
+    let mut ui_state = UIState::new([400, 300]);
+    let frame = |ui_state: &mut UIState, label: &str| {
+        ui_state.generate_frame_with_diff(FrameInfo { size: [400, 300] }, |ui| {
+            ui.anchor(AnchorPosition::TopLeft, None, |ui| {
+                ui.text(label, None, None);
+            });
+        })
+    };
+
+    let (render_list, diff) = frame(&mut ui_state, "a");
+    assert_eq!(diff.added.len(), render_list.text.len());
+    let (_, diff) = frame(&mut ui_state, "a");
+    assert!(diff.is_empty());
+    let (_, diff) = frame(&mut ui_state, "b");
+    assert_eq!(diff.changed.len(), 1);
+}
+
+#[test]
+fn regions_at_the_edge_of_the_u32_range_saturate() {
+    let edge = DirtyRegion {
+        position: [u32::MAX - 10, 0],
+        size: [100, 100],
+    };
+    let origin = DirtyRegion {
+        position: [0, 0],
+        size: [10, 10],
+    };
+    let union = edge.union(&origin);
+    assert_eq!(union.position, [0, 0]);
+    assert_eq!(union.size, [u32::MAX, 100]);
+    assert!(edge.intersects(&DirtyRegion {
+        position: [u32::MAX, 50],
+        size: [1, 1],
+    }));
+    assert!(!edge.intersects(&origin));
+}
+
+// separate changes keep their own regions, unless there are so many that merging them would get slow
+#[test]
+fn many_dirty_regions_become_one_bounding_box() {
+    let grid = |count: u32, color: Color| RenderList {
+        rectangles: (0..count)
+            .map(|i| {
+                let mut r = rectangle([i % 10 * 200, i / 10 * 200], color);
+                r.size = [10, 10];
+                r
+            })
+            .collect(),
+        ..Default::default()
+    };
+
+    let few = grid(10, Color::rgb(0, 0, 255)).diff(&grid(10, Color::rgb(255, 0, 0)));
+    assert_eq!(few.dirty_regions.len(), 10);
+
+    let count = RenderListDiff::MAX_DIRTY_REGIONS as u32 + 1;
+    let many = grid(count, Color::rgb(0, 0, 255)).diff(&grid(count, Color::rgb(255, 0, 0)));
+    assert_eq!(many.changed.len(), count as usize);
+    assert_eq!(
+        many.dirty_regions,
+        [DirtyRegion {
+            position: [0, 0],
+            size: [1810, 1210],
+        }]
+    );
+    assert_eq!(many.dirty_bounds(), Some(many.dirty_regions[0]));
+}
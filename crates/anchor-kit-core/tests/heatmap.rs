@@ -0,0 +1,56 @@
+use anchor_kit_core::{
+    anchor::AnchorPosition,
+    color_scale::SequentialScale,
+    element::HeatmapOptions,
+    primitives::{cell_grid::CellGrid, color::Color},
+    FrameInfo, UIState,
+};
+
+// a grid with more cells than fit in a u32 would wrap around when its size is worked out, so it isn't drawn at all
+#[test]
+fn oversized_grid_is_rejected() {
+    let mut ui_state = UIState::new([400, 300]);
+    let scale = SequentialScale::viridis([0.0, 1.0]);
+    for (rows, columns) in [
+        (usize::MAX, 2),
+        (1 << 33, 1),
+        (1 << 33, 0),
+        (1 << 16, 1 << 16),
+    ] {
+        let render_list = ui_state.generate_frame(FrameInfo { size: [400, 300] }, |ui| {
+            ui.anchor(AnchorPosition::TopLeft, None, |ui| {
+                ui.heatmap(
+                    rows,
+                    columns,
+                    &[0.5],
+                    &scale,
+                    Some(HeatmapOptions {
+                        row_labels: &["a"],
+                        ..Default::default()
+                    }),
+                    None,
+                );
+            });
+        });
+        assert!(render_list.cell_grids.iter().all(|grid| grid.rows == 0));
+        assert!(render_list.text.is_empty());
+    }
+}
+
+// the cell index doesn't fit in a u32 for the last rows of a very large grid
+#[test]
+fn cell_colors_of_large_grids_are_looked_up_without_overflow() {
+    let grid = CellGrid {
+        position: [0, 0],
+        size: [100, 100],
+        rows: 1 << 20,
+        columns: 1 << 20,
+        gap: 0,
+        cell_radius: 0.0,
+        colors: vec![Color::RED, Color::LIME],
+    };
+    assert_eq!(grid.color(0, 1), Color::LIME);
+    assert_eq!(grid.color(1 << 12, 0), Color::TRANSPARENT); // would wrap around to index 0 in a u32
+    assert_eq!(grid.color(u32::MAX, u32::MAX), Color::TRANSPARENT);
+    assert_eq!(grid.color(0, 1 << 20), Color::TRANSPARENT); // outside the grid
+}
@@ -0,0 +1,348 @@
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use anchor_kit_core::{
+    anchor::AnchorPosition,
+    color_scale::{Palette, SequentialScale},
+    element::{
+        BarChartOptions, BarSeries, DividerOrientation, DonutOptions, DonutSlice, GaugeOptions,
+        HeatmapOptions, LineChartOptions, LineSeries, SparklineOptions, TableColumn,
+    },
+    layout_file::{Bindings, LayoutDocument, LayoutFile, LayoutFileError},
+    primitives::{
+        color::Color,
+        path::{FillRule, Path, PathCommand},
+    },
+    render::RenderList,
+    style::{Align, SizingPolicy, Style},
+    time_series::TimeSeries,
+    FrameInfo, UIState, UI,
+};
+
+const SIZE: [u32; 2] = [800, 600];
+
+fn render(f: impl FnOnce(&mut UI)) -> RenderList {
+    let mut ui_state = UIState::new(SIZE);
+    ui_state.generate_frame(FrameInfo { size: SIZE }, f)
+}
+
+fn sized(width: u32, height: u32) -> Option<Style> {
+    Some(Style {
+        width: SizingPolicy::Fixed(width),
+        height: SizingPolicy::Fixed(height),
+        ..Default::default()
+    })
+}
+
+// every kind of node, with its data coming from the bindings
+const DASHBOARD: &str = r#"(
+    nodes: [
+        Anchor(position: TopLeft, children: [
+            FlexColumn(children: [
+                Text(text: "fps: {fps} {{raw}} {missing}"),
+                Progress(value: "{cpu}", range: (0, 100), style: (width: Fixed(120), height: Fixed(8))),
+                Sparkline(values: "frame_times", options: (dot: true), style: (width: Fixed(120), height: Fixed(30))),
+                Path(
+                    path: (
+                        commands: [MoveTo((0, 10)), LineTo((10, 0)), LineTo((20, 10)), Close],
+                        fill: (r: 255, g: 128, b: 0, a: 255),
+                    ),
+                    style: (width: Fixed(40), height: Fixed(20)),
+                ),
+                Divider(orientation: Horizontal, thickness: 2),
+                Table(
+                    columns: [(header: "stat"), (header: "value", align: End)],
+                    rows: [["fps", "{fps}"], ["cpu", "{cpu}%"], ["missing"]],
+                ),
+            ]),
+        ]),
+        Anchor(position: BottomRight, children: [
+            FlexRow(children: [
+                BarChart(
+                    categories: ["mon", "tue", "wed"],
+                    series: [(values: "visits"), (values: "errors", color: (r: 255, g: 0, b: 0, a: 255))],
+                    options: (category_axis: true),
+                    style: (width: Fixed(160), height: Fixed(80)),
+                ),
+                LineChart(
+                    series: [(name: "load", series: "load")],
+                    unit: "{unit}",
+                    options: (legend: true, time_format: Relative),
+                    style: (width: Fixed(200), height: Fixed(100)),
+                ),
+                Gauge(value: "{cpu}", range: (0, 100), label: "{cpu}%", style: (width: Fixed(64), height: Fixed(64))),
+                Donut(values: "disk", colors: [(r: 0, g: 0, b: 255, a: 255)], label: "disk"),
+                Heatmap(
+                    rows: 2,
+                    columns: 3,
+                    values: "cores",
+                    scale: Sequential((domain: (0, 1), palette: (stops: [(r: 0, g: 0, b: 0, a: 255), (r: 255, g: 255, b: 255, a: 255)]))),
+                    row_labels: ["a", "b"],
+                    gap: 1,
+                ),
+            ]),
+        ]),
+    ],
+)"#;
+
+fn bindings() -> Bindings {
+    let mut load = TimeSeries::new(4);
+    for (time, value) in [(10.0, 0.5), (11.0, 0.7), (12.0, 0.6)] {
+        load.push(time, value);
+    }
+    let mut bindings = Bindings::new();
+    bindings.set("fps", 144);
+    bindings.set("cpu", 42.5);
+    bindings.set("unit", "%");
+    bindings.set_values("frame_times", &[16.0, 17.5, 15.0, 16.6]);
+    bindings.set_values("visits", &[3.0, 5.0, 4.0]);
+    bindings.set_values("errors", &[1.0, 0.0, 2.0]);
+    bindings.set_values("disk", &[70.0, 30.0]);
+    bindings.set_values("cores", &[0.1, 0.5, 0.9, 0.3, 0.7, f32::NAN]);
+    bindings.set_time_series("load", &load);
+    bindings
+}
+
+// the same ui as `DASHBOARD`, written with the builder
+fn build_dashboard(ui: &mut UI) {
+    ui.anchor(AnchorPosition::TopLeft, None, |ui| {
+        ui.flex_column(None, |ui| {
+            ui.text("fps: 144 {raw} {missing}", None, None);
+            ui.progress(42.5, [0.0, 100.0], None, sized(120, 8));
+            ui.sparkline(
+                &[16.0, 17.5, 15.0, 16.6],
+                Some(SparklineOptions {
+                    dot: true,
+                    ..Default::default()
+                }),
+                sized(120, 30),
+            );
+            ui.path(
+                &Path {
+                    commands: vec![
+                        PathCommand::MoveTo([0.0, 10.0]),
+                        PathCommand::LineTo([10.0, 0.0]),
+                        PathCommand::LineTo([20.0, 10.0]),
+                        PathCommand::Close,
+                    ],
+                    fill: Some(Color::rgb(255, 128, 0)),
+                    fill_rule: FillRule::NonZero,
+                    stroke: None,
+                },
+                None,
+                sized(40, 20),
+            );
+            ui.divider(DividerOrientation::Horizontal, 2, None);
+            ui.table(
+                &[
+                    TableColumn::new("stat"),
+                    TableColumn {
+                        align: Some(Align::End),
+                        ..TableColumn::new("value")
+                    },
+                ],
+                &[vec!["fps", "144"], vec!["cpu", "42.5%"], vec!["missing"]],
+                None,
+                None,
+            );
+        });
+    });
+    ui.anchor(AnchorPosition::BottomRight, None, |ui| {
+        ui.flex_row(None, |ui| {
+            ui.bar_chart(
+                &["mon", "tue", "wed"],
+                &[
+                    BarSeries::new(&[3.0, 5.0, 4.0]),
+                    BarSeries {
+                        color: Some(Color::RED),
+                        ..BarSeries::new(&[1.0, 0.0, 2.0])
+                    },
+                ],
+                Some(BarChartOptions {
+                    category_axis: true,
+                    ..Default::default()
+                }),
+                sized(160, 80),
+            );
+            ui.line_chart(
+                &[LineSeries::new(
+                    "load",
+                    &[10.0, 11.0, 12.0],
+                    &[0.5, 0.7, 0.6],
+                )],
+                Some(LineChartOptions {
+                    unit: "%",
+                    legend: true,
+                    time_format: anchor_kit_core::element::TimeFormat::Relative,
+                    ..Default::default()
+                }),
+                sized(200, 100),
+            );
+            ui.gauge(
+                42.5,
+                [0.0, 100.0],
+                Some(GaugeOptions {
+                    label: "42.5%",
+                    ..Default::default()
+                }),
+                sized(64, 64),
+            );
+            ui.donut(
+                &[
+                    DonutSlice {
+                        color: Some(Color::BLUE),
+                        ..DonutSlice::new(70.0)
+                    },
+                    DonutSlice::new(30.0),
+                ],
+                Some(DonutOptions {
+                    label: "disk",
+                    ..Default::default()
+                }),
+                None,
+            );
+            ui.heatmap(
+                2,
+                3,
+                &[0.1, 0.5, 0.9, 0.3, 0.7, f32::NAN],
+                &SequentialScale::new([0.0, 1.0], Palette::new(vec![Color::BLACK, Color::WHITE])),
+                Some(HeatmapOptions {
+                    row_labels: &["a", "b"],
+                    gap: 1,
+                    ..Default::default()
+                }),
+                None,
+            );
+        });
+    });
+}
+
+#[test]
+fn layout_file_builds_the_same_ui_as_the_builder() {
+    let document = LayoutDocument::from_ron(DASHBOARD).unwrap();
+    let bindings = bindings();
+    let from_file = render(|ui| document.build(ui, &bindings));
+    let from_builder = render(build_dashboard);
+
+    assert!(!from_file.text.is_empty());
+    assert!(!from_file.polylines.is_empty());
+    assert!(!from_file.paths.is_empty());
+    assert!(!from_file.arcs.is_empty());
+    assert!(!from_file.cell_grids.is_empty());
+    assert_eq!(from_file, from_builder);
+}
+
+#[test]
+fn json_and_ron_documents_are_the_same() {
+    let ron = r#"(nodes: [Pill(style: (padding: (left: 4)), children: [Text(text: "{name}"), Keyed(key: "bar", node: Class(class: "wide", node: Progress(value: "0.5", range: (0, 1))))])])"#;
+    let json = r#"{"nodes": [{"Pill": {"style": {"padding": {"left": 4}}, "children": [
+        {"Text": {"text": "{name}"}},
+        {"Keyed": {"key": "bar", "node": {"Class": {"class": "wide", "node": {"Progress": {"value": "0.5", "range": [0, 1]}}}}}}
+    ]}}]}"#;
+    let mut bindings = Bindings::new();
+    bindings.set("name", "pill");
+    let ron = LayoutDocument::from_ron(ron).unwrap();
+    let json = LayoutDocument::from_json(json).unwrap();
+
+    let from_ron = render(|ui| ron.build(ui, &bindings));
+    let from_json = render(|ui| json.build(ui, &bindings));
+    assert_eq!(from_ron.text[0].text, "pill");
+    assert_eq!(from_ron.rectangles.len(), 3); // the pill, the progress track and its fill
+    assert_eq!(from_ron, from_json);
+}
+
+#[test]
+fn placeholders_are_substituted() {
+    let document =
+        LayoutDocument::from_ron(r#"(nodes: [Text(text: "{a}/{b} {{a}} {c} }} {")])"#).unwrap();
+    let mut bindings = Bindings::new();
+    bindings.set("a", 1);
+    bindings.set("b", "two");
+    let text = |bindings: &Bindings| {
+        render(|ui| document.build(ui, bindings)).text[0]
+            .text
+            .clone()
+    };
+
+    // unbound placeholders and unmatched braces are left as they are
+    assert_eq!(text(&bindings), "1/two {a} {c} } {");
+    bindings.set("a", 10.5);
+    bindings.set("c", "");
+    assert_eq!(text(&bindings), "10.5/two {a}  } {");
+    assert_eq!(bindings.get("a"), Some("10.5"));
+}
+
+#[test]
+fn invalid_documents_are_errors() {
+    assert!(matches!(
+        LayoutDocument::from_ron("(nodes: [Text()])"),
+        Err(LayoutFileError::Ron(_))
+    ));
+    assert!(matches!(
+        LayoutDocument::from_json(r#"{"nodes": [{"Unknown": {}}]}"#),
+        Err(LayoutFileError::Json(_))
+    ));
+}
+
+// a file in a fresh directory under the system temp dir, removed again when the test is done
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, contents: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("anchor-kit-layout-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = Self(dir.join(name));
+        file.write(contents, SystemTime::now());
+        file
+    }
+
+    // sets the modified time explicitly, file systems with coarse timestamps might not see a quick rewrite otherwise
+    fn write(&self, contents: &str, modified: SystemTime) {
+        fs::write(&self.0, contents).unwrap();
+        let file = fs::File::options().write(true).open(&self.0).unwrap();
+        file.set_modified(modified).unwrap();
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn layout_file_reloads_when_it_changes() {
+    let file = TempFile::new("reload.ron", r#"(nodes: [Text(text: "first")])"#);
+    let mut layout_file = LayoutFile::load(&file.0).unwrap();
+    let text = |layout_file: &LayoutFile| {
+        render(|ui| layout_file.build(ui, &Bindings::new())).text[0]
+            .text
+            .clone()
+    };
+    assert_eq!(text(&layout_file), "first");
+    assert!(!layout_file.reload_if_changed().unwrap());
+
+    let later = SystemTime::now() + Duration::from_secs(10);
+    file.write(r#"(nodes: [Text(text: "second")])"#, later);
+    assert!(layout_file.reload_if_changed().unwrap());
+    assert_eq!(text(&layout_file), "second");
+    assert!(!layout_file.reload_if_changed().unwrap());
+
+    // a broken file keeps the last valid document, and isn't read again until it changes
+    file.write("(nodes: [Text(", later + Duration::from_secs(10));
+    assert!(layout_file.reload_if_changed().is_err());
+    assert_eq!(text(&layout_file), "second");
+    assert!(!layout_file.reload_if_changed().unwrap());
+}
+
+#[test]
+fn unknown_extensions_are_rejected() {
+    let file = TempFile::new("layout.txt", "(nodes: [])");
+    assert!(matches!(
+        LayoutFile::load(&file.0),
+        Err(LayoutFileError::UnknownFormat(_))
+    ));
+}
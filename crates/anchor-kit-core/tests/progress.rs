@@ -0,0 +1,65 @@
+use anchor_kit_core::{
+    anchor::AnchorPosition,
+    render::RenderList,
+    style::{SizingPolicy, Style},
+    FrameInfo, UIState,
+};
+
+fn render(value: f32, range: [f32; 2]) -> RenderList {
+    let mut ui_state = UIState::new([400, 300]);
+    ui_state.generate_frame(FrameInfo { size: [400, 300] }, |ui| {
+        ui.anchor(AnchorPosition::TopLeft, None, |ui| {
+            ui.flex_column(None, |ui| {
+                let style = Style {
+                    width: SizingPolicy::Fixed(100),
+                    height: SizingPolicy::Fixed(10),
+                    ..Default::default()
+                };
+                ui.progress(value, range, None, Some(style));
+                ui.gauge(value, range, None, None);
+            });
+        })
+    })
+}
+
+// the width of the progress bar's fill and how much of the gauge's sweep is filled, both 0 when there is no fill
+fn fills(render_list: &RenderList) -> (u32, f32) {
+    // the bar's track comes first, then its fill
+    let mut bar = render_list.rectangles.iter().filter(|r| r.size[1] == 10);
+    let fill = bar.nth(1).map_or(0, |fill| fill.size[0]);
+    let sweep = |i: usize| {
+        let arc = &render_list.arcs[i];
+        arc.end_angle - arc.start_angle
+    };
+    let gauge = match render_list.arcs.len() {
+        2 => sweep(1) / sweep(0),
+        _ => 0.0,
+    };
+    (fill, gauge)
+}
+
+#[test]
+fn progress_and_gauges_fill_to_the_value() {
+    let (bar, gauge) = fills(&render(25.0, [0.0, 100.0]));
+    assert_eq!(bar, 25);
+    assert!((gauge - 0.25).abs() < 1e-4);
+    assert_eq!(fills(&render(150.0, [0.0, 100.0])).0, 100);
+}
+
+// progress bars and gauges treat the same ranges as empty
+#[test]
+fn empty_and_reversed_ranges_show_no_fill() {
+    for (value, range) in [
+        (50.0, [100.0, 0.0]),
+        (50.0, [10.0, 10.0]),
+        (50.0, [0.0, f32::NAN]),
+        (50.0, [f32::NEG_INFINITY, f32::INFINITY]),
+        (f32::NAN, [0.0, 100.0]),
+    ] {
+        assert_eq!(
+            fills(&render(value, range)),
+            (0, 0.0),
+            "{value} in {range:?}"
+        );
+    }
+}
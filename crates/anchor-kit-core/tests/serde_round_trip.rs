@@ -0,0 +1,195 @@
+use std::time::Duration;
+
+use anchor_kit_core::{
+    anchor::AnchorPosition,
+    animation::{Easing, Transition, TransitionEffect},
+    color_scale::SequentialScale,
+    element::{DividerOrientation, GaugeOptions, SparklineOptions},
+    primitives::{
+        color::Color,
+        path::{FillRule, Path, PathCommand},
+        polyline::Stroke,
+    },
+    render::RenderList,
+    style::{Align, FontFamily, FontStyle, FontWeight, Insets, SizingPolicy, Style, TextStyle},
+    FrameInfo, UIState,
+};
+use uuid::Uuid;
+
+// a frame that uses every kind of primitive and most of the style options
+fn build_render_list() -> RenderList {
+    let mut ui_state = UIState::new([800, 600]);
+    let texture_id = Uuid::new_v4();
+    ui_state.generate_frame(FrameInfo { size: [800, 600] }, |ui| {
+        ui.anchor(AnchorPosition::TopRight, None, |ui| {
+            ui.pill(
+                Some(Style {
+                    padding: Insets {
+                        top: 4,
+                        right: 8,
+                        bottom: 4,
+                        left: 8,
+                    },
+                    width: SizingPolicy::Fixed(200),
+                    justify_x: Align::Middle,
+                    background_color: Color {
+                        r: 20,
+                        g: 30,
+                        b: 40,
+                        a: 200,
+                    },
+                    border_radius: [8.0, 8.0, 0.0, 4.5],
+                    border_width: 1.5,
+                    transition: Some(Transition {
+                        enter: None, // no enter animation so the output is the same every run
+                        exit: Some(TransitionEffect {
+                            fade: true,
+                            slide: Some(12),
+                            scale: Some(0.8),
+                        }),
+                        duration: Duration::from_millis(150),
+                        easing: Easing::EaseInOut,
+                    }),
+                    ..Default::default()
+                }),
+                |ui| {
+                    ui.text(
+                        "fps: 144 ✓",
+                        None,
+                        Some(TextStyle {
+                            font_family: FontFamily::Name("Inter".to_string()),
+                            font_weight: FontWeight::SemiBold,
+                            font_style: FontStyle::Italic,
+                            ..Default::default()
+                        }),
+                    );
+                },
+            );
+        });
+        ui.anchor(AnchorPosition::BottomLeft, None, |ui| {
+            ui.flex_column(None, |ui| {
+                ui.image(
+                    texture_id,
+                    Some(Style {
+                        width: SizingPolicy::Fixed(64),
+                        height: SizingPolicy::Fixed(64),
+                        ..Default::default()
+                    }),
+                );
+                ui.divider(DividerOrientation::Horizontal, 2, None);
+                ui.text("mono", None, None);
+            });
+        });
+        ui.anchor(AnchorPosition::BottomRight, None, |ui| {
+            ui.flex_row(None, |ui| {
+                // polylines, with a shaded area under the line
+                ui.sparkline(
+                    &[1.0, 4.5, 2.25, f32::NAN, 3.0],
+                    Some(SparklineOptions {
+                        fill_color: Some(Color::rgba(0, 128, 255, 64)),
+                        ..Default::default()
+                    }),
+                    Some(sized(80, 24)),
+                );
+                ui.path(
+                    &Path {
+                        commands: vec![
+                            PathCommand::MoveTo([0.0, 0.0]),
+                            PathCommand::QuadTo {
+                                control: [5.0, 10.0],
+                                to: [10.0, 0.0],
+                            },
+                            PathCommand::ArcTo {
+                                radii: [5.0, 5.0],
+                                x_rotation: 0.0,
+                                large_arc: false,
+                                sweep: true,
+                                to: [0.0, 0.0],
+                            },
+                            PathCommand::Close,
+                        ],
+                        fill: Some(Color::ORANGE),
+                        fill_rule: FillRule::EvenOdd,
+                        stroke: Some(Stroke::new(1.5, Color::WHITE)),
+                    },
+                    None,
+                    Some(sized(20, 20)),
+                );
+                // arcs
+                ui.gauge(
+                    0.6,
+                    [0.0, 1.0],
+                    Some(GaugeOptions {
+                        label: "60%",
+                        ..Default::default()
+                    }),
+                    Some(sized(48, 48)),
+                );
+                // a cell grid
+                ui.heatmap(
+                    2,
+                    2,
+                    &[0.0, 0.3, f32::NAN, 1.0],
+                    &SequentialScale::viridis([0.0, 1.0]),
+                    None,
+                    None,
+                );
+            });
+        });
+    })
+}
+
+fn sized(width: u32, height: u32) -> Style {
+    Style {
+        width: SizingPolicy::Fixed(width),
+        height: SizingPolicy::Fixed(height),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn json_round_trip() {
+    let render_list = build_render_list();
+    assert!(!render_list.rectangles.is_empty());
+    assert!(!render_list.text.is_empty());
+    assert!(!render_list.images.is_empty());
+    assert!(!render_list.polylines.is_empty());
+    assert!(!render_list.paths.is_empty());
+    assert!(!render_list.arcs.is_empty());
+    assert!(!render_list.cell_grids.is_empty());
+
+    let json = render_list.to_json().unwrap();
+    let decoded = RenderList::from_json(&json).unwrap();
+    assert_eq!(decoded, render_list);
+}
+
+#[test]
+fn binary_round_trip() {
+    let render_list = build_render_list();
+    let bytes = render_list.to_bytes().unwrap();
+    let decoded = RenderList::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, render_list);
+
+    // the binary encoding should be the compact one
+    assert!(bytes.len() < render_list.to_json().unwrap().len());
+}
+
+#[test]
+fn style_round_trip() {
+    let style = Style {
+        height: SizingPolicy::FillParent,
+        align_y: Align::End,
+        transition: Some(Transition::default()),
+        ..Default::default()
+    };
+    let json = serde_json::to_string(&style).unwrap();
+    assert_eq!(serde_json::from_str::<Style>(&json).unwrap(), style);
+    let bytes = bincode::serialize(&style).unwrap();
+    assert_eq!(bincode::deserialize::<Style>(&bytes).unwrap(), style);
+}
+
+#[test]
+fn invalid_input_is_an_error() {
+    assert!(RenderList::from_json("{\"rectangles\": 4}").is_err());
+    assert!(RenderList::from_bytes(&[1, 2, 3]).is_err());
+}
@@ -0,0 +1,252 @@
+use anchor_kit_core::{
+    anchor::AnchorPosition,
+    primitives::color::Color,
+    render::RenderList,
+    style::{FontFamily, FontWeight, Insets, SizingPolicy, Style},
+    stylesheet::Stylesheet,
+    theme::{ColorToken, SpaceToken, Theme, TypeToken},
+    FrameInfo, UIState, UI,
+};
+
+fn render(css: &str, f: impl FnOnce(&mut UI)) -> RenderList {
+    let mut ui_state = UIState::new([400, 300]);
+    ui_state.set_stylesheet(Stylesheet::parse(css).unwrap());
+    ui_state.generate_frame(FrameInfo { size: [400, 300] }, |ui| {
+        ui.anchor(AnchorPosition::TopLeft, None, |ui| ui.flex_column(None, f))
+    })
+}
+
+// the style of each pill, in the order they were added
+fn pill_styles(css: &str, f: impl FnOnce(&mut UI)) -> Vec<Style> {
+    render(css, f).rectangles.iter().map(|r| r.style).collect()
+}
+
+fn insets(all: u32) -> Insets {
+    Insets {
+        top: all,
+        right: all,
+        bottom: all,
+        left: all,
+    }
+}
+
+#[test]
+fn type_and_class_selectors_match() {
+    let css = "
+        pill { background-color: #ff0000; }
+        .card { border-width: 2; }
+        pill.card.wide { width: 120; }
+    ";
+    let styles = pill_styles(css, |ui| {
+        ui.pill(None, |_| {});
+        ui.class("card").pill(None, |_| {});
+        ui.class("wide card").pill(None, |_| {});
+        ui.flex_row(None, |_| {}); // not a pill, so nothing applies
+    });
+    assert_eq!(styles.len(), 3);
+    assert_eq!(styles[0].background_color, Color::RED);
+    assert_eq!(styles[0].border_width, 0.0);
+    assert_eq!(styles[1].border_width, 2.0);
+    assert_eq!(styles[1].width, SizingPolicy::Auto);
+    assert_eq!(styles[2].width, SizingPolicy::Fixed(120));
+}
+
+#[test]
+fn more_specific_rules_win_then_later_ones() {
+    let css = "
+        .card { background-color: #00ff00; }
+        pill { background-color: #0000ff; }
+        pill.card { border-color: #ff0000; }
+        .card { border-color: #00ff00; }
+        pill { border-width: 1; }
+        pill { border-width: 3; }
+    ";
+    let styles = pill_styles(css, |ui| {
+        ui.class("card").pill(None, |_| {});
+        ui.pill(None, |_| {});
+    });
+    // a class beats a type even when the type rule comes later, a class and a type beat a class alone
+    assert_eq!(styles[0].background_color, Color::LIME);
+    assert_eq!(styles[0].border_color, Color::RED);
+    assert_eq!(styles[1].background_color, Color::BLUE);
+    // with the same specificity the last rule wins
+    assert_eq!(styles[0].border_width, 3.0);
+    assert_eq!(styles[1].border_width, 3.0);
+}
+
+#[test]
+fn rules_added_later_win_over_earlier_ones() {
+    let mut ui_state = UIState::new([400, 300]);
+    ui_state
+        .stylesheet_mut()
+        .add("pill { padding: 4; }")
+        .unwrap();
+    ui_state
+        .stylesheet_mut()
+        .add_class("card", "padding: 2 6")
+        .unwrap();
+    ui_state
+        .stylesheet_mut()
+        .add("pill { padding: 8; }")
+        .unwrap();
+    let render_list = ui_state.generate_frame(FrameInfo { size: [400, 300] }, |ui| {
+        ui.pill(None, |_| {});
+        ui.class("card").pill(None, |_| {});
+    });
+    assert_eq!(render_list.rectangles[0].style.padding, insets(8));
+    let card = render_list.rectangles[1].style.padding;
+    assert_eq!([card.top, card.right, card.bottom, card.left], [2, 6, 2, 6]);
+}
+
+#[test]
+fn descendant_selectors_match_any_ancestor() {
+    let css = "
+        .panel text { color: #00ff00; }
+        .panel .row pill { border-width: 4; }
+        flex_row * { margin: 1; }
+    ";
+    let render_list = render(css, |ui| {
+        ui.class("panel").pill(None, |ui| {
+            ui.flex_column(None, |ui| {
+                ui.text("deep inside the panel", None, None);
+                ui.class("row").flex_row(None, |ui| {
+                    ui.flex_column(None, |ui| ui.pill(None, |_| {}));
+                });
+            });
+        });
+        ui.text("outside", None, None);
+        ui.class("row").flex_row(None, |ui| ui.pill(None, |_| {}));
+    });
+    let text_color = |text: &str| {
+        let text = render_list.text.iter().find(|t| t.text == text).unwrap();
+        text.text_style.text_color
+    };
+    assert_eq!(text_color("deep inside the panel"), Color::LIME);
+    assert_ne!(text_color("outside"), Color::LIME);
+
+    // the panel itself, the pill inside the panel's row, and the pill in a row outside the panel
+    let styles: Vec<Style> = render_list.rectangles.iter().map(|r| r.style).collect();
+    assert_eq!(styles.len(), 3);
+    assert_eq!(styles[0].border_width, 0.0);
+    assert_eq!(styles[1].border_width, 4.0);
+    assert_eq!(styles[2].border_width, 0.0);
+    // `*` matches any descendant of a row, not just its children
+    assert_eq!(styles[0].margin, insets(0));
+    assert_eq!(styles[1].margin, insets(1));
+    assert_eq!(styles[2].margin, insets(1));
+}
+
+#[test]
+fn theme_tokens_are_resolved() {
+    let css = "
+        pill {
+            background-color: var(--surface);
+            border-color: var(--accent);
+            padding: var(--space-md) 3;
+            font-size: var(--text-title);
+        }
+        text { color: var(--on-surface); }
+    ";
+    let render_list = render(css, |ui| ui.pill(None, |ui| ui.text("title", None, None)));
+    let theme = Theme::default();
+    let style = render_list.rectangles[0].style;
+    assert_eq!(style.background_color, theme.color(ColorToken::Surface));
+    assert_eq!(style.border_color, theme.color(ColorToken::Accent));
+    assert_eq!(style.padding.top, theme.space(SpaceToken::Md));
+    assert_eq!(style.padding.left, 3);
+
+    // the type size is inherited from the pill, the colour is set on the text itself
+    let text_style = &render_list.text[0].text_style;
+    let title = theme.type_size(TypeToken::Title);
+    assert_eq!(text_style.font_size, title.font_size);
+    assert_eq!(text_style.line_height, title.line_height);
+    assert_eq!(text_style.text_color, theme.color(ColorToken::OnSurface));
+}
+
+#[test]
+fn properties_and_values_are_parsed() {
+    let css = "
+        /* comments are ignored */
+        pill {
+            padding: 1 2 3;
+            border-radius: 4px 8px;
+            border-top-left-radius: 1;
+            height: fill;
+            font-weight: 700;
+            font-family: 'Fira Mono';
+            background: rgb(255 0 0 / 50%);
+        }
+    ";
+    let render_list = render(css, |ui| ui.pill(None, |ui| ui.text("text", None, None)));
+    let style = render_list.rectangles[0].style;
+    let padding = style.padding;
+    assert_eq!(
+        [padding.top, padding.right, padding.bottom, padding.left],
+        [1, 2, 3, 2]
+    );
+    assert_eq!(style.border_radius, [1.0, 8.0, 4.0, 8.0]);
+    assert_eq!(style.height, SizingPolicy::FillParent);
+    assert_eq!(style.background_color, Color::rgba(255, 0, 0, 128));
+    let text_style = &render_list.text[0].text_style;
+    assert_eq!(text_style.font_weight, FontWeight::Bold);
+    assert_eq!(
+        text_style.font_family,
+        FontFamily::Name("Fira Mono".to_string())
+    );
+}
+
+// fields the explicit style sets are kept, the ones still at their default come from the rules
+#[test]
+fn explicit_styles_are_merged_over_class_rules() {
+    let css = ".card { background-color: #ff0000; padding: 6; width: 50; color: #00ff00; }";
+    let render_list = render(css, |ui| {
+        ui.class("card").pill(
+            Some(Style {
+                width: SizingPolicy::Fixed(200),
+                border_width: 2.0,
+                ..Default::default()
+            }),
+            |ui| ui.text("card", None, None),
+        );
+    });
+    let style = render_list.rectangles[0].style;
+    assert_eq!(style.width, SizingPolicy::Fixed(200));
+    assert_eq!(style.border_width, 2.0);
+    assert_eq!(style.background_color, Color::RED);
+    assert_eq!(style.padding, insets(6));
+    assert_eq!(render_list.text[0].text_style.text_color, Color::LIME);
+}
+
+#[test]
+fn errors_report_the_line() {
+    let error = |css: &str| Stylesheet::parse(css).unwrap_err();
+
+    let unknown_property = error("pill {\n  padding: 4;\n}\n\n.card { colour: red; }");
+    assert_eq!(unknown_property.line, 5);
+    assert!(unknown_property.message.contains("colour"));
+
+    assert!(error("button { padding: 4; }")
+        .message
+        .contains("unknown element type `button`"));
+    assert!(error("pill { width: wide; }").message.contains("`wide`"));
+    assert!(error("pill { color: var(--missing); }")
+        .message
+        .contains("var(--missing)"));
+    assert!(error(".card! { padding: 1; }").message.contains("card!"));
+    assert_eq!(error("pill { padding: 1;").line, 1);
+    assert_eq!(error("\n\n} pill {").message, "unexpected `}`");
+}
+
+#[test]
+fn failed_adds_leave_the_stylesheet_unchanged() {
+    let mut stylesheet = Stylesheet::parse("pill { padding: 4; }").unwrap();
+    assert!(stylesheet
+        .add(".card { padding: 1; } .broken { nope: 1; }")
+        .is_err());
+    let mut ui_state = UIState::new([400, 300]);
+    ui_state.set_stylesheet(stylesheet);
+    let render_list = ui_state.generate_frame(FrameInfo { size: [400, 300] }, |ui| {
+        ui.class("card").pill(None, |_| {});
+    });
+    assert_eq!(render_list.rectangles[0].style.padding, insets(4));
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use anchor_kit_core::{
+    anchor::AnchorPosition,
+    color_scale::SequentialScale,
+    element::{GaugeOptions, ProgressOptions, SparklineOptions},
+    primitives::{
+        color::Color,
+        path::{FillRule, Path, PathCommand},
+        polyline::Stroke,
+        text::Text,
+    },
+    style::{Insets, PartialTextStyle, SizingPolicy, Style, TextStyle},
+    FrameInfo, UIState,
+};
+
+const SIZE: [u32; 2] = [320, 240];
+
+// a frame with every kind of primitive, positions only depend on the layout so the output is the same everywhere
+fn golden_frame() -> String {
+    let mut ui_state = UIState::new(SIZE);
+    let mut render_list = ui_state.generate_frame(FrameInfo { size: SIZE }, |ui| {
+        ui.anchor(AnchorPosition::TopLeft, None, |ui| {
+            ui.text_style(PartialTextStyle {
+                text_color: Some(Color::WHITE),
+                ..Default::default()
+            })
+            .pill(
+                Some(Style {
+                    background_color: Color::rgb(32, 32, 48),
+                    border_color: Color::rgba(255, 255, 255, 128),
+                    border_width: 2.0,
+                    border_radius: [8.0; 4],
+                    padding: Insets {
+                        top: 8,
+                        right: 8,
+                        bottom: 8,
+                        left: 8,
+                    },
+                    ..Default::default()
+                }),
+                |ui| {
+                    ui.flex_column(None, |ui| {
+                        ui.text("cpu & <mem>", None, None);
+                        ui.progress(
+                            40.0,
+                            [0.0, 100.0],
+                            Some(ProgressOptions {
+                                segments: 4,
+                                segment_gap: 2,
+                                ..Default::default()
+                            }),
+                            Some(Style {
+                                width: SizingPolicy::Fixed(120),
+                                height: SizingPolicy::Fixed(8),
+                                ..Default::default()
+                            }),
+                        );
+                        ui.sparkline(
+                            &[1.0, 3.0, 2.0, 5.0, 4.0],
+                            Some(SparklineOptions {
+                                fill_color: Some(Color::rgba(0, 128, 255, 64)),
+                                dot: true,
+                                ..Default::default()
+                            }),
+                            Some(Style {
+                                width: SizingPolicy::Fixed(120),
+                                height: SizingPolicy::Fixed(30),
+                                ..Default::default()
+                            }),
+                        );
+                    });
+                },
+            );
+        });
+        ui.anchor(AnchorPosition::BottomRight, None, |ui| {
+            ui.flex_row(None, |ui| {
+                ui.gauge(
+                    75.0,
+                    [0.0, 100.0],
+                    Some(GaugeOptions {
+                        label: "75%",
+                        ..Default::default()
+                    }),
+                    Some(Style {
+                        width: SizingPolicy::Fixed(64),
+                        height: SizingPolicy::Fixed(64),
+                        ..Default::default()
+                    }),
+                );
+                ui.heatmap(
+                    2,
+                    3,
+                    &[0.0, 0.2, 0.4, 0.6, 0.8, f32::NAN],
+                    &SequentialScale::viridis([0.0, 1.0]),
+                    None,
+                    None,
+                );
+                ui.path(
+                    &Path {
+                        commands: vec![
+                            PathCommand::MoveTo([0.0, 10.0]),
+                            PathCommand::LineTo([10.0, 0.0]),
+                            PathCommand::LineTo([20.0, 10.0]),
+                            PathCommand::Close,
+                        ],
+                        fill: Some(Color::ORANGE),
+                        fill_rule: FillRule::NonZero,
+                        stroke: Some(Stroke::new(2.0, Color::WHITE)),
+                    },
+                    None,
+                    Some(Style {
+                        width: SizingPolicy::Fixed(40),
+                        height: SizingPolicy::Fixed(20),
+                        ..Default::default()
+                    }),
+                );
+            });
+        });
+    });
+    // text is measured as one line, so multi-line text is added by hand with room for both lines
+    render_list.text.push(Text {
+        text: "first line\nsecond line".to_string(),
+        position: [10, 120],
+        size: [120, 40],
+        text_style: TextStyle {
+            text_color: Color::WHITE,
+            ..Default::default()
+        },
+        offset: [0.0, 0.0],
+    });
+    render_list.to_svg(SIZE, &HashMap::new())
+}
+
+// run with `ANCHOR_KIT_UPDATE_GOLDEN=1 cargo test -p anchor-kit-core --test svg` after an intended change, and check
+// the new file in a browser before committing it
+#[test]
+fn frame_matches_golden_svg() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/frame.svg");
+    let svg = golden_frame();
+    if std::env::var_os("ANCHOR_KIT_UPDATE_GOLDEN").is_some() {
+        std::fs::write(path, &svg).unwrap();
+    }
+    let golden =
+        std::fs::read_to_string(path).expect("golden file is missing, see the comment above");
+    assert!(svg == golden, "svg output changed:\n{svg}");
+}
+
+#[test]
+fn multi_line_text_is_split_into_lines() {
+    let svg = golden_frame();
+    assert!(svg.contains(
+        r#"<tspan x="10" y="130">first line</tspan><tspan x="10" y="150">second line</tspan></text>"#
+    ));
+    assert!(svg.contains(r#"<rect x="10" y="120" width="120" height="40"/></clipPath>"#));
+    assert!(svg.contains(">cpu &amp; &lt;mem&gt;</tspan>"));
+}
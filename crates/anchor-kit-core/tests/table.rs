@@ -0,0 +1,57 @@
+use anchor_kit_core::{
+    anchor::AnchorPosition,
+    element::{TableColumn, TableOptions},
+    primitives::color::Color,
+    render::RenderList,
+    style::FontWeight,
+    FrameInfo, UIState, UI,
+};
+
+fn render(f: impl FnOnce(&mut UI)) -> RenderList {
+    let mut ui_state = UIState::new([400, 300]);
+    ui_state.generate_frame(FrameInfo { size: [400, 300] }, |ui| {
+        ui.anchor(AnchorPosition::TopLeft, None, f)
+    })
+}
+
+// the header row is measured with the style it is drawn with, so it isn't cut off
+#[test]
+fn header_cells_use_the_header_style() {
+    let render_list = render(|ui| {
+        ui.table(
+            &[TableColumn::new("frame time"), TableColumn::new("fps")],
+            &[["16.6 ms", "60"]],
+            Some(TableOptions {
+                header_color: Some(Color::ORANGE),
+                ..Default::default()
+            }),
+            None,
+        );
+    });
+    let text: Vec<&str> = render_list.text.iter().map(|t| t.text.as_str()).collect();
+    assert_eq!(text, ["frame time", "fps", "16.6 ms", "60"]);
+    for header in &render_list.text[..2] {
+        assert_eq!(header.text_style.font_weight, FontWeight::Bold);
+        assert_eq!(header.text_style.text_color, Color::ORANGE);
+    }
+    assert_ne!(render_list.text[2].text_style.font_weight, FontWeight::Bold);
+}
+
+// rows so tall that the table's height doesn't fit in a u32 fill the space they are given instead of wrapping around
+#[test]
+fn table_height_does_not_overflow() {
+    let render_list = render(|ui| {
+        ui.table(
+            &[TableColumn::new("")],
+            &[["a"], ["b"], ["c"]],
+            Some(TableOptions {
+                cell_padding: Some([0, u32::MAX / 4]),
+                row_dividers: u32::MAX / 2,
+                ..Default::default()
+            }),
+            None,
+        );
+    });
+    assert_eq!(render_list.rectangles[0].size[1], 300);
+    assert!(render_list.text.is_empty()); // not even one row fits
+}
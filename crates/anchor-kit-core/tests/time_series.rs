@@ -0,0 +1,113 @@
+use anchor_kit_core::time_series::{SeriesStats, TimeSeries};
+
+fn series(capacity: usize, samples: impl IntoIterator<Item = (f64, f32)>) -> TimeSeries {
+    let mut series = TimeSeries::new(capacity);
+    for (time, value) in samples {
+        series.push(time, value);
+    }
+    series
+}
+
+#[test]
+fn pushing_past_the_capacity_drops_the_oldest_samples() {
+    let mut series = TimeSeries::new(4);
+    assert!(series.is_empty());
+    assert_eq!(series.last(), None);
+    // several laps, stopping part way through one so the oldest sample isn't in the first slot
+    for i in 0..10 {
+        series.push(i as f64, i as f32 * 10.0);
+        assert_eq!(series.len(), (i + 1).min(4));
+    }
+    assert_eq!(series.capacity(), 4);
+    assert_eq!(series.times(), [6.0, 7.0, 8.0, 9.0]);
+    assert_eq!(series.values(), [60.0, 70.0, 80.0, 90.0]);
+    assert_eq!(series.last(), Some((9.0, 90.0)));
+
+    assert_eq!(
+        series.window(7.0, 8.0),
+        (&[7.0, 8.0][..], &[70.0, 80.0][..])
+    );
+    assert_eq!(series.window(6.5, 6.9), (&[][..], &[][..]));
+    assert_eq!(series.window(9.0, 7.0), (&[][..], &[][..]));
+    assert_eq!(series.latest(1.0).1, [80.0, 90.0]);
+
+    series.clear();
+    assert!(series.is_empty());
+    assert_eq!(series.stats(), None);
+    series.push(10.0, 1.0);
+    assert_eq!(series.values(), [1.0]);
+
+    // a capacity of 0 still keeps the newest sample
+    let single = self::series(0, [(0.0, 1.0), (1.0, 2.0)]);
+    assert_eq!(single.values(), [2.0]);
+}
+
+#[test]
+fn stats_follow_the_samples_in_the_buffer() {
+    let mut series = TimeSeries::new(3);
+    for (i, value) in [5.0, 1.0, f32::NAN, 3.0, 2.0, f32::INFINITY, 4.0]
+        .into_iter()
+        .enumerate()
+    {
+        series.push(i as f64, value);
+        assert_eq!(series.stats(), SeriesStats::of(series.values()), "{i}");
+    }
+    // the smallest and largest values were dropped along the way, so min and max were recomputed
+    let stats = series.stats().unwrap();
+    assert_eq!(
+        [stats.count, stats.min as usize, stats.max as usize],
+        [2, 2, 4]
+    );
+
+    let gaps = self::series(2, [(0.0, f32::NAN), (1.0, f32::NAN)]);
+    assert_eq!(gaps.stats(), None);
+}
+
+// the running sums are rebuilt from the samples once per lap, so large values that were dropped don't leave
+// rounding errors behind
+#[test]
+fn sums_are_recomputed_every_lap() {
+    let mut series = TimeSeries::new(4);
+    for i in 0..4 {
+        series.push(i as f64, 1e12);
+    }
+    for i in 4..8 {
+        series.push(i as f64, 0.1);
+    }
+    let stats = series.stats().unwrap();
+    assert_eq!(Some(stats), SeriesStats::of(series.values()));
+    assert_eq!(stats.mean, 0.1);
+    assert!(stats.std_dev < 1e-6, "{stats:?}");
+}
+
+#[test]
+fn downsampling_keeps_the_smallest_and_largest_value_of_each_bucket() {
+    let values = [3.0, 9.0, 1.0, 5.0, 7.0, 7.0, 2.0, 8.0];
+    let series = series(16, values.iter().enumerate().map(|(i, v)| (i as f64, *v)));
+    let samples: Vec<(f64, f32)> = series.downsample(0.0, 8.0, 2).collect();
+    // in time order within each bucket
+    assert_eq!(samples, [(1.0, 9.0), (2.0, 1.0), (6.0, 2.0), (7.0, 8.0)]);
+
+    let one_each: Vec<(f64, f32)> = series.downsample(0.0, 8.0, 8).collect();
+    assert_eq!(one_each.len(), 8);
+    // a single bucket when asked for none, and nothing outside the window
+    assert_eq!(series.downsample(0.0, 8.0, 0).count(), 2);
+    assert_eq!(series.downsample(20.0, 30.0, 4).count(), 0);
+    // one sample when the smallest and largest are the same one
+    let flat: Vec<(f64, f32)> = series.downsample(4.0, 5.0, 1).collect();
+    assert_eq!(flat, [(4.0, 7.0)]);
+}
+
+// a bucket with a gap keeps the first non finite sample where it was, so lines still break there
+#[test]
+fn downsampling_keeps_gaps() {
+    let values = [1.0, f32::NAN, 4.0, f32::NAN, 2.0, 6.0, f32::NAN, f32::NAN];
+    let series = series(16, values.iter().enumerate().map(|(i, v)| (i as f64, *v)));
+    let samples: Vec<(f64, f32)> = series.downsample(0.0, 8.0, 2).collect();
+    let times: Vec<f64> = samples.iter().map(|(t, _)| *t).collect();
+    assert_eq!(times, [0.0, 1.0, 2.0, 4.0, 5.0, 6.0]);
+    assert!(samples[1].1.is_nan());
+    // the second bucket's smallest and largest values come before its gap, only its first gap is kept
+    assert_eq!(samples[3..5], [(4.0, 2.0), (5.0, 6.0)]);
+    assert!(samples[5].1.is_nan());
+}
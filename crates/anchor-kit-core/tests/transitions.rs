@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use anchor_kit_core::{
+    anchor::AnchorPosition,
+    animation::{Easing, Transition, TransitionEffect},
+    measure::TextMeasurement,
+    render::RenderList,
+    style::{SizingPolicy, Style},
+    FrameInfo, UIState,
+};
+
+// a pill with text at the top left, sliding in from `slide` units past the left edge of the screen
+fn sliding_frame(
+    text_measurement: TextMeasurement,
+    size: [u32; 2],
+    slide: Option<u32>,
+) -> RenderList {
+    let mut ui_state = UIState::new([400, 300]);
+    ui_state.set_text_measurement(text_measurement);
+    ui_state.generate_frame(FrameInfo { size: [400, 300] }, |ui| {
+        ui.anchor(AnchorPosition::TopLeft, None, |ui| {
+            ui.pill(
+                Some(Style {
+                    width: SizingPolicy::Fixed(size[0]),
+                    height: SizingPolicy::Fixed(size[1]),
+                    transition: slide.map(|slide| Transition {
+                        enter: Some(TransitionEffect {
+                            slide: Some(slide),
+                            ..Default::default()
+                        }),
+                        // long enough that the first frame is right at the start of the transition
+                        duration: Duration::from_secs(60),
+                        easing: Easing::Linear,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                |ui| ui.text("sliding", None, None),
+            );
+        });
+    })
+}
+
+// elements at the top left start past the left edge of the screen, so the part that is still off screen is cut off
+#[test]
+fn slide_in_from_top_left_is_clipped_at_the_edge() {
+    let render_list = sliding_frame(TextMeasurement::FontSize, [100, 40], Some(40));
+
+    let pill = render_list.rectangles[0];
+    assert_eq!(pill.position, [0, 0]);
+    assert!(
+        (59..=61).contains(&pill.size[0]),
+        "pill should be 40px off screen, got {:?}",
+        pill.size
+    );
+    assert_eq!(pill.size[1], 40);
+}
+
+// with proportional fonts the text can't be cut by characters, so it keeps all of them and is moved back by the
+// part that is off screen, for the renderers to clip
+#[test]
+fn sliding_text_is_offset_instead_of_cut() {
+    let resting = sliding_frame(TextMeasurement::FontSize, [100, 40], None);
+    let sliding = sliding_frame(TextMeasurement::FontSize, [100, 40], Some(40));
+    let (resting, sliding) = (&resting.text[0], &sliding.text[0]);
+
+    assert_eq!(sliding.text, "sliding");
+    assert_eq!(resting.offset, [0.0, 0.0]);
+    assert_eq!(sliding.position[1], resting.position[1]);
+    // the text starts where it would without clipping
+    let start = sliding.position[0] as f32 + sliding.offset[0];
+    assert!(
+        (start - (resting.position[0] as f32 - 40.0)).abs() < 1.0,
+        "text should start 40px left of {:?}, got {start}",
+        resting.position
+    );
+    assert!(sliding.offset[0] < 0.0);
+    // the bounds only cover the part of the text that is on screen
+    assert_eq!(sliding.position[0], 0);
+    assert!(sliding.size[0] < resting.size[0]);
+}
+
+#[test]
+fn sliding_text_in_cells_is_offset_by_whole_cells() {
+    let sliding = sliding_frame(TextMeasurement::Cells, [12, 3], Some(4));
+    let text = &sliding.text[0];
+
+    assert_eq!(text.text, "sliding");
+    assert_eq!(text.position[0], 0);
+    assert_eq!(text.offset[0].fract(), 0.0);
+    assert!(text.offset[0] < 0.0);
+}
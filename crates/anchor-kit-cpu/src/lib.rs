@@ -0,0 +1,908 @@
+use std::collections::HashMap;
+
+use anchor_kit_core::{
+    primitives::{
+        arc::Arc,
+        cell_grid::CellGrid,
+        color::Color,
+        image::Image,
+        path::{FillRule, Path},
+        polyline::{split_dashes, LineCap, LineJoin, Polyline, Stroke},
+        rectangle::Rectangle,
+        text::Text,
+    },
+    render::RenderList,
+    style::{FontFamily, FontStyle, FontWeight, Style},
+};
+use cosmic_text::{Attrs, Buffer, FontSystem, Metrics, Shaping, SwashCache};
+use image::{Rgba, RgbaImage};
+use uuid::Uuid;
+
+// software renderer that draws a render list into an image without needing a gpu (e.g. for tests, CI, or servers)
+// the rectangle maths mirrors the wgpu shader so both backends produce (almost) the same pixels
+// images are treated like an srgb surface: colours are blended in linear space and stored srgb encoded
+pub struct Renderer {
+    font_system: FontSystem,
+    swash_cache: SwashCache,
+    images: HashMap<Uuid, RgbaImage>,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Self {
+            font_system: FontSystem::new(),
+            swash_cache: SwashCache::new(),
+            images: HashMap::new(),
+        }
+    }
+
+    // system fonts can differ between machines, so load fonts directly for output that needs to be reproducible
+    pub fn load_font_data(&mut self, data: Vec<u8>) {
+        self.font_system.db_mut().load_font_data(data);
+    }
+
+    // fails if the bytes aren't an image in one of the enabled formats (png or jpeg)
+    pub fn get_image_id_from_bytes(
+        &mut self,
+        diffuse_bytes: &[u8],
+    ) -> Result<Uuid, image::ImageError> {
+        let diffuse_image = image::load_from_memory(diffuse_bytes)?;
+        Ok(self.add_image(diffuse_image.to_rgba8()))
+    }
+
+    pub fn add_image(&mut self, image: RgbaImage) -> Uuid {
+        let id = Uuid::new_v4();
+        self.images.insert(id, image);
+        id // return the id so the user can use it to render images in the generate frame pass
+    }
+
+    // creates a new image of the given size cleared to the clear colour and renders into it
+    pub fn render_to_image(
+        &mut self,
+        size: [u32; 2],
+        clear_color: Color,
+        render_list: &RenderList,
+    ) -> RgbaImage {
+        // the wgpu renderer clears with the colour values as linear values, so encode them the same way
+        let [r, g, b, a] = clear_color.to_rgba_f32();
+        let clear_pixel = [linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b), a];
+        let mut target = RgbaImage::from_pixel(size[0], size[1], Rgba(clear_pixel.map(to_u8)));
+        self.render(&mut target, render_list);
+        target
+    }
+
+    // draws on top of the existing image contents, in the same order as the wgpu renderer (rectangles, cell grids,
+    // polylines, paths, arcs, images, text)
+    pub fn render(&mut self, target: &mut RgbaImage, render_list: &RenderList) {
+        for rect in &render_list.rectangles {
+            draw_rectangle(target, rect);
+        }
+        for grid in &render_list.cell_grids {
+            draw_cell_grid(target, grid);
+        }
+        for polyline in &render_list.polylines {
+            draw_polyline(target, polyline);
+        }
+        for path in &render_list.paths {
+            draw_path(target, path);
+        }
+        for arc in &render_list.arcs {
+            draw_arc(target, arc);
+        }
+        for image in &render_list.images {
+            // skip any images that were never loaded
+            if let Some(texture) = self.images.get(&image.texture_id) {
+                draw_image(target, image, texture);
+            }
+        }
+        for text in &render_list.text {
+            self.draw_text(target, text);
+        }
+    }
+
+    fn draw_text(&mut self, target: &mut RgbaImage, text_item: &Text) {
+        let text_style = &text_item.text_style;
+        let mut text_buffer = Buffer::new(
+            &mut self.font_system,
+            Metrics::new(text_style.font_size, text_style.line_height),
+        );
+        // same as the wgpu renderer, the buffer is the size of the screen and the text bounds clip it
+        text_buffer.set_size(
+            &mut self.font_system,
+            Some(target.width() as f32),
+            Some(target.height() as f32),
+        );
+
+        let text_color = cosmic_text::Color::rgba(
+            text_style.text_color.r,
+            text_style.text_color.g,
+            text_style.text_color.b,
+            text_style.text_color.a,
+        );
+        let text_attrs = Attrs::new()
+            .family(anchor_kit_font_family_to_cosmic_text(
+                &text_style.font_family,
+            ))
+            .style(anchor_kit_font_style_to_cosmic_text(&text_style.font_style))
+            .weight(anchor_kit_font_weight_to_cosmic_text(
+                &text_style.font_weight,
+            ))
+            .color(text_color);
+        text_buffer.set_text(
+            &mut self.font_system,
+            &text_item.text,
+            &text_attrs,
+            Shaping::Advanced,
+        );
+        text_buffer.shape_until_scroll(&mut self.font_system, false);
+
+        let [x, y] = text_item.position;
+        let [w, h] = text_item.size;
+        let (left, top) = (x as i32, y as i32);
+        let (right, bottom) = ((x + w) as i32, (y + h) as i32);
+        // glyphs are placed the same way as glyphon (which rounds the line position, unlike `Buffer::draw`)
+        let width = target.width() as i32;
+        let height = target.height() as i32;
+        // the offset moves the text inside its bounds, which still clip it
+        let origin = (
+            left as f32 + text_item.offset[0],
+            top as f32 + text_item.offset[1],
+        );
+        for run in text_buffer.layout_runs() {
+            let line_y = run.line_y.round() as i32;
+            for glyph in run.glyphs.iter() {
+                let physical_glyph = glyph.physical(origin, 1.0);
+                let glyph_color = glyph.color_opt.unwrap_or(text_color);
+                self.swash_cache.with_pixels(
+                    &mut self.font_system,
+                    physical_glyph.cache_key,
+                    glyph_color,
+                    |glyph_x, glyph_y, color| {
+                        let px = physical_glyph.x + glyph_x;
+                        let py = line_y + physical_glyph.y + glyph_y;
+                        if px < left.max(0) || px >= right.min(width) {
+                            return;
+                        }
+                        if py < top.max(0) || py >= bottom.min(height) {
+                            return;
+                        }
+                        // glyphon treats text colours as srgb (and the alpha as the glyph coverage)
+                        let [r, g, b, a] = color.as_rgba();
+                        let src = [
+                            srgb_to_linear(r as f32 / 255.0),
+                            srgb_to_linear(g as f32 / 255.0),
+                            srgb_to_linear(b as f32 / 255.0),
+                            a as f32 / 255.0,
+                        ];
+                        blend_pixel(target.get_pixel_mut(px as u32, py as u32), src);
+                    },
+                );
+            }
+        }
+    }
+}
+
+// values in the same local units as the wgpu vertices (the shortest side of the rectangle is 1.0)
+struct LocalRectangle {
+    scale: [f32; 2],
+    border_radius: [f32; 4], // top-left, top-right, bottom-right, bottom-left (clockwise)
+    border_width: f32,
+}
+
+impl LocalRectangle {
+    fn new(rect: &Rectangle) -> Self {
+        let [w, h] = rect.size;
+        let scale_axis = w.min(h) as f32;
+        let mut border_radius = rect.style.border_radius;
+        for r in border_radius.iter_mut() {
+            *r = (*r / scale_axis).min(0.5) // we don't want the radius exceeding 0.5 to avoid impossible rounded corners
+        }
+        Self {
+            scale: [w as f32 / scale_axis, h as f32 / scale_axis],
+            border_radius,
+            border_width: rect.style.border_width / scale_axis,
+        }
+    }
+
+    // returns the (border, fill) coverage of a pixel, uv is 0-1 within the rectangle
+    fn coverage(&self, uv: [f32; 2]) -> (f32, f32) {
+        // center at 0,0 (modify with aspect ratio)
+        let position = [(uv[0] - 0.5) * self.scale[0], (uv[1] - 0.5) * self.scale[1]];
+        let half_size = [0.5 * self.scale[0], 0.5 * self.scale[1]];
+        let d = sdf_rounded(position, half_size, self.border_radius);
+
+        // basic anti aliasing to get smooth corners
+        let anti_aliasing = 1.0 / 50.0;
+        let alpha_mul = 1.0 - smoothstep(0.0, anti_aliasing, d);
+
+        // we need negative border width since with sdf d < 0.0 means inside the shape
+        let inner_edge = smoothstep(
+            -self.border_width - anti_aliasing,
+            -self.border_width + anti_aliasing,
+            d,
+        );
+        let border = inner_edge * (1.0 - smoothstep(-anti_aliasing, anti_aliasing, d)) * alpha_mul;
+        let fill = (1.0 - inner_edge) * alpha_mul;
+        (border, fill)
+    }
+}
+
+// sdf (signed distance function) inspired by: https://iquilezles.org/articles/distfunctions2d/
+fn sdf_rounded(position: [f32; 2], half_size: [f32; 2], border_radius: [f32; 4]) -> f32 {
+    // pick the radius for the quadrant the pixel is in (y increases downwards)
+    let radius = match (position[0] >= 0.0, position[1] < 0.0) {
+        (true, true) => border_radius[1],   // top-right
+        (true, false) => border_radius[2],  // bottom-right
+        (false, true) => border_radius[0],  // top-left
+        (false, false) => border_radius[3], // bottom-left
+    };
+
+    let q = [
+        position[0].abs() - half_size[0] + radius,
+        position[1].abs() - half_size[1] + radius,
+    ];
+    let outside = (q[0].max(0.0).powi(2) + q[1].max(0.0).powi(2)).sqrt();
+    q[0].max(q[1]).min(0.0) + outside - radius
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+// calls the closure with the pixel position and uv for every pixel center inside the rectangle
+fn for_each_pixel(
+    target: &mut RgbaImage,
+    rect: &Rectangle,
+    mut f: impl FnMut(&mut Rgba<u8>, [f32; 2]),
+) {
+    let [x, y] = rect.position;
+    let [w, h] = rect.size;
+    if w == 0 || h == 0 {
+        return;
+    }
+    let x1 = (x + w).min(target.width());
+    let y1 = (y + h).min(target.height());
+    for py in y..y1 {
+        for px in x..x1 {
+            let uv = [
+                (px - x) as f32 / w as f32 + 0.5 / w as f32,
+                (py - y) as f32 / h as f32 + 0.5 / h as f32,
+            ];
+            f(target.get_pixel_mut(px, py), uv);
+        }
+    }
+}
+
+fn draw_rectangle(target: &mut RgbaImage, rect: &Rectangle) {
+    let local = LocalRectangle::new(rect);
+    let background_color = rect.style.background_color.to_rgba_f32();
+    let border_color = rect.style.border_color.to_rgba_f32();
+    for_each_pixel(target, rect, |pixel, uv| {
+        let (border, fill) = local.coverage(uv);
+        let mut output_color = [0.0; 4];
+        for (i, c) in output_color.iter_mut().enumerate() {
+            *c = border * border_color[i] + fill * background_color[i];
+        }
+        blend_pixel(pixel, output_color);
+    });
+}
+
+// every cell is drawn like a rectangle, so rounded cells match the wgpu renderer
+fn draw_cell_grid(target: &mut RgbaImage, grid: &CellGrid) {
+    for row in 0..grid.rows {
+        for column in 0..grid.columns {
+            let color = grid.color(row, column);
+            if color.a == 0 {
+                continue;
+            }
+            let (position, size) = grid.cell_bounds(row, column);
+            let cell = Rectangle {
+                position,
+                size,
+                style: Style {
+                    background_color: color,
+                    border_radius: [grid.cell_radius; 4],
+                    ..Default::default()
+                },
+            };
+            draw_rectangle(target, &cell);
+        }
+    }
+}
+
+fn draw_image(target: &mut RgbaImage, image: &Image, texture: &RgbaImage) {
+    let local = LocalRectangle::new(&image.rectangle);
+    let border_color = image.rectangle.style.border_color.to_rgba_f32();
+    for_each_pixel(target, &image.rectangle, |pixel, uv| {
+        let (border, fill) = local.coverage(uv);
+        let sample = sample_bilinear(texture, uv);
+        let mut output_color = [0.0; 4];
+        for (i, c) in output_color.iter_mut().enumerate() {
+            *c = border * border_color[i] + fill * sample[i] * image.opacity;
+        }
+        blend_pixel(pixel, output_color);
+    });
+}
+
+// the stroke is split into simple shapes (a box per segment, plus the joins and caps) and each pixel is covered by
+// the closest one, so overlapping shapes (e.g. at joins) don't blend twice. like the wgpu tessellation, edges fade
+// out over a pixel centered on the true edge
+fn draw_polyline(target: &mut RgbaImage, polyline: &Polyline) {
+    let Some(mut area_coverage) = Coverage::new(polyline.bounds(), target) else {
+        return;
+    };
+    if polyline.points.is_empty() {
+        return;
+    }
+    let mut stroke_coverage = area_coverage.clone();
+
+    if let Some(area) = &polyline.area {
+        // each column gets the vertical overlap of the pixel with the span between the line and the baseline
+        for segment in polyline.points.windows(2) {
+            let [[ax, ay], [bx, by]] = [segment[0], segment[1]];
+            let top = ay.min(by).min(area.baseline);
+            let bottom = ay.max(by).max(area.baseline);
+            for (px, py) in area_coverage.pixels([[ax.min(bx), top], [ax.max(bx), bottom]]) {
+                let cx = px as f32 + 0.5;
+                // half open so columns on a shared point are only filled once
+                if cx < ax.min(bx) || cx >= ax.max(bx) {
+                    continue;
+                }
+                let line_y = ay + (by - ay) * (cx - ax) / (bx - ax);
+                let span = [line_y.min(area.baseline), line_y.max(area.baseline)];
+                let overlap = (span[1].min(py as f32 + 1.0) - span[0].max(py as f32)).max(0.0);
+                area_coverage.add(px, py, overlap);
+            }
+        }
+    }
+
+    let stroke = &polyline.stroke;
+    if stroke.width > 0.0 {
+        let mut shapes: Vec<StrokeShape> = vec![];
+        let mut points: Vec<[f32; 2]> = vec![];
+        polyline.for_each_dash(|dash| {
+            without_repeats(dash, &mut points);
+            stroke_shapes(&points, stroke, false, &mut shapes);
+        });
+        stroke_coverage.cover_shapes(&shapes);
+    }
+
+    if let Some(area) = &polyline.area {
+        area_coverage.blend(target, area.color.to_rgba_f32());
+    }
+    stroke_coverage.blend(target, stroke_color(stroke));
+}
+
+// the fill is covered exactly across each row and sampled on several lines down it, the stroke is made of the same
+// shapes as a polyline's
+fn draw_path(target: &mut RgbaImage, path: &Path) {
+    let Some(mut fill_coverage) = Coverage::new(path.bounds(), target) else {
+        return;
+    };
+    let mut stroke_coverage = fill_coverage.clone();
+    let mut sub_paths: Vec<(Vec<[f32; 2]>, bool)> = vec![];
+    path.flatten(|points, closed| {
+        let mut sub_path = vec![];
+        without_repeats(points, &mut sub_path);
+        if closed && sub_path.len() > 1 && sub_path.first() == sub_path.last() {
+            sub_path.pop();
+        }
+        sub_paths.push((sub_path, closed));
+    });
+
+    if let Some(fill) = path.fill {
+        fill_coverage.cover_fill(&sub_paths, path.fill_rule);
+        fill_coverage.blend(target, fill.to_rgba_f32());
+    }
+
+    let Some(stroke) = &path.stroke.filter(|stroke| stroke.width > 0.0) else {
+        return;
+    };
+    let mut shapes: Vec<StrokeShape> = vec![];
+    let mut points: Vec<[f32; 2]> = vec![];
+    for (sub_path, closed) in &sub_paths {
+        if *closed && stroke.dash.is_none() {
+            stroke_shapes(sub_path, stroke, true, &mut shapes);
+            continue;
+        }
+        // dashes on closed sub paths run all the way around, back to the start
+        let mut line = sub_path.clone();
+        if *closed {
+            line.extend(sub_path.first().copied());
+        }
+        split_dashes(&line, stroke.dash, |dash| {
+            without_repeats(dash, &mut points);
+            stroke_shapes(&points, stroke, false, &mut shapes);
+        });
+    }
+    stroke_coverage.cover_shapes(&shapes);
+    stroke_coverage.blend(target, stroke_color(stroke));
+}
+
+// the same distance based coverage as the wgpu arc shader, measured at each pixel centre
+fn draw_arc(target: &mut RgbaImage, arc: &Arc) {
+    let Some(coverage) = Coverage::new(arc.bounds(), target) else {
+        return;
+    };
+    let color = arc.color.to_rgba_f32();
+    for py in coverage.position[1]..coverage.end[1] {
+        for px in coverage.position[0]..coverage.end[0] {
+            let distance = arc.distance([px as f32 + 0.5, py as f32 + 0.5]);
+            let alpha = (0.5 - distance).clamp(0.0, 1.0);
+            if alpha > 0.0 {
+                blend_pixel(
+                    target.get_pixel_mut(px, py),
+                    [color[0], color[1], color[2], color[3] * alpha],
+                );
+            }
+        }
+    }
+}
+
+// lines thinner than a pixel are faded instead
+fn stroke_color(stroke: &Stroke) -> [f32; 4] {
+    let mut color = stroke.color.to_rgba_f32();
+    color[3] *= stroke.width.min(1.0);
+    color
+}
+
+// repeated points have no direction, so they are skipped
+fn without_repeats(points: &[[f32; 2]], out: &mut Vec<[f32; 2]>) {
+    out.clear();
+    for point in points {
+        if out.last() != Some(point) {
+            out.push(*point);
+        }
+    }
+}
+
+// how much of each pixel in an area of the target is covered by a shape, from 0 to 1
+#[derive(Clone)]
+struct Coverage {
+    position: [u32; 2],
+    end: [u32; 2], // exclusive, clipped to the target
+    values: Vec<f32>,
+}
+
+impl Coverage {
+    // returns none if the bounds are entirely outside the target
+    fn new((position, size): ([u32; 2], [u32; 2]), target: &RgbaImage) -> Option<Self> {
+        let end = [
+            (position[0] + size[0]).min(target.width()),
+            (position[1] + size[1]).min(target.height()),
+        ];
+        if end[0] <= position[0] || end[1] <= position[1] {
+            return None;
+        }
+        let len = (end[0] - position[0]) * (end[1] - position[1]);
+        Some(Self {
+            position,
+            end,
+            values: vec![0.0; len as usize],
+        })
+    }
+
+    fn index(&self, px: u32, py: u32) -> usize {
+        ((py - self.position[1]) * (self.end[0] - self.position[0]) + (px - self.position[0]))
+            as usize
+    }
+
+    // pixels inside both the coverage area and the bounds (min and max corners)
+    fn pixels(&self, [min, max]: [[f32; 2]; 2]) -> impl Iterator<Item = (u32, u32)> {
+        let [x, y] = self.position;
+        let [x1, y1] = self.end;
+        let px0 = (min[0].floor().max(x as f32) as u32).min(x1);
+        let py0 = (min[1].floor().max(y as f32) as u32).min(y1);
+        let px1 = (max[0].ceil().max(0.0) as u32).clamp(px0, x1);
+        let py1 = (max[1].ceil().max(0.0) as u32).clamp(py0, y1);
+        (py0..py1).flat_map(move |py| (px0..px1).map(move |px| (px, py)))
+    }
+
+    fn add(&mut self, px: u32, py: u32, coverage: f32) {
+        let index = self.index(px, py);
+        self.values[index] = (self.values[index] + coverage).min(1.0);
+    }
+
+    fn cover_shapes(&mut self, shapes: &[StrokeShape]) {
+        for shape in shapes {
+            for (px, py) in self.pixels(shape.bounds()) {
+                let distance = shape.distance([px as f32 + 0.5, py as f32 + 0.5]);
+                let coverage = (0.5 - distance).clamp(0.0, 1.0);
+                let index = self.index(px, py);
+                self.values[index] = self.values[index].max(coverage);
+            }
+        }
+    }
+
+    // every sub path is treated as closed, like svg does when filling
+    fn cover_fill(&mut self, sub_paths: &[(Vec<[f32; 2]>, bool)], fill_rule: FillRule) {
+        const SAMPLES: u32 = 16; // lines per pixel row
+        let [x, y] = self.position;
+        let [x1, y1] = self.end;
+        let mut crossings: Vec<(f32, i32)> = vec![];
+        for py in y..y1 {
+            for sample in 0..SAMPLES {
+                let sample_y = py as f32 + (sample as f32 + 0.5) / SAMPLES as f32;
+                crossings.clear();
+                for (points, _) in sub_paths {
+                    for (i, a) in points.iter().enumerate() {
+                        let b = points[(i + 1) % points.len()];
+                        if (a[1] <= sample_y) == (b[1] <= sample_y) {
+                            continue;
+                        }
+                        let cross_x = a[0] + (b[0] - a[0]) * (sample_y - a[1]) / (b[1] - a[1]);
+                        crossings.push((cross_x, if b[1] > a[1] { 1 } else { -1 }));
+                    }
+                }
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+                // each span between crossings that is inside adds its exact overlap with the pixels it touches
+                let mut winding = 0;
+                for pair in crossings.windows(2) {
+                    winding += pair[0].1;
+                    if !fill_rule.contains(winding) {
+                        continue;
+                    }
+                    let [start, end] = [pair[0].0.max(x as f32), pair[1].0.min(x1 as f32)];
+                    if end <= start {
+                        continue;
+                    }
+                    for px in start.floor() as u32..(end.ceil() as u32).min(x1) {
+                        let overlap = end.min(px as f32 + 1.0) - start.max(px as f32);
+                        self.add(px, py, overlap.max(0.0) / SAMPLES as f32);
+                    }
+                }
+            }
+        }
+    }
+
+    fn blend(&self, target: &mut RgbaImage, color: [f32; 4]) {
+        if color[3] <= 0.0 {
+            return;
+        }
+        for py in self.position[1]..self.end[1] {
+            for px in self.position[0]..self.end[0] {
+                let coverage = self.values[self.index(px, py)];
+                if coverage > 0.0 {
+                    blend_pixel(
+                        target.get_pixel_mut(px, py),
+                        [color[0], color[1], color[2], color[3] * coverage],
+                    );
+                }
+            }
+        }
+    }
+}
+
+enum StrokeShape {
+    // a segment from `start` along `direction`, covering `from` to `to` along it and `half_width` to either side
+    Segment {
+        start: [f32; 2],
+        direction: [f32; 2],
+        from: f32,
+        to: f32,
+        half_width: f32,
+    },
+    Circle {
+        center: [f32; 2],
+        radius: f32,
+    },
+    ConvexPolygon(Vec<[f32; 2]>),
+}
+
+impl StrokeShape {
+    // signed distance from the edge of the shape, negative inside
+    fn distance(&self, point: [f32; 2]) -> f32 {
+        match self {
+            StrokeShape::Segment {
+                start,
+                direction,
+                from,
+                to,
+                half_width,
+            } => {
+                let offset = [point[0] - start[0], point[1] - start[1]];
+                let along = offset[0] * direction[0] + offset[1] * direction[1];
+                let across = offset[0] * -direction[1] + offset[1] * direction[0];
+                let outside = [(from - along).max(along - to), across.abs() - half_width];
+                let outside_length =
+                    (outside[0].max(0.0).powi(2) + outside[1].max(0.0).powi(2)).sqrt();
+                outside_length + outside[0].max(outside[1]).min(0.0)
+            }
+            StrokeShape::Circle { center, radius } => {
+                ((point[0] - center[0]).powi(2) + (point[1] - center[1]).powi(2)).sqrt() - radius
+            }
+            // the furthest edge line, which is exact along the edges and slightly rounds the corners
+            StrokeShape::ConvexPolygon(points) => {
+                let count = points.len() as f32;
+                let center = points.iter().fold([0.0, 0.0], |c, p| {
+                    [c[0] + p[0] / count, c[1] + p[1] / count]
+                });
+                let mut distance = f32::MIN;
+                for (i, a) in points.iter().enumerate() {
+                    let b = points[(i + 1) % points.len()];
+                    let edge = [b[0] - a[0], b[1] - a[1]];
+                    let length = (edge[0] * edge[0] + edge[1] * edge[1]).sqrt();
+                    if length <= f32::EPSILON {
+                        continue;
+                    }
+                    let mut normal = [edge[1] / length, -edge[0] / length];
+                    if normal[0] * (center[0] - a[0]) + normal[1] * (center[1] - a[1]) > 0.0 {
+                        normal = [-normal[0], -normal[1]];
+                    }
+                    distance =
+                        distance.max(normal[0] * (point[0] - a[0]) + normal[1] * (point[1] - a[1]));
+                }
+                distance
+            }
+        }
+    }
+
+    // area that can be covered, including the faded edge
+    fn bounds(&self) -> [[f32; 2]; 2] {
+        let (mut min, mut max) = ([f32::MAX; 2], [f32::MIN; 2]);
+        let mut include = |[x, y]: [f32; 2], extent: f32| {
+            min = [min[0].min(x - extent), min[1].min(y - extent)];
+            max = [max[0].max(x + extent), max[1].max(y + extent)];
+        };
+        match self {
+            StrokeShape::Segment {
+                start,
+                direction,
+                from,
+                to,
+                half_width,
+            } => {
+                for distance in [from, to] {
+                    let point = [
+                        start[0] + direction[0] * distance,
+                        start[1] + direction[1] * distance,
+                    ];
+                    include(point, half_width + 1.0);
+                }
+            }
+            StrokeShape::Circle { center, radius } => include(*center, radius + 1.0),
+            StrokeShape::ConvexPolygon(points) => {
+                for point in points {
+                    include(*point, 1.0);
+                }
+            }
+        }
+        [min, max]
+    }
+}
+
+// same shapes as the wgpu tessellation: segments are cut square at joins, and the joins fill the outside corner
+fn stroke_shapes(
+    points: &[[f32; 2]],
+    stroke: &Stroke,
+    closed: bool,
+    shapes: &mut Vec<StrokeShape>,
+) {
+    let half_width = (stroke.width / 2.0).max(0.5); // thin lines are drawn a pixel wide and faded
+    let cap_extension = match stroke.cap {
+        LineCap::Square if !closed => half_width,
+        _ => 0.0,
+    };
+    if points.len() == 1 {
+        // a single point is only visible with round or square caps, e.g. for dotted lines
+        shapes.push(match stroke.cap {
+            LineCap::Butt => return,
+            LineCap::Square => StrokeShape::Segment {
+                start: points[0],
+                direction: [1.0, 0.0],
+                from: -half_width,
+                to: half_width,
+                half_width,
+            },
+            LineCap::Round => StrokeShape::Circle {
+                center: points[0],
+                radius: half_width,
+            },
+        });
+        return;
+    }
+
+    // closed lines also have a segment back to the start, and a join there instead of caps
+    let segment_count = match closed {
+        true => points.len(),
+        false => points.len().saturating_sub(1),
+    };
+    let mut first: Option<[f32; 2]> = None;
+    let mut previous: Option<[f32; 2]> = None;
+    for i in 0..segment_count {
+        let [a, b] = [points[i], points[(i + 1) % points.len()]];
+        let edge = [b[0] - a[0], b[1] - a[1]];
+        let length = (edge[0] * edge[0] + edge[1] * edge[1]).sqrt();
+        let direction = [edge[0] / length, edge[1] / length];
+        shapes.push(StrokeShape::Segment {
+            start: a,
+            direction,
+            from: if i == 0 { -cap_extension } else { 0.0 },
+            to: if i + 1 == segment_count {
+                length + cap_extension
+            } else {
+                length
+            },
+            half_width,
+        });
+        if let Some(previous) = previous {
+            if let Some(join) = join_shape(a, previous, direction, stroke.join, half_width) {
+                shapes.push(join);
+            }
+        }
+        first = first.or(Some(direction));
+        previous = Some(direction);
+    }
+    if let (true, Some(first), Some(last)) = (closed, first, previous) {
+        if let Some(join) = join_shape(points[0], last, first, stroke.join, half_width) {
+            shapes.push(join);
+        }
+    }
+    if stroke.cap == LineCap::Round && !closed {
+        for point in [points[0], points[points.len() - 1]] {
+            shapes.push(StrokeShape::Circle {
+                center: point,
+                radius: half_width,
+            });
+        }
+    }
+}
+
+fn join_shape(
+    point: [f32; 2],
+    previous: [f32; 2],
+    next: [f32; 2],
+    join: LineJoin,
+    half_width: f32,
+) -> Option<StrokeShape> {
+    let turn = previous[0] * next[1] - previous[1] * next[0];
+    if turn.abs() <= 1e-4 && previous[0] * next[0] + previous[1] * next[1] > 0.0 {
+        return None; // straight through, the segments already meet
+    }
+    if join == LineJoin::Round {
+        return Some(StrokeShape::Circle {
+            center: point,
+            radius: half_width,
+        });
+    }
+
+    // the outside of the corner is opposite to the direction the line turns
+    let side = if turn > 0.0 { -1.0 } else { 1.0 };
+    let previous_normal = [-previous[1] * side, previous[0] * side];
+    let next_normal = [-next[1] * side, next[0] * side];
+    let offset = |p: [f32; 2], d: [f32; 2], s: f32| [p[0] + d[0] * s, p[1] + d[1] * s];
+
+    // the polygon reaches a pixel back into both segments, so the seam between them is fully covered
+    let mut polygon = vec![
+        offset(point, previous, -1.0),
+        offset(offset(point, previous, -1.0), previous_normal, half_width),
+        offset(point, previous_normal, half_width),
+    ];
+    let sum = [
+        previous_normal[0] + next_normal[0],
+        previous_normal[1] + next_normal[1],
+    ];
+    let sum_length = (sum[0] * sum[0] + sum[1] * sum[1]).sqrt();
+    if join == LineJoin::Miter && sum_length > 1e-3 {
+        let miter = [sum[0] / sum_length, sum[1] / sum_length];
+        let miter_length =
+            1.0 / (miter[0] * previous_normal[0] + miter[1] * previous_normal[1]).max(1e-3);
+        if miter_length <= Stroke::MITER_LIMIT {
+            polygon.push(offset(point, miter, miter_length * half_width));
+        }
+    }
+    polygon.extend([
+        offset(point, next_normal, half_width),
+        offset(offset(point, next, 1.0), next_normal, half_width),
+        offset(point, next, 1.0),
+    ]);
+    Some(StrokeShape::ConvexPolygon(polygon))
+}
+
+// linear filtering with clamp to edge, same as the wgpu image sampler
+// textures are srgb, so texels are converted to linear before filtering like the gpu does
+fn sample_bilinear(texture: &RgbaImage, uv: [f32; 2]) -> [f32; 4] {
+    let (w, h) = texture.dimensions();
+    if w == 0 || h == 0 {
+        return [0.0; 4];
+    }
+    let x = (uv[0] * w as f32 - 0.5).clamp(0.0, (w - 1) as f32);
+    let y = (uv[1] * h as f32 - 0.5).clamp(0.0, (h - 1) as f32);
+    let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+    let (x1, y1) = ((x0 + 1).min(w - 1), (y0 + 1).min(h - 1));
+    let (tx, ty) = (x - x0 as f32, y - y0 as f32);
+
+    let texel = |px: u32, py: u32, i: usize| {
+        let value = texture.get_pixel(px, py)[i] as f32 / 255.0;
+        if i < 3 {
+            srgb_to_linear(value)
+        } else {
+            value
+        }
+    };
+    let mut sample = [0.0; 4];
+    for (i, s) in sample.iter_mut().enumerate() {
+        let top = texel(x0, y0, i) * (1.0 - tx) + texel(x1, y0, i) * tx;
+        let bottom = texel(x0, y1, i) * (1.0 - tx) + texel(x1, y1, i) * tx;
+        *s = top * (1.0 - ty) + bottom * ty;
+    }
+    sample
+}
+
+// standard (non premultiplied) alpha blending, the same as wgpu::BlendState::ALPHA_BLENDING on an srgb target
+fn blend_pixel(pixel: &mut Rgba<u8>, src: [f32; 4]) {
+    let src_a = src[3].clamp(0.0, 1.0);
+    if src_a <= 0.0 {
+        return;
+    }
+    let dst = pixel.0.map(|c| c as f32 / 255.0);
+    let mut out = [0.0; 4];
+    for i in 0..3 {
+        let blended = src[i] * src_a + srgb_to_linear(dst[i]) * (1.0 - src_a);
+        out[i] = linear_to_srgb(blended);
+    }
+    out[3] = src_a + dst[3] * (1.0 - src_a);
+    pixel.0 = out.map(to_u8);
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    let value = value.clamp(0.0, 1.0);
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn anchor_kit_font_family_to_cosmic_text(font_family: &FontFamily) -> cosmic_text::Family<'_> {
+    match font_family {
+        FontFamily::Name(name) => cosmic_text::Family::Name(name),
+        FontFamily::Serif => cosmic_text::Family::Serif,
+        FontFamily::SansSerif => cosmic_text::Family::SansSerif,
+        FontFamily::Cursive => cosmic_text::Family::Cursive,
+        FontFamily::Fantasy => cosmic_text::Family::Fantasy,
+        FontFamily::Monospace => cosmic_text::Family::Monospace,
+    }
+}
+
+fn anchor_kit_font_weight_to_cosmic_text(font_weight: &FontWeight) -> cosmic_text::Weight {
+    match font_weight {
+        FontWeight::Thin => cosmic_text::Weight::THIN,
+        FontWeight::ExtraLight => cosmic_text::Weight::EXTRA_LIGHT,
+        FontWeight::Light => cosmic_text::Weight::LIGHT,
+        FontWeight::Normal => cosmic_text::Weight::NORMAL,
+        FontWeight::Medium => cosmic_text::Weight::MEDIUM,
+        FontWeight::SemiBold => cosmic_text::Weight::SEMIBOLD,
+        FontWeight::Bold => cosmic_text::Weight::BOLD,
+        FontWeight::ExtraBold => cosmic_text::Weight::EXTRA_BOLD,
+        FontWeight::Black => cosmic_text::Weight::BLACK,
+    }
+}
+
+fn anchor_kit_font_style_to_cosmic_text(font_style: &FontStyle) -> cosmic_text::Style {
+    match font_style {
+        FontStyle::Normal => cosmic_text::Style::Normal,
+        FontStyle::Italic => cosmic_text::Style::Italic,
+        FontStyle::Oblique => cosmic_text::Style::Oblique,
+    }
+}
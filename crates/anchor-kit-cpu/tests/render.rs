@@ -0,0 +1,250 @@
+use anchor_kit_core::{
+    primitives::{
+        arc::Arc,
+        cell_grid::CellGrid,
+        color::Color,
+        path::{FillRule, Path, PathCommand},
+        polyline::{Polyline, Stroke},
+        rectangle::Rectangle,
+        text::Text,
+    },
+    render::RenderList,
+    style::{FontFamily, Style, TextStyle},
+};
+use anchor_kit_cpu::Renderer;
+use image::RgbaImage;
+
+// pure colours are the same encoded as srgb or linear, so they can be checked exactly (up to anti-aliasing)
+const CLEAR: Color = Color::BLACK;
+
+// fira mono is loaded directly so the glyphs don't depend on the fonts installed on the machine
+fn renderer() -> Renderer {
+    let mut renderer = Renderer::new();
+    renderer.load_font_data(include_bytes!("fonts/FiraMono-Medium.ttf").to_vec());
+    renderer
+}
+
+fn render(render_list: RenderList) -> RgbaImage {
+    renderer().render_to_image([100, 100], CLEAR, &render_list)
+}
+
+fn assert_pixel(image: &RgbaImage, [x, y]: [u32; 2], color: Color) {
+    let pixel = image.get_pixel(x, y).0;
+    let expected = [color.r, color.g, color.b, color.a];
+    let close = pixel.iter().zip(expected).all(|(a, b)| a.abs_diff(b) <= 2);
+    assert!(
+        close,
+        "pixel at {x}, {y} is {pixel:?}, expected {expected:?}"
+    );
+}
+
+fn is_clear(image: &RgbaImage, [x, y]: [u32; 2]) -> bool {
+    image.get_pixel(x, y).0 == [0, 0, 0, 255]
+}
+
+#[test]
+fn rectangle_fills_its_bounds() {
+    let image = render(RenderList {
+        rectangles: vec![Rectangle::new(
+            [10, 20],
+            [30, 40],
+            Some(Style {
+                background_color: Color::RED,
+                ..Default::default()
+            }),
+        )],
+        ..Default::default()
+    });
+    assert_pixel(&image, [10, 20], Color::RED);
+    assert_pixel(&image, [39, 59], Color::RED);
+    assert!(is_clear(&image, [9, 20]));
+    assert!(is_clear(&image, [40, 20]));
+    assert!(is_clear(&image, [10, 60]));
+}
+
+#[test]
+fn rounded_border_is_inside_the_rectangle() {
+    let image = render(RenderList {
+        rectangles: vec![Rectangle::new(
+            [10, 10],
+            [80, 60],
+            Some(Style {
+                background_color: Color::BLUE,
+                border_color: Color::LIME,
+                border_width: 4.0,
+                border_radius: [20.0; 4],
+                ..Default::default()
+            }),
+        )],
+        ..Default::default()
+    });
+    // the corners are cut off by the radius
+    assert!(is_clear(&image, [10, 10]));
+    assert!(is_clear(&image, [89, 69]));
+    // the border runs along the edges, the fill is inside it
+    assert_pixel(&image, [50, 11], Color::LIME);
+    assert_pixel(&image, [11, 40], Color::LIME);
+    assert_pixel(&image, [50, 40], Color::BLUE);
+}
+
+#[test]
+fn polyline_strokes_between_its_points() {
+    let image = render(RenderList {
+        polylines: vec![Polyline {
+            points: vec![[10.0, 50.0], [90.0, 50.0]],
+            stroke: Stroke::new(4.0, Color::RED),
+            area: None,
+        }],
+        ..Default::default()
+    });
+    assert_pixel(&image, [50, 49], Color::RED);
+    assert_pixel(&image, [50, 50], Color::RED);
+    assert!(is_clear(&image, [50, 45]));
+    assert!(is_clear(&image, [50, 54]));
+    // butt caps end at the points
+    assert!(is_clear(&image, [5, 50]));
+    assert!(is_clear(&image, [95, 50]));
+}
+
+#[test]
+fn path_fill_rule_cuts_holes() {
+    let square = |x0: f32, x1: f32| {
+        [
+            PathCommand::MoveTo([x0, x0]),
+            PathCommand::LineTo([x1, x0]),
+            PathCommand::LineTo([x1, x1]),
+            PathCommand::LineTo([x0, x1]),
+            PathCommand::Close,
+        ]
+    };
+    let path = |fill_rule| Path {
+        commands: square(10.0, 90.0)
+            .into_iter()
+            .chain(square(30.0, 70.0))
+            .collect(),
+        fill: Some(Color::LIME),
+        fill_rule,
+        stroke: None,
+    };
+    let non_zero = render(RenderList {
+        paths: vec![path(FillRule::NonZero)],
+        ..Default::default()
+    });
+    assert_pixel(&non_zero, [20, 50], Color::LIME);
+    assert_pixel(&non_zero, [50, 50], Color::LIME);
+    assert!(is_clear(&non_zero, [5, 50]));
+
+    let even_odd = render(RenderList {
+        paths: vec![path(FillRule::EvenOdd)],
+        ..Default::default()
+    });
+    assert_pixel(&even_odd, [20, 50], Color::LIME);
+    assert!(is_clear(&even_odd, [50, 50]));
+}
+
+#[test]
+fn arc_covers_its_sweep() {
+    // the right half of a ring, clockwise from 12 o'clock
+    let image = render(RenderList {
+        arcs: vec![Arc {
+            center: [50.0, 50.0],
+            radius: 30.0,
+            thickness: 10.0,
+            start_angle: 0.0,
+            end_angle: 180.0,
+            color: Color::RED,
+            rounded_caps: false,
+        }],
+        ..Default::default()
+    });
+    assert_pixel(&image, [80, 50], Color::RED);
+    assert_pixel(&image, [50, 22], Color::RED);
+    assert!(is_clear(&image, [50, 50])); // the middle of the ring
+    assert!(is_clear(&image, [20, 50])); // the left half isn't covered
+    assert!(is_clear(&image, [95, 50]));
+}
+
+#[test]
+fn cell_grid_colours_each_cell() {
+    let image = render(RenderList {
+        cell_grids: vec![CellGrid {
+            position: [10, 10],
+            size: [82, 82],
+            rows: 2,
+            columns: 2,
+            gap: 2,
+            cell_radius: 0.0,
+            colors: vec![Color::RED, Color::LIME, Color::BLUE, Color::TRANSPARENT],
+        }],
+        ..Default::default()
+    });
+    assert_pixel(&image, [30, 30], Color::RED);
+    assert_pixel(&image, [70, 30], Color::LIME);
+    assert_pixel(&image, [30, 70], Color::BLUE);
+    assert!(is_clear(&image, [70, 70])); // transparent cells are left empty
+    assert!(is_clear(&image, [51, 30])); // the gap between the columns
+}
+
+fn text(position: [u32; 2], size: [u32; 2], offset: [f32; 2]) -> Text {
+    Text {
+        text: "MMMM".to_string(),
+        position,
+        size,
+        text_style: TextStyle {
+            font_family: FontFamily::Monospace,
+            font_size: 20.0,
+            line_height: 24.0,
+            text_color: Color::WHITE,
+            ..Default::default()
+        },
+        offset,
+    }
+}
+
+// every drawn pixel as (x, y)
+fn drawn_pixels(image: &RgbaImage) -> Vec<[u32; 2]> {
+    image
+        .enumerate_pixels()
+        .filter(|(_, _, pixel)| pixel.0 != [0, 0, 0, 255])
+        .map(|(x, y, _)| [x, y])
+        .collect()
+}
+
+#[test]
+fn text_is_drawn_inside_its_bounds() {
+    let image = render(RenderList {
+        text: vec![text([10, 10], [60, 24], [0.0, 0.0])],
+        ..Default::default()
+    });
+    let drawn = drawn_pixels(&image);
+    assert!(drawn.len() > 50, "expected glyphs to be drawn");
+    assert!(drawn
+        .iter()
+        .all(|[x, y]| (10..70).contains(x) && (10..34).contains(y)));
+}
+
+// a negative offset moves the text left behind its bounds, so only the part still inside them is drawn
+#[test]
+fn text_offset_is_clipped_to_its_bounds() {
+    let whole = render(RenderList {
+        text: vec![text([0, 10], [60, 24], [0.0, 0.0])],
+        ..Default::default()
+    });
+    let shifted = render(RenderList {
+        text: vec![text([0, 10], [60, 24], [-24.0, 0.0])],
+        ..Default::default()
+    });
+    // the same glyphs, moved left and cut at the left edge of the bounds
+    let expected: Vec<[u32; 2]> = drawn_pixels(&whole)
+        .into_iter()
+        .filter(|[x, _]| *x >= 24)
+        .map(|[x, y]| [x - 24, y])
+        .collect();
+    assert_eq!(drawn_pixels(&shifted), expected);
+}
+
+#[test]
+fn invalid_image_bytes_are_an_error() {
+    let mut renderer = Renderer::new();
+    assert!(renderer.get_image_id_from_bytes(b"not an image").is_err());
+}
@@ -0,0 +1,491 @@
+use std::{fmt::Write as _, io};
+
+use anchor_kit_core::{
+    primitives::{
+        arc::Arc,
+        cell_grid::CellGrid,
+        color::Color,
+        image::Image,
+        path::{winding_number, Path},
+        polyline::{split_dashes, Polyline},
+        rectangle::Rectangle,
+        text::Text,
+    },
+    render::RenderList,
+    style::{FontStyle, FontWeight},
+};
+
+// renders a render list to a grid of terminal cells
+// layouts should be generated with `UIState::set_text_measurement(TextMeasurement::Cells)` so that all positions and
+// sizes in the render list are in cells (columns, rows) instead of pixels
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cell {
+    pub symbol: char,
+    pub fg: Option<Color>, // None uses the terminal's default colours
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            symbol: ' ',
+            fg: None,
+            bg: None,
+            bold: false,
+            italic: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Buffer {
+    size: [u32; 2], // columns, rows
+    cells: Vec<Cell>,
+}
+
+impl Buffer {
+    pub fn new(size: [u32; 2]) -> Self {
+        Self {
+            size,
+            cells: vec![Cell::default(); cell_count(size)],
+        }
+    }
+
+    pub fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    // resets all cells, keeping the buffer's allocation
+    pub fn resize(&mut self, size: [u32; 2]) {
+        self.size = size;
+        self.cells.clear();
+        self.cells.resize(cell_count(size), Cell::default());
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::default());
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> Option<&Cell> {
+        if x >= self.size[0] || y >= self.size[1] {
+            return None;
+        }
+        self.cells.get(cell_index(self.size, x, y))
+    }
+
+    pub fn get_mut(&mut self, x: u32, y: u32) -> Option<&mut Cell> {
+        if x >= self.size[0] || y >= self.size[1] {
+            return None;
+        }
+        self.cells.get_mut(cell_index(self.size, x, y))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.size[0].max(1) as usize)
+    }
+
+    // draws the render list on top of the current buffer contents, in the same order as the other renderers
+    pub fn render(&mut self, render_list: &RenderList) {
+        for rect in &render_list.rectangles {
+            self.draw_rectangle(rect);
+        }
+        for grid in &render_list.cell_grids {
+            self.draw_cell_grid(grid);
+        }
+        for polyline in &render_list.polylines {
+            self.draw_polyline(polyline);
+        }
+        for path in &render_list.paths {
+            self.draw_path(path);
+        }
+        for arc in &render_list.arcs {
+            self.draw_arc(arc);
+        }
+        for image in &render_list.images {
+            self.draw_image(image);
+        }
+        for text in &render_list.text {
+            self.draw_text(text);
+        }
+    }
+
+    // lines of characters without any colours, useful for golden tests
+    pub fn to_plain_string(&self) -> String {
+        let mut out = String::with_capacity(self.cells.len() + self.size[1] as usize);
+        for row in self.rows() {
+            out.extend(row.iter().map(|c| c.symbol));
+            out.push('\n');
+        }
+        out
+    }
+
+    // lines of characters with truecolor ansi escape codes, ready to be printed to a terminal
+    pub fn to_ansi_string(&self) -> String {
+        let mut out = String::new();
+        for row in self.rows() {
+            let mut current = Cell::default();
+            for cell in row {
+                write_style_change(&mut out, &current, cell);
+                out.push(cell.symbol);
+                current = *cell;
+            }
+            if current != Cell::default() {
+                out.push_str("\x1b[0m");
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn write_ansi(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        writer.write_all(self.to_ansi_string().as_bytes())
+    }
+
+    fn draw_rectangle(&mut self, rect: &Rectangle) {
+        let [x, y] = rect.position;
+        let [w, h] = rect.size;
+        if w == 0 || h == 0 {
+            return;
+        }
+
+        let background_color = rect.style.background_color;
+        if background_color.a > 0 {
+            for cy in y..y + h {
+                for cx in x..x + w {
+                    if let Some(cell) = self.get_mut(cx, cy) {
+                        cell.bg = Some(blend(cell.bg, background_color));
+                    }
+                }
+            }
+        }
+
+        // borders need at least a 2x2 area for the corners
+        if rect.style.border_width > 0.0 && rect.style.border_color.a > 0 && w >= 2 && h >= 2 {
+            self.draw_border(rect);
+        }
+    }
+
+    fn draw_border(&mut self, rect: &Rectangle) {
+        let [x0, y0] = rect.position;
+        let [w, h] = rect.size;
+        let [x1, y1] = [x0 + w - 1, y0 + h - 1];
+        let border_color = Some(rect.style.border_color);
+
+        // border radius is clockwise from the top-left, like in the other renderers
+        let corner = |radius: f32, rounded: char, square: char| match radius > 0.0 {
+            true => rounded,
+            false => square,
+        };
+        let [tl, tr, br, bl] = rect.style.border_radius;
+        let mut set = |cx: u32, cy: u32, symbol: char| {
+            if let Some(cell) = self.get_mut(cx, cy) {
+                cell.symbol = symbol;
+                cell.fg = border_color;
+                cell.bold = false;
+                cell.italic = false;
+            }
+        };
+        for cx in x0 + 1..x1 {
+            set(cx, y0, '─');
+            set(cx, y1, '─');
+        }
+        for cy in y0 + 1..y1 {
+            set(x0, cy, '│');
+            set(x1, cy, '│');
+        }
+        set(x0, y0, corner(tl, '╭', '┌'));
+        set(x1, y0, corner(tr, '╮', '┐'));
+        set(x1, y1, corner(br, '╯', '┘'));
+        set(x0, y1, corner(bl, '╰', '└'));
+    }
+
+    // lines are plotted with braille dots, which gives each cell a 2x4 grid of points
+    fn draw_polyline(&mut self, polyline: &Polyline) {
+        let ([x, y], [w, h]) = polyline.bounds();
+        if w == 0 || h == 0 {
+            return;
+        }
+
+        // the area is filled by cell, for cells whose center is between the line and the baseline
+        if let Some(area) = polyline.area.filter(|area| area.color.a > 0) {
+            for segment in polyline.points.windows(2) {
+                let [[ax, ay], [bx, by]] = [segment[0], segment[1]];
+                let (min_x, max_x) = (ax.min(bx), ax.max(bx));
+                for cx in min_x.floor().max(0.0) as u32..max_x.ceil().max(0.0) as u32 {
+                    let center_x = cx as f32 + 0.5;
+                    if center_x < min_x || center_x >= max_x {
+                        continue;
+                    }
+                    let line_y = ay + (by - ay) * (center_x - ax) / (bx - ax);
+                    let (top, bottom) = (line_y.min(area.baseline), line_y.max(area.baseline));
+                    for cy in top.floor().max(0.0) as u32..bottom.ceil().max(0.0) as u32 {
+                        let center_y = cy as f32 + 0.5;
+                        if center_y < top || center_y >= bottom {
+                            continue;
+                        }
+                        if let Some(cell) = self.get_mut(cx, cy) {
+                            cell.bg = Some(blend(cell.bg, area.color));
+                        }
+                    }
+                }
+            }
+        }
+
+        let stroke = &polyline.stroke;
+        if stroke.width <= 0.0 || stroke.color.a == 0 {
+            return;
+        }
+        // joins and caps are too small to show at this resolution, but dashes still are
+        let mut dots = Dots::new([x, y], [w, h]);
+        polyline.for_each_dash(|points| dots.plot(points));
+        self.draw_dots(&dots, stroke.color);
+    }
+
+    // cells whose center is inside the fill get its background, the stroke is plotted like a polyline
+    fn draw_path(&mut self, path: &Path) {
+        let ([x, y], [w, h]) = path.bounds();
+        if w == 0 || h == 0 {
+            return;
+        }
+        let mut sub_paths: Vec<(Vec<[f32; 2]>, bool)> = vec![];
+        path.flatten(|points, closed| sub_paths.push((points.to_vec(), closed)));
+
+        if let Some(fill) = path.fill.filter(|fill| fill.a > 0) {
+            for cy in y..y + h {
+                for cx in x..x + w {
+                    let center = [cx as f32 + 0.5, cy as f32 + 0.5];
+                    let winding = winding_number(
+                        sub_paths.iter().map(|(points, _)| points.as_slice()),
+                        center,
+                    );
+                    if !path.fill_rule.contains(winding) {
+                        continue;
+                    }
+                    if let Some(cell) = self.get_mut(cx, cy) {
+                        cell.bg = Some(blend(cell.bg, fill));
+                    }
+                }
+            }
+        }
+
+        let Some(stroke) = path
+            .stroke
+            .filter(|stroke| stroke.width > 0.0 && stroke.color.a > 0)
+        else {
+            return;
+        };
+        let mut dots = Dots::new([x, y], [w, h]);
+        for (points, closed) in &mut sub_paths {
+            if *closed {
+                points.extend(points.first().copied());
+            }
+            split_dashes(points, stroke.dash, |points| dots.plot(points));
+        }
+        self.draw_dots(&dots, stroke.color);
+    }
+
+    // each grid cell colours the terminal cells it covers, corner radii are too small to show
+    fn draw_cell_grid(&mut self, grid: &CellGrid) {
+        for row in 0..grid.rows {
+            for column in 0..grid.columns {
+                let color = grid.color(row, column);
+                if color.a == 0 {
+                    continue;
+                }
+                let ([x, y], [w, h]) = grid.cell_bounds(row, column);
+                for cy in y..y + h {
+                    for cx in x..x + w {
+                        if let Some(cell) = self.get_mut(cx, cy) {
+                            cell.bg = Some(blend(cell.bg, color));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // cells whose center is inside the arc get its colour as the background, like path fills
+    fn draw_arc(&mut self, arc: &Arc) {
+        if arc.color.a == 0 {
+            return;
+        }
+        let ([x, y], [w, h]) = arc.bounds();
+        for cy in y..y + h {
+            for cx in x..x + w {
+                if arc.distance([cx as f32 + 0.5, cy as f32 + 0.5]) > 0.0 {
+                    continue;
+                }
+                if let Some(cell) = self.get_mut(cx, cy) {
+                    cell.bg = Some(blend(cell.bg, arc.color));
+                }
+            }
+        }
+    }
+
+    fn draw_dots(&mut self, dots: &Dots, color: Color) {
+        let [x, y] = dots.position;
+        let w = dots.size[0];
+        for (i, bits) in dots.bits.iter().enumerate() {
+            if *bits == 0 {
+                continue;
+            }
+            let (cx, cy) = (x + i as u32 % w, y + i as u32 / w);
+            if let Some(cell) = self.get_mut(cx, cy) {
+                cell.symbol = char::from_u32(0x2800 + *bits as u32).unwrap_or(' ');
+                cell.fg = Some(blend(cell.bg, color));
+                cell.bold = false;
+                cell.italic = false;
+            }
+        }
+    }
+
+    // terminals can't show images, so we just shade the area the image would take up
+    fn draw_image(&mut self, image: &Image) {
+        let [x, y] = image.rectangle.position;
+        let [w, h] = image.rectangle.size;
+        for cy in y..y + h {
+            for cx in x..x + w {
+                if let Some(cell) = self.get_mut(cx, cy) {
+                    cell.symbol = '░';
+                    cell.fg = None;
+                }
+            }
+        }
+    }
+
+    fn draw_text(&mut self, text: &Text) {
+        let [x, y] = text.position;
+        let [w, h] = text.size;
+        let text_style = &text.text_style;
+        let bold = matches!(
+            text_style.font_weight,
+            FontWeight::SemiBold | FontWeight::Bold | FontWeight::ExtraBold | FontWeight::Black
+        );
+        let italic = !matches!(text_style.font_style, FontStyle::Normal);
+
+        // text is clipped to its bounds, each line goes on the next row. a negative offset hides whole cells, which
+        // is exact since positions are in cells here
+        let [skip_chars, skip_lines] = text
+            .offset
+            .map(|offset| (-offset).round().max(0.0) as usize);
+        let lines = text.text.lines().skip(skip_lines).take(h as usize);
+        for (line_index, line) in lines.enumerate() {
+            let cy = y + line_index as u32;
+            let chars = line.chars().skip(skip_chars).take(w as usize);
+            for (char_index, symbol) in chars.enumerate() {
+                if let Some(cell) = self.get_mut(x + char_index as u32, cy) {
+                    cell.symbol = symbol;
+                    cell.fg = Some(blend(cell.bg, text_style.text_color));
+                    cell.bold = bold;
+                    cell.italic = italic;
+                }
+            }
+        }
+    }
+}
+
+// braille dots for the lines plotted in an area of cells
+struct Dots {
+    position: [u32; 2],
+    size: [u32; 2],
+    bits: Vec<u8>,
+}
+
+impl Dots {
+    fn new(position: [u32; 2], size: [u32; 2]) -> Self {
+        Self {
+            position,
+            size,
+            bits: vec![0; cell_count(size)],
+        }
+    }
+
+    fn plot(&mut self, points: &[[f32; 2]]) {
+        let ([x, y], [w, h]) = (self.position, self.size);
+        for segment in points.windows(2) {
+            let [[ax, ay], [bx, by]] = [segment[0], segment[1]];
+            // step a quarter dot at a time so no dots are skipped
+            let steps = ((bx - ax).abs() * 8.0).max((by - ay).abs() * 16.0).ceil() as u32;
+            for step in 0..=steps {
+                let t = step as f32 / steps.max(1) as f32;
+                let (px, py) = (ax + (bx - ax) * t, ay + (by - ay) * t);
+                if px < x as f32 || py < y as f32 {
+                    continue;
+                }
+                let (cx, cy) = (px as u32, py as u32);
+                if cx - x >= w || cy - y >= h {
+                    continue;
+                }
+                let dot_x = (((px - cx as f32) * 2.0) as usize).min(1);
+                let dot_y = (((py - cy as f32) * 4.0) as usize).min(3);
+                self.bits[cell_index(self.size, cx - x, cy - y)] |= BRAILLE_DOTS[dot_y][dot_x];
+            }
+        }
+    }
+}
+
+// bit for each dot of a braille character, by row and column
+const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+// sizes and indices are worked out as usize, so large areas don't wrap around a u32
+fn cell_count(size: [u32; 2]) -> usize {
+    size[0] as usize * size[1] as usize
+}
+
+fn cell_index(size: [u32; 2], x: u32, y: u32) -> usize {
+    y as usize * size[0] as usize + x as usize
+}
+
+// renders the render list into a new buffer and returns it as an ansi string
+pub fn render_to_string(render_list: &RenderList, size: [u32; 2]) -> String {
+    let mut buffer = Buffer::new(size);
+    buffer.render(render_list);
+    buffer.to_ansi_string()
+}
+
+// terminal colours are opaque, so translucent colours are mixed with what is underneath (if we know it)
+fn blend(below: Option<Color>, color: Color) -> Color {
+    let below = match below {
+        Some(below) if color.a < 255 => below,
+        _ => return Color { a: 255, ..color },
+    };
+    let a = color.a as f32 / 255.0;
+    let mix = |top: u8, bottom: u8| (top as f32 * a + bottom as f32 * (1.0 - a)).round() as u8;
+    Color {
+        r: mix(color.r, below.r),
+        g: mix(color.g, below.g),
+        b: mix(color.b, below.b),
+        a: 255,
+    }
+}
+
+// only writes the escape codes for attributes that changed since the last cell
+fn write_style_change(out: &mut String, current: &Cell, next: &Cell) {
+    let needs_reset = (current.fg.is_some() && next.fg.is_none())
+        || (current.bg.is_some() && next.bg.is_none())
+        || (current.bold && !next.bold)
+        || (current.italic && !next.italic);
+    let current = match needs_reset {
+        true => {
+            out.push_str("\x1b[0m");
+            &Cell::default()
+        }
+        false => current,
+    };
+
+    if next.bold && !current.bold {
+        out.push_str("\x1b[1m");
+    }
+    if next.italic && !current.italic {
+        out.push_str("\x1b[3m");
+    }
+    if let Some(fg) = next.fg.filter(|fg| current.fg != Some(*fg)) {
+        let _ = write!(out, "\x1b[38;2;{};{};{}m", fg.r, fg.g, fg.b);
+    }
+    if let Some(bg) = next.bg.filter(|bg| current.bg != Some(*bg)) {
+        let _ = write!(out, "\x1b[48;2;{};{};{}m", bg.r, bg.g, bg.b);
+    }
+}
@@ -0,0 +1,153 @@
+use anchor_kit_core::{
+    anchor::AnchorPosition,
+    measure::TextMeasurement,
+    primitives::{
+        color::Color,
+        polyline::{Polyline, Stroke},
+        text::Text,
+    },
+    render::RenderList,
+    style::{FontWeight, Insets, Style, TextStyle},
+    FrameInfo, UIState,
+};
+use anchor_kit_term::{Buffer, Cell};
+
+fn render(render_list: &RenderList, size: [u32; 2]) -> Buffer {
+    let mut buffer = Buffer::new(size);
+    buffer.render(render_list);
+    buffer
+}
+
+fn text(text: &str, position: [u32; 2], size: [u32; 2], offset: [f32; 2]) -> Text {
+    Text {
+        text: text.to_string(),
+        position,
+        size,
+        text_style: TextStyle {
+            text_color: Color::WHITE,
+            ..Default::default()
+        },
+        offset,
+    }
+}
+
+#[test]
+fn layouts_are_measured_in_cells() {
+    let size = [12, 4];
+    let mut ui_state = UIState::new(size);
+    ui_state.set_text_measurement(TextMeasurement::Cells);
+    let render_list = ui_state.generate_frame(FrameInfo { size }, |ui| {
+        ui.anchor(AnchorPosition::TopLeft, None, |ui| {
+            ui.pill(
+                Some(Style {
+                    border_color: Color::WHITE,
+                    border_width: 1.0,
+                    padding: Insets {
+                        top: 0,
+                        right: 1,
+                        bottom: 0,
+                        left: 1,
+                    },
+                    ..Default::default()
+                }),
+                |ui| ui.text("hello", None, None),
+            );
+        });
+    });
+    // the font size is ignored, each character is one column and each line one row
+    assert_eq!(render_list.text[0].size, [5, 1]);
+    assert_eq!(
+        render(&render_list, size).to_plain_string(),
+        "┌───────┐   \n\
+         │ hello │   \n\
+         └───────┘   \n\
+         \x20           \n"
+    );
+}
+
+#[test]
+fn polylines_are_plotted_with_braille_dots() {
+    let render_list = RenderList {
+        polylines: vec![Polyline {
+            // a horizontal line through the third row of dots, then down the left column of the fourth cell to the
+            // top of the third row
+            points: vec![[0.0, 0.6], [3.1, 0.6], [3.1, 2.0]],
+            stroke: Stroke::new(1.0, Color::RED),
+            area: None,
+        }],
+        ..Default::default()
+    };
+    let buffer = render(&render_list, [5, 3]);
+    assert_eq!(buffer.to_plain_string(), "⠤⠤⠤⡄ \n   ⡇ \n   ⠁ \n");
+    let cell = buffer.get(0, 0).unwrap();
+    assert_eq!(cell.fg, Some(Color::RED));
+    assert_eq!(cell.bg, None);
+}
+
+#[test]
+fn ansi_output_only_writes_style_changes() {
+    let mut buffer = Buffer::new([4, 2]);
+    let styled = Cell {
+        symbol: 'a',
+        fg: Some(Color::rgb(1, 2, 3)),
+        bg: Some(Color::rgb(4, 5, 6)),
+        bold: true,
+        italic: false,
+    };
+    *buffer.get_mut(0, 0).unwrap() = styled;
+    *buffer.get_mut(1, 0).unwrap() = Cell {
+        symbol: 'b',
+        ..styled
+    };
+    *buffer.get_mut(2, 0).unwrap() = Cell {
+        symbol: 'c',
+        bold: false,
+        ..styled
+    };
+    *buffer.get_mut(1, 1).unwrap() = Cell {
+        symbol: 'd',
+        italic: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        buffer.to_ansi_string(),
+        "\x1b[1m\x1b[38;2;1;2;3m\x1b[48;2;4;5;6mab\
+         \x1b[0m\x1b[38;2;1;2;3m\x1b[48;2;4;5;6mc\
+         \x1b[0m \n \
+         \x1b[3md\x1b[0m  \n"
+    );
+    // rows of default cells don't need any escape codes
+    assert_eq!(Buffer::new([2, 1]).to_ansi_string(), "  \n");
+}
+
+#[test]
+fn bold_text_is_drawn_bold() {
+    let mut label = text("hi", [0, 0], [2, 1], [0.0, 0.0]);
+    label.text_style.font_weight = FontWeight::Bold;
+    let buffer = render(
+        &RenderList {
+            text: vec![label],
+            ..Default::default()
+        },
+        [2, 1],
+    );
+    assert!(buffer.get(0, 0).unwrap().bold);
+    assert!(buffer.to_ansi_string().starts_with("\x1b[1m"));
+}
+
+#[test]
+fn text_is_offset_and_clipped_to_its_bounds() {
+    let render_list = RenderList {
+        text: vec![
+            // starts two columns and one line to the left and above its position
+            text("first\nsecond\nthird", [1, 0], [4, 2], [-2.0, -1.0]),
+            // positive offsets don't move text, it already starts at its position
+            text("abc", [0, 2], [2, 1], [3.0, 0.0]),
+        ],
+        ..Default::default()
+    };
+    assert_eq!(
+        render(&render_list, [6, 3]).to_plain_string(),
+        " cond \n ird  \nab    \n"
+    );
+}
@@ -0,0 +1,56 @@
+use anchor_kit_core::primitives::arc::Arc;
+
+use crate::{ArcVertex, ScreenInfo};
+
+// arcs are drawn as a quad over their bounds, the fragment shader works out which pixels are inside
+// v0--v1
+// |  /|
+// | / |
+// |/  |
+// v3--v2
+pub(crate) fn get_vertices_and_indices_for_arc(
+    arc: &Arc,
+    screen_info: &ScreenInfo,
+    vertex_offset: u32,
+) -> ([ArcVertex; 4], [u32; 6]) {
+    let ([x, y], [w, h]) = arc.bounds();
+    let [screen_w, screen_h] = screen_info.size_px.map(|s| s as f32);
+    let half_sweep = arc.half_sweep();
+    let (mid_sin, mid_cos) = ((arc.start_angle + arc.end_angle) / 2.0)
+        .to_radians()
+        .sin_cos();
+    let (end_sin, end_cos) = half_sweep.sin_cos();
+
+    let vertex = |corner_x: u32, corner_y: u32| ArcVertex {
+        position: [corner_x as f32 / screen_w, corner_y as f32 / screen_h],
+        offset: [
+            corner_x as f32 - arc.center[0],
+            corner_y as f32 - arc.center[1],
+        ],
+        radius: arc.radius,
+        thickness: arc.thickness,
+        direction: [mid_sin, mid_cos],
+        end: [end_sin, end_cos],
+        half_sweep,
+        rounded_caps: if arc.rounded_caps { 1.0 } else { 0.0 },
+        color: arc.color.to_rgba_f32(),
+    };
+    let vertices = [
+        vertex(x, y),
+        vertex(x + w, y),
+        vertex(x + w, y + h),
+        vertex(x, y + h),
+    ];
+
+    // triangles are v0 -> v2 -> v1, and v0 -> v3 -> v2, like rectangles
+    let indices = [
+        vertex_offset,
+        vertex_offset + 2,
+        vertex_offset + 1,
+        vertex_offset,
+        vertex_offset + 3,
+        vertex_offset + 2,
+    ];
+
+    (vertices, indices)
+}
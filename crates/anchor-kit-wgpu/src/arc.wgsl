@@ -0,0 +1,83 @@
+// shader for arcs (gauge tracks, donut slices), the coverage of each pixel comes from its distance to the arc
+// this is the same math as `Arc::distance` in anchor-kit-core, so the cpu renderer gives matching results
+struct VertexInput {
+    @location(0) position: vec2<f32>, // normalized pos
+    @location(1) offset: vec2<f32>, // from the centre of the arc in pixels
+    @location(2) radius: f32, // to the middle of the ring
+    @location(3) thickness: f32,
+    @location(4) direction: vec2<f32>, // sin, cos of the angle through the middle of the arc
+    @location(5) end: vec2<f32>, // sin, cos of half the sweep
+    @location(6) half_sweep: f32, // pi for a full ring
+    @location(7) rounded_caps: f32, // 1.0 for rounded caps
+    @location(8) color: vec4<f32>, // r, g, b, a
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) offset: vec2<f32>,
+    @location(1) radius: f32,
+    @location(2) thickness: f32,
+    @location(3) direction: vec2<f32>,
+    @location(4) end: vec2<f32>,
+    @location(5) half_sweep: f32,
+    @location(6) rounded_caps: f32,
+    @location(7) color: vec4<f32>,
+};
+
+const PI: f32 = 3.14159265;
+
+@vertex
+fn vs_main(
+    model: VertexInput
+) -> VertexOutput {
+    let ndc_x = model.position.x * 2.0 - 1.0;
+    let ndc_y = 1.0 - model.position.y * 2.0; // flip y (to bottom up) for GPU coords
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(ndc_x, ndc_y, 0.0, 1.0);
+    out.offset = model.offset;
+    out.radius = model.radius;
+    out.thickness = model.thickness;
+    out.direction = model.direction;
+    out.end = model.end;
+    out.half_sweep = model.half_sweep;
+    out.rounded_caps = model.rounded_caps;
+    out.color = model.color;
+    return out;
+}
+
+// signed distance from the edge of the arc, negative inside
+fn arc_distance(in: VertexOutput) -> f32 {
+    let half_thickness = in.thickness / 2.0;
+    let ring = abs(length(in.offset) - in.radius) - half_thickness;
+    if in.half_sweep >= PI {
+        return ring;
+    }
+
+    // rotate so the middle of the arc points along +v, the arc is then symmetric around that axis
+    let u = abs(in.offset.x * in.direction.y + in.offset.y * in.direction.x);
+    let v = in.offset.x * in.direction.x - in.offset.y * in.direction.y;
+    if atan2(u, v) <= in.half_sweep {
+        // straight caps are also an edge from inside, measured across the line through the end
+        if in.rounded_caps < 0.5 && u * in.end.x + v * in.end.y > 0.0 {
+            return max(ring, u * in.end.y - v * in.end.x);
+        }
+        return ring;
+    }
+    let end = in.end * in.radius;
+    if in.rounded_caps > 0.5 {
+        return length(vec2<f32>(u, v) - end) - half_thickness;
+    }
+    // distance to the straight cap, the segment across the ring at the end angle
+    let along = clamp(u * in.end.x + v * in.end.y - in.radius, -half_thickness, half_thickness);
+    return length(vec2<f32>(u, v) - in.end * (in.radius + along));
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let coverage = clamp(0.5 - arc_distance(in), 0.0, 1.0);
+    if coverage <= 0.0 {
+        discard;
+    }
+    return vec4<f32>(in.color.rgb, in.color.a * coverage);
+}
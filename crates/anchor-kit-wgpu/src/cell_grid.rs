@@ -0,0 +1,135 @@
+use anchor_kit_core::primitives::cell_grid::CellGrid;
+
+use crate::{CellInstance, ScreenInfo};
+
+// grids with at least this many cells, no gaps and square corners are uploaded as a texture with a texel per cell
+// instead of an instance per cell, so even very large matrices are a single quad
+const TEXTURE_MIN_CELLS: u32 = 4096;
+
+pub(crate) fn uses_texture(grid: &CellGrid, max_texture_size: u32) -> bool {
+    grid.gap == 0
+        && grid.cell_radius <= 0.0
+        && grid
+            .rows
+            .checked_mul(grid.columns)
+            .is_some_and(|cells| cells >= TEXTURE_MIN_CELLS)
+        && grid.rows <= max_texture_size
+        && grid.columns <= max_texture_size
+}
+
+pub(crate) fn push_cell_instances(
+    grid: &CellGrid,
+    screen_info: &ScreenInfo,
+    instances: &mut Vec<CellInstance>,
+) {
+    let [screen_w, screen_h] = screen_info.size_px.map(|s| s as f32);
+    for row in 0..grid.rows {
+        for column in 0..grid.columns {
+            let color = grid.color(row, column);
+            let ([x, y], [w, h]) = grid.cell_bounds(row, column);
+            if color.a == 0 || w == 0 || h == 0 {
+                continue;
+            }
+            // the same local units as rectangles, see get_vertices_and_indices_for_rectangle
+            let scale_axis = w.min(h) as f32;
+            instances.push(CellInstance {
+                position: [x as f32 / screen_w, y as f32 / screen_h],
+                size: [w as f32 / screen_w, h as f32 / screen_h],
+                scale: [w as f32 / scale_axis, h as f32 / scale_axis],
+                radius_local: (grid.cell_radius / scale_axis).min(0.5),
+                color: color.to_rgba_f32(),
+            });
+        }
+    }
+}
+
+// a texture kept between frames for a grid drawn as one quad, recreated when the grid changes size
+pub(crate) struct CellGridTexture {
+    size: [u32; 2], // columns, rows
+    texture: wgpu::Texture,
+    pub(crate) bind_group: wgpu::BindGroup,
+}
+
+impl CellGridTexture {
+    pub(crate) fn new(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        size: [u32; 2],
+    ) -> Self {
+        // not srgb, so the texels hold the same values the rectangle shader gets for its colours
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            size: wgpu::Extent3d {
+                width: size[0],
+                height: size[1],
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            label: Some("anchor-kit cell grid texture"),
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        // nearest filtering keeps the cell edges sharp however far the texture is stretched
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+            label: Some("anchor-kit cell grid bind group"),
+        });
+        Self {
+            size,
+            texture,
+            bind_group,
+        }
+    }
+
+    pub(crate) fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    pub(crate) fn write(&self, queue: &wgpu::Queue, grid: &CellGrid, pixels: &mut Vec<u8>) {
+        pixels.clear();
+        pixels.extend(grid.colors.iter().flat_map(|c| [c.r, c.g, c.b, c.a]));
+        // grids with fewer colours than cells leave the rest empty
+        pixels.resize((self.size[0] * self.size[1] * 4) as usize, 0);
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            pixels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * self.size[0]),
+                rows_per_image: Some(self.size[1]),
+            },
+            wgpu::Extent3d {
+                width: self.size[0],
+                height: self.size[1],
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use std::{fmt, path::Path};
+
+use anchor_kit_core::{primitives::color::Color, render::RenderList};
+use image::RgbaImage;
+use uuid::Uuid;
+
+use crate::{Renderer, ScreenInfo};
+
+// format of the offscreen texture, srgb so the output matches what the examples draw to a window
+const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+#[derive(Debug)]
+pub enum HeadlessError {
+    RequestAdapter(wgpu::RequestAdapterError),
+    RequestDevice(wgpu::RequestDeviceError),
+    BufferMap(wgpu::BufferAsyncError),
+    Poll(wgpu::PollError),
+    Image(image::ImageError),
+    InvalidSize([u32; 2]), // zero or larger than the device's texture limit
+    ReadBack, // the frame read back from the gpu didn't have the expected number of pixels
+}
+
+impl fmt::Display for HeadlessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeadlessError::RequestAdapter(err) => write!(f, "no suitable adapter: {err}"),
+            HeadlessError::RequestDevice(err) => write!(f, "failed to create device: {err}"),
+            HeadlessError::BufferMap(err) => write!(f, "failed to read back frame: {err}"),
+            HeadlessError::Poll(err) => write!(f, "failed to wait for the gpu: {err}"),
+            HeadlessError::Image(err) => write!(f, "failed to write image: {err}"),
+            HeadlessError::InvalidSize([width, height]) => {
+                write!(f, "can't render a {width}x{height} image")
+            }
+            HeadlessError::ReadBack => {
+                write!(f, "the frame read back from the gpu is the wrong size")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HeadlessError {}
+
+// renders frames into an offscreen texture instead of a window surface, e.g. for thumbnails or tests
+// works with software adapters (lavapipe, llvmpipe, warp) so it can run on machines without a gpu
+pub struct HeadlessRenderer {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    renderer: Renderer,
+}
+
+impl HeadlessRenderer {
+    // uses a hardware adapter if there is one, otherwise falls back to a software adapter
+    pub fn new() -> Result<Self, HeadlessError> {
+        pollster::block_on(Self::new_async())
+    }
+
+    pub async fn new_async() -> Result<Self, HeadlessError> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let adapter = match instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: None, // we never present to a surface
+                force_fallback_adapter: false,
+            })
+            .await
+        {
+            Ok(adapter) => adapter,
+            Err(_) => instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference: wgpu::PowerPreference::default(),
+                    compatible_surface: None,
+                    force_fallback_adapter: true,
+                })
+                .await
+                .map_err(HeadlessError::RequestAdapter)?,
+        };
+
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("anchor-kit headless device"),
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::downlevel_defaults(),
+                memory_hints: Default::default(),
+                trace: wgpu::Trace::Off,
+            })
+            .await
+            .map_err(HeadlessError::RequestDevice)?;
+
+        Ok(Self::from_device(device, queue))
+    }
+
+    // for when the application already has a device (e.g. rendering thumbnails alongside a window)
+    pub fn from_device(device: wgpu::Device, queue: wgpu::Queue) -> Self {
+        let renderer = Renderer::new(&device, &queue, TEXTURE_FORMAT);
+        Self {
+            device,
+            queue,
+            renderer,
+        }
+    }
+
+    pub fn get_image_id_from_bytes(&mut self, diffuse_bytes: &[u8]) -> Uuid {
+        self.renderer
+            .get_image_id_from_bytes(&self.device, &self.queue, diffuse_bytes)
+    }
+
+    pub fn render_to_image(
+        &mut self,
+        size: [u32; 2],
+        clear_color: Color,
+        render_list: &RenderList,
+    ) -> Result<RgbaImage, HeadlessError> {
+        let [width, height] = size;
+        let max_size = self.device.limits().max_texture_dimension_2d;
+        if width == 0 || height == 0 || width > max_size || height > max_size {
+            return Err(HeadlessError::InvalidSize(size));
+        }
+        let texture_size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("anchor-kit headless texture"),
+            size: texture_size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TEXTURE_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // rows in the read back buffer have to be aligned, so there can be padding at the end of each row
+        let unpadded_bytes_per_row = width * 4;
+        let padded_bytes_per_row = unpadded_bytes_per_row
+            .div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let output_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("anchor-kit headless output buffer"),
+            size: (padded_bytes_per_row * height) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("anchor-kit headless encoder"),
+            });
+        {
+            let [r, g, b, a] = clear_color.to_rgba_f32();
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("anchor-kit headless render pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: r as f64,
+                            g: g as f64,
+                            b: b as f64,
+                            a: a as f64,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            let screen_info = ScreenInfo {
+                size_px: size,
+                scale_factor: 1.0,
+            };
+            self.renderer.render(
+                &self.device,
+                &self.queue,
+                &mut render_pass,
+                &screen_info,
+                render_list,
+            );
+        }
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &output_buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture_size,
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        // wait for the gpu to finish and map the buffer so we can read it
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let buffer_slice = output_buffer.slice(..);
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device
+            .poll(wgpu::PollType::Wait)
+            .map_err(HeadlessError::Poll)?;
+        // the callback always runs during the poll above
+        if let Ok(result) = receiver.recv() {
+            result.map_err(HeadlessError::BufferMap)?;
+        }
+
+        let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+        {
+            let data = buffer_slice.get_mapped_range();
+            for row in data.chunks(padded_bytes_per_row as usize) {
+                pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+            }
+        }
+        output_buffer.unmap();
+
+        RgbaImage::from_raw(width, height, pixels).ok_or(HeadlessError::ReadBack)
+    }
+
+    pub fn render_to_png(
+        &mut self,
+        path: impl AsRef<Path>,
+        size: [u32; 2],
+        clear_color: Color,
+        render_list: &RenderList,
+    ) -> Result<(), HeadlessError> {
+        let image = self.render_to_image(size, clear_color, render_list)?;
+        image
+            .save_with_format(path, image::ImageFormat::Png)
+            .map_err(HeadlessError::Image)
+    }
+}
@@ -1,15 +1,25 @@
+mod arc;
+mod cell_grid;
+pub mod headless;
+mod path;
+mod polyline;
+
 use std::collections::HashMap;
 
 use anchor_kit_core::{
-    primitives::rectangle::Rectangle,
+    primitives::{color::Color, rectangle::Rectangle},
     render::RenderList,
     style::{FontFamily, FontStyle, FontWeight},
 };
+use arc::get_vertices_and_indices_for_arc;
+use cell_grid::{push_cell_instances, uses_texture, CellGridTexture};
 use glyphon::{
     Attrs, Cache, FontSystem, Metrics, Shaping, SwashCache, TextArea, TextAtlas, TextBounds,
     TextRenderer, Viewport,
 };
 use image::GenericImageView;
+use path::tessellate_path;
+use polyline::tessellate_polyline;
 use uuid::Uuid;
 use wgpu::include_wgsl;
 
@@ -54,6 +64,107 @@ impl Vertex {
     }
 }
 
+// vertex for tessellated shapes (polylines and paths), drawn with the mesh pipeline
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::NoUninit)]
+struct MeshVertex {
+    position: [f32; 2], // x, y (normalized)
+    color: [f32; 4],    // r, g, b, a
+}
+
+impl MeshVertex {
+    const ATTRIBS: [wgpu::VertexAttribute; 2] = wgpu::vertex_attr_array![
+        0 => Float32x2, // location 0 is normalized position
+        1 => Float32x4, // location 1 is colour
+    ];
+
+    fn capacity_to_bytes(capacity: usize) -> wgpu::BufferAddress {
+        (capacity * std::mem::size_of::<Self>()) as wgpu::BufferAddress
+    }
+
+    fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBS,
+        }
+    }
+}
+
+// vertex for arcs, every vertex of the quad carries the whole arc so the fragment shader can work out its coverage
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::NoUninit)]
+struct ArcVertex {
+    position: [f32; 2], // x, y (normalized)
+    offset: [f32; 2],   // from the centre of the arc in pixels
+    radius: f32,        // to the middle of the ring
+    thickness: f32,
+    direction: [f32; 2], // sin, cos of the angle through the middle of the arc
+    end: [f32; 2],       // sin, cos of half the sweep
+    half_sweep: f32,
+    rounded_caps: f32, // 1.0 for rounded caps, 0.0 for straight ones
+    color: [f32; 4],   // r, g, b, a
+}
+
+impl ArcVertex {
+    const ATTRIBS: [wgpu::VertexAttribute; 9] = wgpu::vertex_attr_array![
+        0 => Float32x2, // location 0 is normalized position
+        1 => Float32x2, // location 1 is the offset from the centre in pixels
+        2 => Float32, // location 2 is the radius
+        3 => Float32, // location 3 is the thickness
+        4 => Float32x2, // location 4 is the direction through the middle
+        5 => Float32x2, // location 5 is the direction of the end, relative to the middle
+        6 => Float32, // location 6 is half the sweep
+        7 => Float32, // location 7 is the cap style
+        8 => Float32x4, // location 8 is colour
+    ];
+
+    fn capacity_to_bytes(capacity: usize) -> wgpu::BufferAddress {
+        (capacity * std::mem::size_of::<Self>()) as wgpu::BufferAddress
+    }
+
+    fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBS,
+        }
+    }
+}
+
+// instance for a heatmap cell, drawn as a rectangle by the main shader
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::NoUninit)]
+struct CellInstance {
+    position: [f32; 2], // x, y of the top left corner (normalized)
+    size: [f32; 2],     // w, h (normalized)
+    scale: [f32; 2],    // scale x,y to w,h
+    radius_local: f32,  // corner radius in local units
+    color: [f32; 4],    // r, g, b, a
+}
+
+impl CellInstance {
+    const ATTRIBS: [wgpu::VertexAttribute; 5] = wgpu::vertex_attr_array![
+        0 => Float32x2, // location 0 is normalized position
+        1 => Float32x2, // location 1 is normalized size
+        2 => Float32x2, // location 2 is the scale
+        3 => Float32, // location 3 is the corner radius
+        4 => Float32x4, // location 4 is colour
+    ];
+
+    fn capacity_to_bytes(capacity: usize) -> wgpu::BufferAddress {
+        (capacity * std::mem::size_of::<Self>()) as wgpu::BufferAddress
+    }
+
+    fn desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &Self::ATTRIBS,
+        }
+    }
+}
+
 fn get_vertex_buffer(device: &wgpu::Device, capacity_bytes: wgpu::BufferAddress) -> wgpu::Buffer {
     device.create_buffer(&wgpu::BufferDescriptor {
         label: Some("anchor-kit vertex buffer"),
@@ -293,9 +404,9 @@ impl GlyphonRenderer {
             );
 
             text_areas.push(TextArea {
-                buffer: &text_buffer,
-                left: x as f32,
-                top: y as f32,
+                buffer: text_buffer,
+                left: x as f32 + text_item.offset[0],
+                top: y as f32 + text_item.offset[1],
                 scale: 1.0, // ignore screen scale factor (TODO: investigate if we want to include this later)
                 bounds: text_bounds,
                 default_color: text_color,
@@ -319,7 +430,7 @@ impl GlyphonRenderer {
 
         if let Err(err) = self
             .text_renderer
-            .render(&mut self.atlas, &self.viewport, render_pass)
+            .render(&self.atlas, &self.viewport, render_pass)
         {
             // TODO: add better error handling
             println!("error with glyphon text render: {:?}", err);
@@ -366,8 +477,20 @@ impl GlyphonRenderer {
 pub struct Renderer {
     main_pipeline: wgpu::RenderPipeline,
     image_pipeline: wgpu::RenderPipeline, // we need a new pipeline for iamges because we have to pass bind groups to the fragment shader
+    mesh_pipeline: wgpu::RenderPipeline,  // tessellated shapes have their own vertex layout
+    arc_pipeline: wgpu::RenderPipeline,   // arcs are shaded from their distance to each pixel
+    cell_pipeline: wgpu::RenderPipeline,  // cell grids are instanced rectangles
+    cell_texture_pipeline: wgpu::RenderPipeline, // or a texture when they are large
     vertex_buffer: wgpu::Buffer,
     vertex_buffer_capacity: usize,
+    mesh_vertex_buffer: wgpu::Buffer,
+    mesh_vertex_buffer_capacity: usize,
+    arc_vertex_buffer: wgpu::Buffer,
+    arc_vertex_buffer_capacity: usize,
+    cell_instance_buffer: wgpu::Buffer,
+    cell_instance_buffer_capacity: usize,
+    cell_grid_textures: Vec<CellGridTexture>, // one for each large cell grid, in render list order
+    max_texture_size: u32,
     index_buffer: wgpu::Buffer,
     index_buffer_capacity: usize,
     glyphon_renderer: GlyphonRenderer,
@@ -510,13 +633,201 @@ impl Renderer {
             cache: None,
         });
 
+        // large cell grids are textured quads like images, but without the rounded edge
+        let cell_texture_pipeline =
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("anchor-kit cell texture pipeline"),
+                layout: Some(&image_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[Vertex::desc()],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_cell_texture"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: texture_format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: Some(wgpu::Face::Back),
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    unclipped_depth: false,
+                    conservative: false,
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState {
+                    count: 1,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview: None,
+                cache: None,
+            });
+
+        let mesh_shader = device.create_shader_module(include_wgsl!("mesh.wgsl"));
+        let initial_mesh_vertex_buffer_capacity = 1024;
+        let mesh_vertex_buffer = get_vertex_buffer(
+            device,
+            MeshVertex::capacity_to_bytes(initial_mesh_vertex_buffer_capacity),
+        );
+
+        // the mesh pipeline doesn't need any bind groups either, so it can share the main layout
+        let mesh_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("anchor-kit mesh pipeline"),
+            layout: Some(&main_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &mesh_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[MeshVertex::desc()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &mesh_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: texture_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None, // tessellated triangles can face either way (e.g. an area crossing its baseline)
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let arc_shader = device.create_shader_module(include_wgsl!("arc.wgsl"));
+        let initial_arc_vertex_buffer_capacity = 64;
+        let arc_vertex_buffer = get_vertex_buffer(
+            device,
+            ArcVertex::capacity_to_bytes(initial_arc_vertex_buffer_capacity),
+        );
+
+        let arc_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("anchor-kit arc pipeline"),
+            layout: Some(&main_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &arc_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[ArcVertex::desc()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &arc_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: texture_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let initial_cell_instance_buffer_capacity = 256;
+        let cell_instance_buffer = get_vertex_buffer(
+            device,
+            CellInstance::capacity_to_bytes(initial_cell_instance_buffer_capacity),
+        );
+
+        // cells use the rectangle shader with their own vertex entry point, so they can share the main layout
+        let cell_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("anchor-kit cell pipeline"),
+            layout: Some(&main_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_cell"),
+                buffers: &[CellInstance::desc()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: texture_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
         Renderer {
             main_pipeline,
             image_pipeline,
+            mesh_pipeline,
+            arc_pipeline,
+            cell_pipeline,
+            cell_texture_pipeline,
             vertex_buffer,
-            vertex_buffer_capacity: initial_vertex_buffer_capacity as usize,
+            vertex_buffer_capacity: initial_vertex_buffer_capacity,
+            mesh_vertex_buffer,
+            mesh_vertex_buffer_capacity: initial_mesh_vertex_buffer_capacity,
+            arc_vertex_buffer,
+            arc_vertex_buffer_capacity: initial_arc_vertex_buffer_capacity,
+            cell_instance_buffer,
+            cell_instance_buffer_capacity: initial_cell_instance_buffer_capacity,
+            cell_grid_textures: vec![],
+            max_texture_size: device.limits().max_texture_dimension_2d,
             index_buffer,
-            index_buffer_capacity: initial_index_buffer_capacity as usize,
+            index_buffer_capacity: initial_index_buffer_capacity,
             glyphon_renderer: GlyphonRenderer::new(device, queue, texture_format),
             bind_groups: HashMap::new(),
             texture_bind_group_layout,
@@ -546,6 +857,59 @@ impl Renderer {
         }
         let main_pipeline_index_count = indices.len();
 
+        // cell grids go over the rectangles, large ones as a single quad with a texel per cell and the rest as an
+        // instance per cell. the quads are drawn like images, so they share the main vertices
+        let mut cell_instances: Vec<CellInstance> = vec![];
+        let mut cell_grid_texture_draws: Vec<usize> = vec![]; // index offset of each textured grid's quad
+        let mut pixels: Vec<u8> = vec![];
+        for grid in &render_list.cell_grids {
+            if !uses_texture(grid, self.max_texture_size) {
+                push_cell_instances(grid, screen_info, &mut cell_instances);
+                continue;
+            }
+            let texture_index = cell_grid_texture_draws.len();
+            let size = [grid.columns, grid.rows];
+            let new_texture =
+                || CellGridTexture::new(device, &self.texture_bind_group_layout, size);
+            match self.cell_grid_textures.get_mut(texture_index) {
+                Some(texture) if texture.size() == size => {}
+                Some(texture) => *texture = new_texture(),
+                None => self.cell_grid_textures.push(new_texture()),
+            }
+            self.cell_grid_textures[texture_index].write(queue, grid, &mut pixels);
+
+            cell_grid_texture_draws.push(indices.len());
+            let quad = Rectangle::new(grid.position, grid.size, None);
+            let (new_vertices, new_indices) =
+                get_vertices_and_indices_for_rectangle(&quad, screen_info, vertices.len() as u32);
+            vertices.extend_from_slice(&new_vertices);
+            indices.extend_from_slice(&new_indices);
+        }
+        self.cell_grid_textures
+            .truncate(cell_grid_texture_draws.len());
+
+        // polylines and paths are tessellated into their own vertex list, their indices share the index buffer
+        let mut mesh_vertices: Vec<MeshVertex> = vec![];
+        let mesh_pipeline_index_offset = indices.len();
+        for polyline in &render_list.polylines {
+            tessellate_polyline(polyline, screen_info, &mut mesh_vertices, &mut indices);
+        }
+        for path in &render_list.paths {
+            tessellate_path(path, screen_info, &mut mesh_vertices, &mut indices);
+        }
+        let mesh_pipeline_index_count = indices.len();
+
+        // arcs are a quad each with their own vertex list, after the meshes in the shared index buffer
+        let mut arc_vertices: Vec<ArcVertex> = vec![];
+        let arc_pipeline_index_offset = indices.len();
+        for arc in &render_list.arcs {
+            let (new_vertices, new_indices) =
+                get_vertices_and_indices_for_arc(arc, screen_info, arc_vertices.len() as u32);
+            arc_vertices.extend_from_slice(&new_vertices);
+            indices.extend_from_slice(&new_indices);
+        }
+        let arc_pipeline_index_count = indices.len();
+
         // we will keep track of image draws seperatly so that we can use the correct bind gorups later for the texture rendering
         struct ImageDraw {
             texture_id: Uuid,
@@ -557,8 +921,17 @@ impl Renderer {
         for image in &render_list.images {
             let image_index_offset = indices.len();
 
+            // the image fragment shader doesn't use the background colour, so we pass the image opacity in its alpha
+            let mut image_rectangle = image.rectangle;
+            image_rectangle.style.background_color = Color {
+                r: 255,
+                g: 255,
+                b: 255,
+                a: (image.opacity.clamp(0.0, 1.0) * 255.0) as u8,
+            };
+
             let (new_vertices, new_indices) = get_vertices_and_indices_for_rectangle(
-                &image.rectangle,
+                &image_rectangle,
                 screen_info,
                 vertices.len() as u32,
             );
@@ -574,10 +947,28 @@ impl Renderer {
 
         // make sure there is enough capcity on the gpu
         self.resize_vertex_buffer_if_required(device, vertices.len());
+        self.resize_mesh_vertex_buffer_if_required(device, mesh_vertices.len());
+        self.resize_arc_vertex_buffer_if_required(device, arc_vertices.len());
+        self.resize_cell_instance_buffer_if_required(device, cell_instances.len());
         self.resize_index_buffer_if_required(device, indices.len());
 
         // write data to the queue
         queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+        queue.write_buffer(
+            &self.mesh_vertex_buffer,
+            0,
+            bytemuck::cast_slice(&mesh_vertices),
+        );
+        queue.write_buffer(
+            &self.arc_vertex_buffer,
+            0,
+            bytemuck::cast_slice(&arc_vertices),
+        );
+        queue.write_buffer(
+            &self.cell_instance_buffer,
+            0,
+            bytemuck::cast_slice(&cell_instances),
+        );
         queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(&indices));
 
         render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
@@ -591,6 +982,46 @@ impl Renderer {
             0..1,
         );
 
+        if !cell_instances.is_empty() {
+            render_pass.set_pipeline(&self.cell_pipeline);
+            render_pass.set_vertex_buffer(0, self.cell_instance_buffer.slice(..));
+            render_pass.draw(0..6, 0..cell_instances.len() as u32);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        }
+        if !cell_grid_texture_draws.is_empty() {
+            render_pass.set_pipeline(&self.cell_texture_pipeline);
+            for (texture, index_offset) in
+                self.cell_grid_textures.iter().zip(&cell_grid_texture_draws)
+            {
+                render_pass.set_bind_group(0, &texture.bind_group, &[]);
+                render_pass.draw_indexed(*index_offset as u32..*index_offset as u32 + 6, 0, 0..1);
+            }
+        }
+
+        // draw the polylines and paths on top of the rectangles, then switch back to the main vertices for the images
+        if mesh_pipeline_index_count > mesh_pipeline_index_offset {
+            render_pass.set_pipeline(&self.mesh_pipeline);
+            render_pass.set_vertex_buffer(0, self.mesh_vertex_buffer.slice(..));
+            render_pass.draw_indexed(
+                mesh_pipeline_index_offset as u32..mesh_pipeline_index_count as u32,
+                0,
+                0..1,
+            );
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        }
+
+        // arcs go over the shapes and under the images
+        if arc_pipeline_index_count > arc_pipeline_index_offset {
+            render_pass.set_pipeline(&self.arc_pipeline);
+            render_pass.set_vertex_buffer(0, self.arc_vertex_buffer.slice(..));
+            render_pass.draw_indexed(
+                arc_pipeline_index_offset as u32..arc_pipeline_index_count as u32,
+                0,
+                0..1,
+            );
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        }
+
         // draw the images using the image pipeline (individual draws for each since they could have different textures)
         render_pass.set_pipeline(&self.image_pipeline);
         for image_draw in image_draws.iter() {
@@ -702,6 +1133,47 @@ impl Renderer {
         self.vertex_buffer_capacity = new_size;
     }
 
+    fn resize_mesh_vertex_buffer_if_required(
+        &mut self,
+        device: &wgpu::Device,
+        num_requested_vertices: usize,
+    ) {
+        if num_requested_vertices <= self.mesh_vertex_buffer_capacity {
+            return;
+        }
+        let new_size = num_requested_vertices.next_power_of_two();
+        self.mesh_vertex_buffer =
+            get_vertex_buffer(device, MeshVertex::capacity_to_bytes(new_size));
+        self.mesh_vertex_buffer_capacity = new_size;
+    }
+
+    fn resize_arc_vertex_buffer_if_required(
+        &mut self,
+        device: &wgpu::Device,
+        num_requested_vertices: usize,
+    ) {
+        if num_requested_vertices <= self.arc_vertex_buffer_capacity {
+            return;
+        }
+        let new_size = num_requested_vertices.next_power_of_two();
+        self.arc_vertex_buffer = get_vertex_buffer(device, ArcVertex::capacity_to_bytes(new_size));
+        self.arc_vertex_buffer_capacity = new_size;
+    }
+
+    fn resize_cell_instance_buffer_if_required(
+        &mut self,
+        device: &wgpu::Device,
+        num_requested_instances: usize,
+    ) {
+        if num_requested_instances <= self.cell_instance_buffer_capacity {
+            return;
+        }
+        let new_size = num_requested_instances.next_power_of_two();
+        self.cell_instance_buffer =
+            get_vertex_buffer(device, CellInstance::capacity_to_bytes(new_size));
+        self.cell_instance_buffer_capacity = new_size;
+    }
+
     fn resize_index_buffer_if_required(
         &mut self,
         device: &wgpu::Device,
@@ -0,0 +1,163 @@
+use anchor_kit_core::primitives::{
+    path::{winding_number, FillRule, Path},
+    polyline::split_dashes,
+};
+use lyon::{
+    math::point,
+    tessellation::{BuffersBuilder, FillOptions, FillTessellator, FillVertex, VertexBuffers},
+};
+
+use crate::{
+    polyline::{add, direction, dot, length, normal, scale, without_repeats, Mesh, Stroker},
+    MeshVertex, ScreenInfo,
+};
+
+// the inside of the fill is tessellated by lyon, then a half pixel fringe is added around the outside edges for
+// anti-aliasing. the stroke uses the same tessellation as polylines
+pub(crate) fn tessellate_path(
+    path: &Path,
+    screen_info: &ScreenInfo,
+    vertices: &mut Vec<MeshVertex>,
+    indices: &mut Vec<u32>,
+) {
+    let mut sub_paths: Vec<(Vec<[f32; 2]>, bool)> = vec![];
+    path.flatten(|points, closed| {
+        let mut sub_path = vec![];
+        without_repeats(points, &mut sub_path);
+        if closed && sub_path.len() > 1 && sub_path.first() == sub_path.last() {
+            sub_path.pop();
+        }
+        sub_paths.push((sub_path, closed));
+    });
+    let mut mesh = Mesh::new(vertices, indices, screen_info);
+
+    if let Some(fill) = path.fill.filter(|fill| fill.a > 0) {
+        let contours: Vec<&[[f32; 2]]> = sub_paths
+            .iter()
+            .map(|(points, _)| points.as_slice())
+            .filter(|points| points.len() > 2)
+            .collect();
+        fill_contours(&mut mesh, &contours, path.fill_rule, fill.to_rgba_f32());
+    }
+
+    let Some(stroke) = &path.stroke else {
+        return;
+    };
+    let Some(mut stroker) = Stroker::new(&mut mesh, stroke) else {
+        return;
+    };
+    let mut points: Vec<[f32; 2]> = vec![];
+    for (sub_path, closed) in &sub_paths {
+        if *closed && stroke.dash.is_none() {
+            stroker.stroke(sub_path, true);
+            continue;
+        }
+        // dashes on closed sub paths run all the way around, back to the start
+        let mut line = sub_path.clone();
+        if *closed {
+            line.extend(sub_path.first().copied());
+        }
+        split_dashes(&line, stroke.dash, |dash| {
+            without_repeats(dash, &mut points);
+            stroker.stroke(&points, false);
+        });
+    }
+}
+
+fn fill_contours(mesh: &mut Mesh, contours: &[&[[f32; 2]]], fill_rule: FillRule, color: [f32; 4]) {
+    if contours.is_empty() {
+        return;
+    }
+    // the flattened points are used for lyon's path too, so the fringe lines up exactly with the inside
+    let mut builder = lyon::path::Path::builder();
+    for contour in contours {
+        builder.begin(point(contour[0][0], contour[0][1]));
+        for [x, y] in &contour[1..] {
+            builder.line_to(point(*x, *y));
+        }
+        builder.end(true);
+    }
+    let lyon_path = builder.build();
+
+    let options = FillOptions::tolerance(Path::TOLERANCE).with_fill_rule(match fill_rule {
+        FillRule::NonZero => lyon::tessellation::FillRule::NonZero,
+        FillRule::EvenOdd => lyon::tessellation::FillRule::EvenOdd,
+    });
+    let mut geometry: VertexBuffers<[f32; 2], u32> = VertexBuffers::new();
+    let result = FillTessellator::new().tessellate_path(
+        &lyon_path,
+        &options,
+        &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex| {
+            vertex.position().to_array()
+        }),
+    );
+    if result.is_err() {
+        return;
+    }
+    let ids: Vec<u32> = geometry
+        .vertices
+        .iter()
+        .map(|position| mesh.vertex(*position, color))
+        .collect();
+    for triangle in geometry.indices.chunks_exact(3) {
+        mesh.triangle(
+            ids[triangle[0] as usize],
+            ids[triangle[1] as usize],
+            ids[triangle[2] as usize],
+        );
+    }
+
+    // the fringe fades from half the fill's alpha on the edge to nothing half a pixel out, which is how much of the
+    // pixels just outside the edge the shape covers
+    let edge_color = [color[0], color[1], color[2], color[3] * 0.5];
+    let transparent = [color[0], color[1], color[2], 0.0];
+    for contour in contours {
+        let count = contour.len();
+        // which side of each edge is outside depends on the fill rule and the other sub paths (e.g. holes), so it's
+        // tested just either side of the edge. edges with the fill (or nothing) on both sides don't get a fringe
+        let outwards: Vec<Option<[f32; 2]>> = (0..count)
+            .map(|i| {
+                let [a, b] = [contour[i], contour[(i + 1) % count]];
+                let edge_normal = normal(direction(a, b));
+                let middle = scale(add(a, b), 0.5);
+                let inside = |side: f32| {
+                    let sample = add(middle, scale(edge_normal, side * 0.25));
+                    fill_rule.contains(winding_number(contours.iter().copied(), sample))
+                };
+                match (inside(1.0), inside(-1.0)) {
+                    (false, true) => Some(edge_normal),
+                    (true, false) => Some(scale(edge_normal, -1.0)),
+                    _ => None,
+                }
+            })
+            .collect();
+        // where both edges at a point have a fringe they meet at a shared (mitered) offset, so there are no gaps
+        let offset = |i: usize, edge: [f32; 2]| -> [f32; 2] {
+            let previous = outwards[(i + count - 1) % count];
+            let next = outwards[i % count];
+            match (previous, next) {
+                (Some(previous), Some(next)) => {
+                    let sum = add(previous, next);
+                    let sum_length = length(sum);
+                    if sum_length < 1e-3 {
+                        return scale(edge, 0.5);
+                    }
+                    let miter = scale(sum, 1.0 / sum_length);
+                    scale(miter, (0.5 / dot(miter, next).max(0.25)).min(2.0))
+                }
+                _ => scale(edge, 0.5),
+            }
+        };
+        for i in 0..count {
+            let Some(outward) = outwards[i] else {
+                continue;
+            };
+            let [a, b] = [contour[i], contour[(i + 1) % count]];
+            let edge_a = mesh.vertex(a, edge_color);
+            let edge_b = mesh.vertex(b, edge_color);
+            let outer_a = mesh.vertex(add(a, offset(i, outward)), transparent);
+            let outer_b = mesh.vertex(add(b, offset(i + 1, outward)), transparent);
+            mesh.quad(edge_a, edge_b, outer_b, outer_a);
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use std::f32::consts::PI;
+
+use anchor_kit_core::primitives::polyline::{LineCap, LineJoin, Polyline, Stroke};
+
+use crate::{MeshVertex, ScreenInfo};
+
+// the stroke is a strip with 4 vertices across the line at every point:
+// outer edge (transparent), inner edge (opaque), inner edge (opaque), outer edge (transparent)
+// the outer edges are 1px further out than the inner ones, so the alpha fades over a pixel for anti-aliasing
+// joins and caps add their own triangles between the strips, with the same 1px fade around their edges
+// v0--v4
+// |  /|
+// v1--v5
+// |  /|
+// v2--v6
+// |  /|
+// v3--v7
+pub(crate) fn tessellate_polyline(
+    polyline: &Polyline,
+    screen_info: &ScreenInfo,
+    vertices: &mut Vec<MeshVertex>,
+    indices: &mut Vec<u32>,
+) {
+    let mut mesh = Mesh::new(vertices, indices, screen_info);
+
+    if let Some(area) = &polyline.area {
+        let color = area.color.to_rgba_f32();
+        // one trapezoid per segment, down (or up) to the baseline
+        for segment in polyline.points.windows(2) {
+            let [[x0, y0], [x1, y1]] = [segment[0], segment[1]];
+            let a = mesh.vertex([x0, y0], color);
+            let b = mesh.vertex([x1, y1], color);
+            let c = mesh.vertex([x1, area.baseline], color);
+            let d = mesh.vertex([x0, area.baseline], color);
+            mesh.quad(a, b, c, d);
+        }
+    }
+
+    let Some(mut stroker) = Stroker::new(&mut mesh, &polyline.stroke) else {
+        return;
+    };
+    let mut points: Vec<[f32; 2]> = vec![];
+    polyline.for_each_dash(|dash| {
+        without_repeats(dash, &mut points);
+        stroker.stroke(&points, false);
+    });
+}
+
+// repeated points have no direction, so they are skipped
+pub(crate) fn without_repeats(points: &[[f32; 2]], out: &mut Vec<[f32; 2]>) {
+    out.clear();
+    for point in points {
+        if out.last() != Some(point) {
+            out.push(*point);
+        }
+    }
+}
+
+pub(crate) struct Mesh<'a> {
+    vertices: &'a mut Vec<MeshVertex>,
+    indices: &'a mut Vec<u32>,
+    screen_size: [f32; 2],
+}
+
+impl<'a> Mesh<'a> {
+    pub(crate) fn new(
+        vertices: &'a mut Vec<MeshVertex>,
+        indices: &'a mut Vec<u32>,
+        screen_info: &ScreenInfo,
+    ) -> Self {
+        Self {
+            vertices,
+            indices,
+            screen_size: screen_info.size_px.map(|s| s as f32),
+        }
+    }
+
+    // takes the position in pixels and returns the index of the new vertex
+    pub(crate) fn vertex(&mut self, [x, y]: [f32; 2], color: [f32; 4]) -> u32 {
+        let index = self.vertices.len() as u32;
+        self.vertices.push(MeshVertex {
+            position: [x / self.screen_size[0], y / self.screen_size[1]],
+            color,
+        });
+        index
+    }
+
+    pub(crate) fn triangle(&mut self, a: u32, b: u32, c: u32) {
+        self.indices.extend_from_slice(&[a, b, c]);
+    }
+
+    pub(crate) fn quad(&mut self, a: u32, b: u32, c: u32, d: u32) {
+        self.indices.extend_from_slice(&[a, b, c, a, c, d]);
+    }
+}
+
+// vertices across the line where a segment starts or ends, on the side of the segment normal first:
+// outer edge, inner edge, inner edge, outer edge
+type CrossSection = [u32; 4];
+
+pub(crate) struct Stroker<'a, 'b> {
+    mesh: &'a mut Mesh<'b>,
+    stroke: &'a Stroke,
+    inner: f32, // distance from the center of the line to where the edge starts fading out
+    outer: f32, // distance to where the edge is fully transparent
+    opaque: [f32; 4],
+    transparent: [f32; 4],
+}
+
+impl<'a, 'b> Stroker<'a, 'b> {
+    // returns none when the stroke is invisible
+    pub(crate) fn new(mesh: &'a mut Mesh<'b>, stroke: &'a Stroke) -> Option<Self> {
+        if stroke.width <= 0.0 || stroke.color.a == 0 {
+            return None;
+        }
+        // lines thinner than a pixel are drawn a pixel wide and faded instead
+        let inner = (stroke.width / 2.0 - 0.5).max(0.0);
+        let mut color = stroke.color.to_rgba_f32();
+        color[3] *= stroke.width.min(1.0);
+        Some(Self {
+            mesh,
+            stroke,
+            inner,
+            outer: inner + 1.0,
+            opaque: color,
+            transparent: [color[0], color[1], color[2], 0.0],
+        })
+    }
+
+    // closed lines have a join at every point (including the first) instead of caps
+    pub(crate) fn stroke(&mut self, points: &[[f32; 2]], closed: bool) {
+        if closed && points.len() > 1 {
+            self.stroke_closed(points);
+            return;
+        }
+        let directions: Vec<[f32; 2]> = match points.len() {
+            0 => return,
+            // a single point is only visible with round or square caps, e.g. for dotted lines
+            1 if self.stroke.cap == LineCap::Butt => return,
+            1 => vec![[1.0, 0.0]],
+            _ => points.windows(2).map(|s| direction(s[0], s[1])).collect(),
+        };
+        let last = points.len() - 1;
+
+        let mut start = self.cap(points[0], scale(directions[0], -1.0), normal(directions[0]));
+        for (i, direction) in directions.iter().enumerate() {
+            let (end, next_start) = match i + 1 < last {
+                true => self.join(points[i + 1], *direction, directions[i + 1]),
+                false => {
+                    let end = self.cap(points[last], *direction, normal(*direction));
+                    (end, end)
+                }
+            };
+            self.segment(start, end);
+            start = next_start;
+        }
+    }
+
+    fn stroke_closed(&mut self, points: &[[f32; 2]]) {
+        let count = points.len();
+        let directions: Vec<[f32; 2]> = (0..count)
+            .map(|i| direction(points[i], points[(i + 1) % count]))
+            .collect();
+        let (last_end, mut start) = self.join(points[0], directions[count - 1], directions[0]);
+        for i in 0..count {
+            let (end, next_start) = match i + 1 < count {
+                true => self.join(points[i + 1], directions[i], directions[i + 1]),
+                false => (last_end, last_end),
+            };
+            self.segment(start, end);
+            start = next_start;
+        }
+    }
+
+    fn segment(&mut self, start: CrossSection, end: CrossSection) {
+        for band in 0..3 {
+            self.mesh
+                .quad(start[band], start[band + 1], end[band + 1], end[band]);
+        }
+    }
+
+    // returns the cross section for the segment going away from the point in the opposite direction of `outward`
+    fn cap(&mut self, point: [f32; 2], outward: [f32; 2], normal: [f32; 2]) -> CrossSection {
+        match self.stroke.cap {
+            LineCap::Butt | LineCap::Square => {
+                // the end is faded over a pixel like the sides, centered on where the line should end
+                let extension = match self.stroke.cap {
+                    LineCap::Square => self.inner + 0.5,
+                    _ => 0.0,
+                };
+                let base = add(point, scale(outward, extension - 0.5));
+                let tip = add(point, scale(outward, extension + 0.5));
+                let section = self.cross_section(base, normal, self.opaque);
+                let tip = self.cross_section(tip, normal, self.transparent);
+                for band in 0..3 {
+                    self.mesh
+                        .quad(section[band], section[band + 1], tip[band + 1], tip[band]);
+                }
+                section
+            }
+            LineCap::Round => {
+                // half circle from the normal side, around the end point, to the other side
+                let sign = cross(normal, outward).signum();
+                let (first, last) = self.arc(point, normal, PI * sign, self.outer);
+                [first.1, first.0, last.0, last.1]
+            }
+        }
+    }
+
+    // returns the cross sections where the previous segment ends and the next one starts
+    fn join(
+        &mut self,
+        point: [f32; 2],
+        previous: [f32; 2],
+        next: [f32; 2],
+    ) -> (CrossSection, CrossSection) {
+        let (previous_normal, next_normal) = (normal(previous), normal(next));
+        let sum = add(previous_normal, next_normal);
+        let sum_length = length(sum);
+        if sum_length < 1e-3 {
+            // the line doubles back on itself, so just end one segment and start the next
+            let end = self.cross_section(point, previous_normal, self.opaque);
+            let start = self.cross_section(point, next_normal, self.opaque);
+            return (end, start);
+        }
+
+        // the outside of the corner is opposite to the direction the line turns
+        let side = match cross(previous, next) > 0.0 {
+            true => -1.0,
+            false => 1.0,
+        };
+        let miter = scale(sum, 1.0 / sum_length);
+        let miter_length = 1.0 / dot(miter, previous_normal).max(1e-3);
+
+        // both segments meet at the same point on the inside of the corner
+        let inside_length = miter_length.min(Stroke::MITER_LIMIT);
+        let inside_edge = self.mesh.vertex(
+            add(point, scale(miter, -side * inside_length * self.inner)),
+            self.opaque,
+        );
+        let inside_outer_edge = self.mesh.vertex(
+            add(point, scale(miter, -side * inside_length * self.outer)),
+            self.transparent,
+        );
+
+        let outside_from = scale(previous_normal, side);
+        let outside_to = scale(next_normal, side);
+        let ((first_inner, first_outer), (last_inner, last_outer)) = match self.stroke.join {
+            LineJoin::Miter if miter_length <= Stroke::MITER_LIMIT => {
+                let corner = scale(miter, side * miter_length);
+                let inner_edge = self
+                    .mesh
+                    .vertex(add(point, scale(corner, self.inner)), self.opaque);
+                let outer_edge = self
+                    .mesh
+                    .vertex(add(point, scale(corner, self.outer)), self.transparent);
+                ((inner_edge, outer_edge), (inner_edge, outer_edge))
+            }
+            LineJoin::Round => {
+                let angle = cross(outside_from, outside_to).atan2(dot(outside_from, outside_to));
+                self.arc_with_inside(point, outside_from, angle, inside_edge)
+            }
+            // bevels are a round join with a single step
+            LineJoin::Miter | LineJoin::Bevel => {
+                let angle = cross(outside_from, outside_to).atan2(dot(outside_from, outside_to));
+                self.arc_steps_with_inside(point, outside_from, angle, 1, inside_edge)
+            }
+        };
+
+        match side > 0.0 {
+            true => (
+                [first_outer, first_inner, inside_edge, inside_outer_edge],
+                [last_outer, last_inner, inside_edge, inside_outer_edge],
+            ),
+            false => (
+                [inside_outer_edge, inside_edge, first_inner, first_outer],
+                [inside_outer_edge, inside_edge, last_inner, last_outer],
+            ),
+        }
+    }
+
+    // vertices on both sides of the point along the normal, in cross section order
+    fn cross_section(
+        &mut self,
+        point: [f32; 2],
+        normal: [f32; 2],
+        inner_color: [f32; 4],
+    ) -> CrossSection {
+        let (inner, outer) = (self.inner, self.outer);
+        [
+            self.mesh
+                .vertex(add(point, scale(normal, outer)), self.transparent),
+            self.mesh
+                .vertex(add(point, scale(normal, inner)), inner_color),
+            self.mesh
+                .vertex(add(point, scale(normal, -inner)), inner_color),
+            self.mesh
+                .vertex(add(point, scale(normal, -outer)), self.transparent),
+        ]
+    }
+
+    // fan around the point from the `from` direction, rotating by `angle`
+    // returns the (inner edge, outer edge) vertices at the start and the end of the arc
+    fn arc(
+        &mut self,
+        point: [f32; 2],
+        from: [f32; 2],
+        angle: f32,
+        radius: f32,
+    ) -> ((u32, u32), (u32, u32)) {
+        let steps = arc_steps(angle, radius);
+        let center = self.mesh.vertex(point, self.opaque);
+        self.arc_around(point, from, angle, steps, center)
+    }
+
+    fn arc_with_inside(
+        &mut self,
+        point: [f32; 2],
+        from: [f32; 2],
+        angle: f32,
+        inside_edge: u32,
+    ) -> ((u32, u32), (u32, u32)) {
+        let steps = arc_steps(angle, self.outer);
+        self.arc_steps_with_inside(point, from, angle, steps, inside_edge)
+    }
+
+    // joins also fill the triangles between the center, the inside corner and the ends of the arc
+    fn arc_steps_with_inside(
+        &mut self,
+        point: [f32; 2],
+        from: [f32; 2],
+        angle: f32,
+        steps: u32,
+        inside_edge: u32,
+    ) -> ((u32, u32), (u32, u32)) {
+        let center = self.mesh.vertex(point, self.opaque);
+        let (first, last) = self.arc_around(point, from, angle, steps, center);
+        self.mesh.triangle(center, inside_edge, first.0);
+        self.mesh.triangle(center, last.0, inside_edge);
+        (first, last)
+    }
+
+    fn arc_around(
+        &mut self,
+        point: [f32; 2],
+        from: [f32; 2],
+        angle: f32,
+        steps: u32,
+        center: u32,
+    ) -> ((u32, u32), (u32, u32)) {
+        let mut previous: Option<(u32, u32)> = None;
+        let mut first = (0, 0);
+        for step in 0..=steps {
+            let direction = rotate(from, angle * step as f32 / steps as f32);
+            let inner_edge = self
+                .mesh
+                .vertex(add(point, scale(direction, self.inner)), self.opaque);
+            let outer_edge = self
+                .mesh
+                .vertex(add(point, scale(direction, self.outer)), self.transparent);
+            match previous {
+                Some((previous_inner, previous_outer)) => {
+                    self.mesh.triangle(center, previous_inner, inner_edge);
+                    self.mesh
+                        .quad(previous_inner, inner_edge, outer_edge, previous_outer);
+                }
+                None => first = (inner_edge, outer_edge),
+            }
+            previous = Some((inner_edge, outer_edge));
+        }
+        (first, previous.unwrap_or(first))
+    }
+}
+
+// enough steps that the arc is never more than a fraction of a pixel away from a true circle
+fn arc_steps(angle: f32, radius: f32) -> u32 {
+    let max_step = (2.0 * (1.0 - 0.2 / radius.max(0.2)).acos()).clamp(0.05, PI / 4.0);
+    (angle.abs() / max_step).ceil().max(1.0) as u32
+}
+
+pub(crate) fn direction(from: [f32; 2], to: [f32; 2]) -> [f32; 2] {
+    let d = [to[0] - from[0], to[1] - from[1]];
+    scale(d, 1.0 / length(d))
+}
+
+// perpendicular to the direction, pointing to the right of the line in screen coordinates (y down)
+pub(crate) fn normal([x, y]: [f32; 2]) -> [f32; 2] {
+    [-y, x]
+}
+
+fn rotate([x, y]: [f32; 2], angle: f32) -> [f32; 2] {
+    let (sin, cos) = angle.sin_cos();
+    [x * cos - y * sin, x * sin + y * cos]
+}
+
+pub(crate) fn add(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] + b[0], a[1] + b[1]]
+}
+
+pub(crate) fn scale(a: [f32; 2], s: f32) -> [f32; 2] {
+    [a[0] * s, a[1] * s]
+}
+
+pub(crate) fn dot(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+fn cross(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[1] - a[1] * b[0]
+}
+
+pub(crate) fn length(a: [f32; 2]) -> f32 {
+    dot(a, a).sqrt()
+}
@@ -36,6 +36,44 @@ fn vs_main(
     return out;
 }
 
+// one instance per heatmap cell, the quad's corners come from the vertex index so no vertex buffer is needed
+// cells are shaded by fs_main, exactly like rectangles without a border
+struct CellInstance {
+    @location(0) position: vec2<f32>, // normalized pos of the top left corner
+    @location(1) size: vec2<f32>, // normalized
+    @location(2) scale: vec2<f32>,
+    @location(3) radius_local: f32, // in local units (0-0.5)
+    @location(4) color: vec4<f32>, // r, g, b, a
+}
+
+@vertex
+fn vs_cell(
+    @builtin(vertex_index) vertex_index: u32,
+    cell: CellInstance
+) -> VertexOutput {
+    // same triangles as rectangles: v0 -> v2 -> v1, and v0 -> v3 -> v2
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let position = cell.position + corner * cell.size;
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(position.x * 2.0 - 1.0, 1.0 - position.y * 2.0, 0.0, 1.0);
+    out.local_uv = corner;
+    out.background_color = cell.color;
+    out.border_radius_local = vec4<f32>(cell.radius_local);
+    out.border_width_local = 0.0;
+    out.border_color = vec4<f32>(0.0, 0.0, 0.0, 1.0); // the default style's border colour, it tints the edge like it does for rectangles
+    out.scale = cell.scale;
+    return out;
+}
+
 struct SDFInput {
     position: vec2<f32>, // normalized
     half_size: vec2<f32>, // SDFs work with half sizes
@@ -46,8 +84,9 @@ struct SDFInput {
 fn sdf_rounded(in: SDFInput) -> f32 {
     var radius_to_choose: f32;
     // we need to pick which radius we want to calculate the sdf on based on the position of the pixel (which quadrent of the box we are in)
+    // local uv y increases downwards (top of the element is negative y here)
     if in.position[0] >= 0.0 {
-        if in.position[1] >= 0.0 {
+        if in.position[1] < 0.0 {
             // top-right
             radius_to_choose = in.border_radius[1];
         } else {
@@ -55,7 +94,7 @@ fn sdf_rounded(in: SDFInput) -> f32 {
             radius_to_choose = in.border_radius[2];
         }
     } else {
-        if in.position[1] >= 0.0 {
+        if in.position[1] < 0.0 {
             // top-left
             radius_to_choose = in.border_radius[0];
         } else {
@@ -126,6 +165,12 @@ fn fs_image(in: VertexOutput) -> @location(0) vec4<f32> {
 
     var output_color = vec4<f32>(0.0, 0.0, 0.0, 0.0);
     output_color += border * in.border_color;
-    output_color += fill * textureSample(t_diffuse, s_diffuse, in.local_uv); // use the texture from the bindings for the fill colour
+    output_color += fill * textureSample(t_diffuse, s_diffuse, in.local_uv) * in.background_color.a; // use the texture from the bindings for the fill colour (background alpha is the image opacity)
     return output_color;
 }
+
+// large cell grids are a texture with a texel per cell, drawn without the rounded edge so the outer cells keep their colour
+@fragment
+fn fs_cell_texture(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(t_diffuse, s_diffuse, in.local_uv);
+}
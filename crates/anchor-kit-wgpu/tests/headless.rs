@@ -0,0 +1,52 @@
+use anchor_kit_core::{
+    primitives::{color::Color, rectangle::Rectangle},
+    render::RenderList,
+    style::Style,
+};
+use anchor_kit_wgpu::headless::{HeadlessError, HeadlessRenderer};
+
+// machines without a gpu or a software adapter (e.g. some CI runners) can't run these, so they are skipped there
+fn renderer() -> Option<HeadlessRenderer> {
+    match HeadlessRenderer::new() {
+        Ok(renderer) => Some(renderer),
+        Err(err) => {
+            eprintln!("skipping, no adapter: {err}");
+            None
+        }
+    }
+}
+
+#[test]
+fn renders_a_rectangle() {
+    let Some(mut renderer) = renderer() else {
+        return;
+    };
+    let render_list = RenderList {
+        rectangles: vec![Rectangle::new(
+            [8, 8],
+            [16, 16],
+            Some(Style {
+                background_color: Color::RED,
+                ..Default::default()
+            }),
+        )],
+        ..Default::default()
+    };
+    let image = renderer
+        .render_to_image([32, 32], Color::BLACK, &render_list)
+        .unwrap();
+    assert_eq!(image.dimensions(), (32, 32));
+    assert_eq!(image.get_pixel(16, 16).0, [255, 0, 0, 255]);
+    assert_eq!(image.get_pixel(2, 2).0, [0, 0, 0, 255]);
+}
+
+#[test]
+fn empty_size_is_an_error() {
+    let Some(mut renderer) = renderer() else {
+        return;
+    };
+    for size in [[0, 32], [32, 0], [u32::MAX, 1]] {
+        let result = renderer.render_to_image(size, Color::BLACK, &RenderList::default());
+        assert!(matches!(result, Err(HeadlessError::InvalidSize(s)) if s == size));
+    }
+}
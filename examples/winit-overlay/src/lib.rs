@@ -122,9 +122,8 @@ impl State {
     }
 
     fn handle_key(&self, event_loop: &ActiveEventLoop, code: KeyCode, is_pressed: bool) {
-        match (code, is_pressed) {
-            (KeyCode::Escape, true) => event_loop.exit(),
-            _ => {}
+        if let (KeyCode::Escape, true) = (code, is_pressed) {
+            event_loop.exit()
         }
     }
 
@@ -283,7 +282,7 @@ impl State {
                                         }),
                                     );
                                     ui.text(
-                                        "MPH".to_string(),
+                                        "MPH",
                                         Some(Style {
                                             align_x: anchor_kit_core::style::Align::Middle,
                                             margin: Insets {
@@ -546,6 +545,12 @@ impl App {
     }
 }
 
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ApplicationHandler<State> for App {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         #[allow(unused_mut)]
@@ -107,9 +107,8 @@ impl State {
     }
 
     fn handle_key(&self, event_loop: &ActiveEventLoop, code: KeyCode, is_pressed: bool) {
-        match (code, is_pressed) {
-            (KeyCode::Escape, true) => event_loop.exit(),
-            _ => {}
+        if let (KeyCode::Escape, true) = (code, is_pressed) {
+            event_loop.exit()
         }
     }
 
@@ -191,7 +190,7 @@ impl State {
                                 }),
                                 |ui| {
                                     ui.text(
-                                        "row 1".to_string(),
+                                        "row 1",
                                         Some(Style {
                                             margin: Insets {
                                                 top: 5,
@@ -240,7 +239,7 @@ impl State {
                                 }),
                                 |ui| {
                                     ui.text(
-                                        "row 2".to_string(),
+                                        "row 2",
                                         Some(Style {
                                             margin: Insets {
                                                 top: 5,
@@ -289,7 +288,7 @@ impl State {
                                 }),
                                 |ui| {
                                     ui.text(
-                                        "row 3".to_string(),
+                                        "row 3",
                                         Some(Style {
                                             margin: Insets {
                                                 top: 5,
@@ -344,7 +343,7 @@ impl State {
                         }),
                         |ui| {
                             ui.text(
-                                "col 1".to_string(),
+                                "col 1",
                                 Some(Style {
                                     margin: Insets {
                                         top: 5,
@@ -393,7 +392,7 @@ impl State {
                         }),
                         |ui| {
                             ui.text(
-                                "col 2".to_string(),
+                                "col 2",
                                 Some(Style {
                                     margin: Insets {
                                         top: 5,
@@ -442,7 +441,7 @@ impl State {
                         }),
                         |ui| {
                             ui.text(
-                                "col 3".to_string(),
+                                "col 3",
                                 Some(Style {
                                     margin: Insets {
                                         top: 5,
@@ -509,7 +508,7 @@ impl State {
                                 }),
                                 |ui| {
                                     ui.text(
-                                        "justify end - align start".to_string(),
+                                        "justify end - align start",
                                         Some(Style {
                                             margin: Insets {
                                                 top: 5,
@@ -562,7 +561,7 @@ impl State {
                                 }),
                                 |ui| {
                                     ui.text(
-                                        "justify end - align middle".to_string(),
+                                        "justify end - align middle",
                                         Some(Style {
                                             margin: Insets {
                                                 top: 5,
@@ -615,7 +614,7 @@ impl State {
                                 }),
                                 |ui| {
                                     ui.text(
-                                        "justify end - align end".to_string(),
+                                        "justify end - align end",
                                         Some(Style {
                                             margin: Insets {
                                                 top: 5,
@@ -698,6 +697,12 @@ impl App {
     }
 }
 
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ApplicationHandler<State> for App {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         #[allow(unused_mut)]